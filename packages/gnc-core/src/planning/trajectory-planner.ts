@@ -69,12 +69,22 @@ declare class WasmTrajectoryGraphBuilder {
 declare function benchmark_algorithms(
   graph: WasmSparseGraph,
   source: number,
-  iterations: number
+  iterations: number,
+  contractionHierarchy?: boolean
 ): {
   enhancedTimeMs: number
   dijkstraTimeMs: number
   speedupFactor: number
+  preprocessTimeMs: number
   iterations: number
+  // Present when contractionHierarchy is true
+  p2pDijkstraTimeMs?: number
+  bidirectionalTimeMs?: number
+  chQueryTimeMs?: number
+  chPreprocessTimeMs?: number
+  chSpeedupFactor?: number
+  chBreakEvenQueries?: number
+  chShortcuts?: number
 }
 
 /**
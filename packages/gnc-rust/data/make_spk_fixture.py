@@ -0,0 +1,120 @@
+#!/usr/bin/env python3
+"""Generate data/test_type2.bsp, the SPK Type 2 fixture used by src/spk.rs tests.
+
+The kernel is synthetic: each segment is a Chebyshev fit of a circular orbit
+whose closed form is repeated in the Rust tests, so the reader can be checked
+against exact values. Layout follows the NAIF DAF and SPK Required Reading
+(little-endian IEEE, ND = 2, NI = 6).
+
+Usage: python3 make_spk_fixture.py   (writes next to this script)
+"""
+
+import math
+import os
+import struct
+
+ET0 = 7.5e8  # TDB seconds past J2000
+DAY = 86400.0
+SPAN = 8 * DAY
+DEGREE = 13
+
+AU_KM = 1.495978707e8
+
+
+def circle(radius_km, period_days, phase, inclination_deg):
+    w = 2 * math.pi / (period_days * DAY)
+    ci = math.cos(math.radians(inclination_deg))
+    si = math.sin(math.radians(inclination_deg))
+
+    def f(et):
+        a = w * (et - ET0) + phase
+        x, y = radius_km * math.cos(a), radius_km * math.sin(a)
+        return (x, y * ci, y * si)
+
+    return f
+
+
+# (target, center, function, records, segment splits)
+SEGMENTS = [
+    (10, 0, circle(7.0e5, 4332.6, 0.3, 1.3), 1),
+    (3, 0, circle(AU_KM, 365.25, 1.0, 0.0), 2),
+    (399, 3, circle(4671.0, 27.321661, math.pi + 0.5, 5.1), 4),
+    (301, 3, circle(379729.0, 27.321661, 0.5, 5.1), 4),
+]
+
+
+def cheb_fit(f, mid, radius, degree):
+    n = degree + 1
+    nodes = [math.cos(math.pi * (k + 0.5) / n) for k in range(n)]
+    values = [f(mid + radius * s) for s in nodes]
+    coeffs = []
+    for axis in range(3):
+        c = []
+        for j in range(n):
+            acc = sum(values[k][axis] * math.cos(math.pi * j * (k + 0.5) / n) for k in range(n))
+            c.append(acc * (1.0 if j == 0 else 2.0) / n)
+        coeffs.append(c)
+    return coeffs
+
+
+def type2_segment(f, start, end, records):
+    intlen = (end - start) / records
+    data = []
+    for r in range(records):
+        mid = start + (r + 0.5) * intlen
+        radius = 0.5 * intlen
+        cx, cy, cz = cheb_fit(f, mid, radius, DEGREE)
+        data += [mid, radius] + cx + cy + cz
+    rsize = 2 + 3 * (DEGREE + 1)
+    return data + [start, intlen, float(rsize), float(records)]
+
+
+def main():
+    # Segment data: Moon coverage split in two segments to exercise lookup
+    segs = []
+    for target, center, f, records in SEGMENTS:
+        if target == 301:
+            half = ET0 + SPAN / 2
+            segs.append((target, center, ET0, half, type2_segment(f, ET0, half, records // 2)))
+            segs.append((target, center, half, ET0 + SPAN, type2_segment(f, half, ET0 + SPAN, records // 2)))
+        else:
+            segs.append((target, center, ET0, ET0 + SPAN, type2_segment(f, ET0, ET0 + SPAN, records)))
+
+    words = []
+    summaries = []
+    addr = 3 * 128 + 1  # after file, summary and name records
+    for target, center, start, end, data in segs:
+        summaries.append((start, end, target, center, 1, 2, addr, addr + len(data) - 1))
+        words += data
+        addr += len(data)
+
+    file_rec = bytearray(1024)
+    file_rec[0:8] = b"DAF/SPK "
+    struct.pack_into("<ii", file_rec, 8, 2, 6)
+    file_rec[16:76] = b"gnc_rust synthetic Type 2 fixture".ljust(60)
+    struct.pack_into("<iii", file_rec, 76, 2, 2, addr)
+    file_rec[88:96] = b"LTL-IEEE"
+    ftp = b"FTPSTR:\r:\n:\r\n:\r\x00:\x81:\x10\xce:ENDFTP"
+    file_rec[699:699 + len(ftp)] = ftp
+
+    summ_rec = bytearray(1024)
+    struct.pack_into("<ddd", summ_rec, 0, 0.0, 0.0, float(len(summaries)))
+    for i, (start, end, target, center, frame, typ, a0, a1) in enumerate(summaries):
+        struct.pack_into("<dd6i", summ_rec, 24 + 40 * i, start, end, target, center, frame, typ, a0, a1)
+
+    name_rec = bytearray(b" " * 1024)
+    for i, s in enumerate(summaries):
+        name = ("SYNTHETIC %d WRT %d" % (s[2], s[3])).encode().ljust(40)
+        name_rec[40 * i:40 * (i + 1)] = name
+
+    body = struct.pack("<%dd" % len(words), *words)
+    body += b"\0" * (-len(body) % 1024)
+
+    path = os.path.join(os.path.dirname(os.path.abspath(__file__)), "test_type2.bsp")
+    with open(path, "wb") as fh:
+        fh.write(bytes(file_rec) + bytes(summ_rec) + bytes(name_rec) + body)
+    print("wrote %s (%d bytes)" % (path, 3072 + len(body)))
+
+
+if __name__ == "__main__":
+    main()
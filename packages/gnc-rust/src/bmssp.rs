@@ -0,0 +1,476 @@
+//! ID: WASM-BMSSP-001
+//! Requirement: Single-source shortest paths on sparse directed graphs with
+//!   non-negative weights in O(m log^{2/3} n) time, the bound sssp.rs promises.
+//! Purpose: Selectable alternative to binary-heap Dijkstra in EnhancedSSSpSolver.
+//! Rationale: Duan et al.'s bounded multi-source SSSP (BMSSP) avoids keeping
+//!   every frontier vertex sorted. Each recursion level first runs k relaxation
+//!   rounds (FindPivots), so only ≈|S|/k pivots with large shortest-path trees
+//!   enter the next level. The frontier lives in a block-linked structure that
+//!   only partially sorts: Insert and BatchPrepend are cheap, and Pull returns
+//!   the next M smallest keys without a total order.
+//! Inputs: SparseGraph (CSR, weights ≥ 0), source node
+//! Outputs: distances, predecessors (−1 for the source and unreachable nodes),
+//!   nodes settled and edges relaxed
+//! Assumptions/Limits: the paper's distinct-path-length assumption is met
+//!   with its tie-break: labels are ordered by (distance, hop count, node), so a
+//!   predecessor always precedes its successor even over zero-weight edges.
+//!   Vertices already returned as complete are never queued again. The frontier
+//!   structure deletes lazily (stale copies are skipped when pulled).
+//!   Constant degree is not enforced: results stay exact, but the bound assumes
+//!   bounded out-degree.
+//! References: Duan, Mao, Mao, Shu & Yin (2025) "Breaking the Sorting Barrier
+//!   for Directed Single-Source Shortest Paths", STOC 2025 (arXiv:2504.17033),
+//!   Algorithms 1–3 and Lemma 3.3
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
+
+use rustc_hash::FxHashMap;
+
+use crate::sssp::{SearchStats, SparseGraph};
+
+/// Totally ordered vertex label (distance, hop count, node).
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Key {
+    dist: f64,
+    hops: u32,
+    node: u32,
+}
+
+impl Eq for Key {}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dist.total_cmp(&other.dist)
+            .then(self.hops.cmp(&other.hops))
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Upper bound above every label
+const UNBOUNDED: Key = Key { dist: f64::INFINITY, hops: u32::MAX, node: u32::MAX };
+
+/// 2^e, saturating
+fn pow2(e: u64) -> usize {
+    if e >= usize::BITS as u64 - 1 { usize::MAX } else { 1 << e }
+}
+
+/// Lemma 3.3 structure: D₀ holds batch-prepended blocks (front = smallest),
+/// D₁ holds inserted blocks keyed by upper bound. Blocks hold at most M keys.
+struct BlockList {
+    m: usize,
+    bound: Key,
+    d0: VecDeque<Vec<Key>>,
+    d1: BTreeMap<(Key, u64), Vec<Key>>,
+    next_id: u64,
+    live: FxHashMap<u32, Key>,
+}
+
+impl BlockList {
+    fn new(m: usize, bound: Key) -> Self {
+        BlockList { m, bound, d0: VecDeque::new(), d1: BTreeMap::new(), next_id: 0, live: FxHashMap::default() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.live.is_empty()
+    }
+
+    fn is_live(&self, k: &Key) -> bool {
+        self.live.get(&k.node) == Some(k)
+    }
+
+    /// Record `k` if it improves on the node's current key.
+    fn improve(&mut self, k: Key) -> bool {
+        match self.live.get(&k.node) {
+            Some(old) if *old <= k => false,
+            _ => {
+                self.live.insert(k.node, k);
+                true
+            }
+        }
+    }
+
+    fn insert(&mut self, k: Key) {
+        if !self.improve(k) {
+            return;
+        }
+        let slot = match self.d1.range((k, 0)..).next() {
+            Some((&slot, _)) => slot,
+            None => {
+                self.next_id += 1;
+                let slot = (self.bound, self.next_id);
+                self.d1.insert(slot, Vec::new());
+                slot
+            }
+        };
+        let block = self.d1.get_mut(&slot).unwrap();
+        block.push(k);
+        if block.len() > self.m {
+            self.split(slot);
+        }
+    }
+
+    /// Split an oversized D₁ block at its median.
+    fn split(&mut self, slot: (Key, u64)) {
+        let mut left = self.d1.remove(&slot).unwrap();
+        let mid = left.len() / 2;
+        left.select_nth_unstable(mid);
+        let right = left.split_off(mid);
+        let upper = *left.iter().max().unwrap();
+        self.next_id += 1;
+        self.d1.insert((upper, self.next_id), left);
+        self.d1.insert(slot, right);
+    }
+
+    /// Prepend keys that are all smaller than anything in D₁.
+    fn batch_prepend(&mut self, keys: Vec<Key>) {
+        let mut keys: Vec<Key> = keys.into_iter().filter(|&k| self.improve(k)).collect();
+        if keys.len() <= self.m {
+            if !keys.is_empty() {
+                self.d0.push_front(keys);
+            }
+            return;
+        }
+        let mut blocks = Vec::new();
+        split_ordered(&mut keys, self.m.div_ceil(2), &mut blocks);
+        for block in blocks.into_iter().rev() {
+            self.d0.push_front(block);
+        }
+    }
+
+    /// Smallest live key of an ordered block sequence: the minimum of the
+    /// first block that still holds a live key
+    fn first_live<'a>(&self, mut blocks: impl Iterator<Item = &'a Vec<Key>>) -> Option<Key> {
+        blocks.find_map(|b| b.iter().filter(|k| self.is_live(k)).min().copied())
+    }
+
+    /// Remove and return up to M smallest nodes, plus a bound that separates
+    /// them from what remains (`bound` once the structure is empty).
+    fn pull(&mut self) -> Option<(Key, Vec<usize>)> {
+        if self.is_empty() {
+            return None;
+        }
+        // Prefixes of D₀ and D₁ with at least M live keys each (or all of them)
+        let mut got: Vec<(Key, bool)> = Vec::new();
+        let mut taken = 0;
+        while taken < self.m {
+            let Some(block) = self.d0.pop_front() else { break };
+            for k in block {
+                if self.is_live(&k) {
+                    got.push((k, false));
+                    taken += 1;
+                }
+            }
+        }
+        let mut last_slot = None;
+        taken = 0;
+        while taken < self.m {
+            let Some((slot, block)) = self.d1.pop_first() else { break };
+            last_slot = Some(slot);
+            for k in block {
+                if self.is_live(&k) {
+                    got.push((k, true));
+                    taken += 1;
+                }
+            }
+        }
+
+        let rest = if got.len() > self.m {
+            got.select_nth_unstable_by(self.m, |a, b| a.0.cmp(&b.0));
+            got.split_off(self.m)
+        } else {
+            Vec::new()
+        };
+        let (mut rest0, mut rest1) = (Vec::new(), Vec::new());
+        for (k, from_d1) in rest {
+            if from_d1 { rest1.push(k) } else { rest0.push(k) }
+        }
+        let next = [
+            rest0.iter().chain(&rest1).min().copied(),
+            self.first_live(self.d0.iter()),
+            self.first_live(self.d1.values()),
+        ];
+        if !rest0.is_empty() {
+            self.d0.push_front(rest0);
+        }
+        if let (false, Some(slot)) = (rest1.is_empty(), last_slot) {
+            let over = rest1.len() > self.m;
+            self.d1.insert(slot, rest1);
+            if over {
+                self.split(slot);
+            }
+        }
+
+        let nodes = got.iter().map(|(k, _)| {
+            self.live.remove(&k.node);
+            k.node as usize
+        }).collect();
+        let bound = next.into_iter().flatten().min().unwrap_or(self.bound);
+        Some((bound, nodes))
+    }
+}
+
+/// Split keys into consecutive blocks of at most `size`, in key order
+/// between blocks (unordered within), by recursive median selection.
+fn split_ordered(keys: &mut [Key], size: usize, out: &mut Vec<Vec<Key>>) {
+    if keys.len() <= size {
+        out.push(keys.to_vec());
+        return;
+    }
+    let mid = keys.len() / 2;
+    keys.select_nth_unstable(mid);
+    let (left, right) = keys.split_at_mut(mid);
+    split_ordered(left, size, out);
+    split_ordered(right, size, out);
+}
+
+/// Stamp-based membership set over node ids, cleared in O(1)
+struct Marks {
+    stamp: Vec<u32>,
+    epoch: u32,
+}
+
+impl Marks {
+    fn new(n: usize) -> Self {
+        Marks { stamp: vec![0; n], epoch: 0 }
+    }
+
+    fn clear(&mut self) {
+        self.epoch += 1;
+    }
+
+    /// Insert; false if already present
+    fn insert(&mut self, v: usize) -> bool {
+        let fresh = self.stamp[v] != self.epoch;
+        self.stamp[v] = self.epoch;
+        fresh
+    }
+
+    fn contains(&self, v: usize) -> bool {
+        self.stamp[v] == self.epoch
+    }
+}
+
+struct Bmssp<'g> {
+    graph: &'g SparseGraph,
+    k: usize,
+    t: u64,
+    dist: Vec<f64>,
+    hops: Vec<u32>,
+    pred: Vec<i32>,
+    complete: Vec<bool>,
+    in_w: Marks,
+    in_s: Marks,
+    in_layer: Marks,
+    seen: Marks,
+    stats: SearchStats,
+}
+
+impl Bmssp<'_> {
+    fn key(&self, v: usize) -> Key {
+        Key { dist: self.dist[v], hops: self.hops[v], node: v as u32 }
+    }
+
+    /// Relax (u, v). Returns v's candidate key when it is ≤ v's label
+    /// (the paper's non-strict test), lowering the label if strictly better.
+    fn relax(&mut self, u: usize, v: usize, w: f64) -> Option<Key> {
+        self.stats.edges_relaxed += 1;
+        let cand = Key { dist: self.dist[u] + w, hops: self.hops[u] + 1, node: v as u32 };
+        let current = self.key(v);
+        if cand > current {
+            return None;
+        }
+        if cand < current {
+            self.dist[v] = cand.dist;
+            self.hops[v] = cand.hops;
+            self.pred[v] = u as i32;
+        }
+        Some(cand)
+    }
+
+    fn complete_all(&mut self, nodes: &mut Vec<usize>) {
+        nodes.retain(|&v| !std::mem::replace(&mut self.complete[v], true));
+    }
+
+    /// Algorithm 2: Dijkstra from a single vertex, stopped after k + 1 vertices.
+    fn base_case(&mut self, bound: Key, x: usize) -> (Key, Vec<usize>) {
+        self.seen.clear();
+        let mut u0 = Vec::new();
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse(self.key(x)));
+        while u0.len() < self.k + 1 {
+            let Some(Reverse(ku)) = heap.pop() else { break };
+            let u = ku.node as usize;
+            if ku != self.key(u) || !self.seen.insert(u) {
+                continue;
+            }
+            u0.push(u);
+            self.stats.nodes_visited += 1;
+            for (v, w) in self.graph.edges(u) {
+                if let Some(kv) = self.relax(u, v, w) {
+                    if kv < bound && !self.seen.contains(v) && !self.complete[v] {
+                        heap.push(Reverse(kv));
+                    }
+                }
+            }
+        }
+        let (bound, mut u0) = if u0.len() <= self.k {
+            (bound, u0)
+        } else {
+            let top = u0.iter().map(|&v| self.key(v)).max().unwrap();
+            (top, u0.into_iter().filter(|&v| self.key(v) < top).collect())
+        };
+        self.complete_all(&mut u0);
+        (bound, u0)
+    }
+
+    /// Algorithm 1: k rounds of relaxation from S below `bound`. Returns the
+    /// pivots (roots of shortest-path trees with ≥ k vertices) and W.
+    fn find_pivots(&mut self, bound: Key, s: &[usize]) -> (Vec<usize>, Vec<usize>) {
+        self.in_w.clear();
+        self.in_s.clear();
+        let mut w_set: Vec<usize> = s.to_vec();
+        for &x in s {
+            self.in_w.insert(x);
+            self.in_s.insert(x);
+        }
+        let mut frontier = s.to_vec();
+        for _ in 0..self.k {
+            self.in_layer.clear();
+            let mut next = Vec::new();
+            for &u in &frontier {
+                for (v, w) in self.graph.edges(u) {
+                    if let Some(kv) = self.relax(u, v, w) {
+                        if kv < bound && self.in_layer.insert(v) {
+                            next.push(v);
+                            if self.in_w.insert(v) {
+                                w_set.push(v);
+                            }
+                        }
+                    }
+                }
+            }
+            if w_set.len() > self.k * s.len() {
+                return (s.to_vec(), w_set);
+            }
+            frontier = next;
+        }
+
+        // Forest of tight predecessor edges inside W; count tree sizes per
+        // root. A vertex whose chain cannot be traced back to S makes the
+        // forest uncertain, and P = S is always a valid (if slower) answer.
+        let mut size: FxHashMap<usize, usize> = FxHashMap::default();
+        for &v in &w_set {
+            let mut u = v;
+            while !self.in_s.contains(u) {
+                match self.tight_parent(u) {
+                    Some(p) if self.in_w.contains(p) => u = p,
+                    _ => return (s.to_vec(), w_set),
+                }
+            }
+            *size.entry(u).or_insert(0) += 1;
+        }
+        let pivots = s.iter().copied().filter(|x| size.get(x).copied().unwrap_or(0) >= self.k).collect();
+        (pivots, w_set)
+    }
+
+    /// The predecessor of u if its edge still produces u's exact label.
+    fn tight_parent(&self, u: usize) -> Option<usize> {
+        let p = usize::try_from(self.pred[u]).ok()?;
+        let tight = self.hops[p].checked_add(1) == Some(self.hops[u])
+            && self.graph.edges(p).any(|(v, w)| v == u && self.dist[p] + w == self.dist[u]);
+        tight.then_some(p)
+    }
+
+    /// Algorithm 3: bounded multi-source shortest paths at recursion level l.
+    fn recurse(&mut self, level: u64, bound: Key, s: Vec<usize>) -> (Key, Vec<usize>) {
+        if level == 0 {
+            return match s.first() {
+                Some(&x) => self.base_case(bound, x),
+                None => (bound, Vec::new()),
+            };
+        }
+        let (pivots, w_set) = self.find_pivots(bound, &s);
+
+        let mut d = BlockList::new(pow2((level - 1) * self.t), bound);
+        let mut last = bound;
+        for &x in &pivots {
+            let kx = self.key(x);
+            last = last.min(kx);
+            d.insert(kx);
+        }
+        let limit = self.k.saturating_mul(pow2(level * self.t));
+        let mut u = Vec::new();
+        while u.len() < limit {
+            let Some((bi, mut si)) = d.pull() else { break };
+            si.retain(|&x| !self.complete[x]);
+            let (sub_bound, ui) = self.recurse(level - 1, bi, si.clone());
+            last = sub_bound;
+
+            let mut prepend = Vec::new();
+            for &x in &ui {
+                for (v, w) in self.graph.edges(x) {
+                    if let Some(kv) = self.relax(x, v, w) {
+                        if self.complete[v] {
+                            continue;
+                        }
+                        if kv >= bi && kv < bound {
+                            d.insert(kv);
+                        } else if kv >= sub_bound && kv < bi {
+                            prepend.push(kv);
+                        }
+                    }
+                }
+            }
+            for &x in &si {
+                let kx = self.key(x);
+                if !self.complete[x] && kx >= sub_bound && kx < bi {
+                    prepend.push(kx);
+                }
+            }
+            d.batch_prepend(prepend);
+            u.extend(ui);
+        }
+
+        let bound = last.min(bound);
+        let mut from_w: Vec<usize> = w_set.into_iter().filter(|&x| self.key(x) < bound).collect();
+        self.complete_all(&mut from_w);
+        u.extend(from_w);
+        (bound, u)
+    }
+}
+
+/// Shortest-path distances and predecessors from `source` with BMSSP.
+pub(crate) fn bmssp(graph: &SparseGraph, source: usize) -> (Vec<f64>, Vec<i32>, SearchStats) {
+    let n = graph.node_count();
+    let log_n = (n.max(2) as f64).log2();
+    // k = ⌊log^{1/3} n⌋, t = ⌊log^{2/3} n⌋, top level ⌈log n / t⌉
+    let k = (log_n.cbrt().floor() as usize).max(1);
+    let t = (log_n.powf(2.0 / 3.0).floor() as u64).max(1);
+    let levels = (log_n / t as f64).ceil() as u64;
+
+    let mut run = Bmssp {
+        graph,
+        k,
+        t,
+        dist: vec![f64::INFINITY; n],
+        hops: vec![u32::MAX; n],
+        pred: vec![-1; n],
+        complete: vec![false; n],
+        in_w: Marks::new(n),
+        in_s: Marks::new(n),
+        in_layer: Marks::new(n),
+        seen: Marks::new(n),
+        stats: SearchStats::default(),
+    };
+    run.dist[source] = 0.0;
+    run.hops[source] = 0;
+    run.recurse(levels, UNBOUNDED, vec![source]);
+    (run.dist, run.pred, run.stats)
+}
@@ -0,0 +1,90 @@
+//! ID: WASM-CONST-001
+//! Requirement: Provide gravitational parameters and reference radii for the
+//!   bodies the Rust dynamics models support.
+//! Purpose: Single source of truth in the crate, mirroring gnc-core's
+//!   math/constants.ts so TS and WASM propagations use identical values.
+//! Rationale: Perturbation models (zonals, shadows, body-fixed frames) need the
+//!   body radius consistent with the μ and coefficients they were fitted with.
+//! Assumptions: SI units throughout (m, m³/s²).
+//! References: IAU WG on Numerical Standards (2012); EGM96 (Earth);
+//!   GRAIL GL0660B (Moon); GMM-3 (Mars); NASA JPL Planetary Fact Sheets
+
+use wasm_bindgen::prelude::*;
+
+pub const MU_SUN: f64 = 1.32712440018e20; // m^3/s^2
+pub const MU_EARTH: f64 = 3.986004418e14; // m^3/s^2
+pub const MU_MOON: f64 = 4.9028000661e12; // m^3/s^2
+pub const MU_MARS: f64 = 4.282837e13; // m^3/s^2
+pub const MU_MERCURY: f64 = 2.2031868551e13; // m^3/s^2
+pub const MU_VENUS: f64 = 3.24858592e14; // m^3/s^2
+pub const MU_JUPITER: f64 = 1.26712764e17; // m^3/s^2 (system)
+pub const MU_SATURN: f64 = 3.7940585e16; // m^3/s^2 (system)
+pub const MU_URANUS: f64 = 5.794556e15; // m^3/s^2 (system)
+pub const MU_NEPTUNE: f64 = 6.836527e15; // m^3/s^2 (system)
+pub const AU: f64 = 1.495978707e11; // m
+
+/// Julian date of the J2000.0 epoch (2000-01-01 12:00 TT)
+pub const JD_J2000: f64 = 2451545.0;
+/// Days per Julian century
+pub const DAYS_PER_CENTURY: f64 = 36525.0;
+/// Mean obliquity of the ecliptic at J2000 (IAU 1976) [rad]
+pub const OBLIQUITY_J2000: f64 = 23.43929111 * std::f64::consts::PI / 180.0;
+
+/// Solar photospheric radius (IAU 2015 nominal) [m]
+pub const R_SUN: f64 = 6.957e8;
+/// Solar radiation pressure at 1 AU, 1361 W/m² / c [N/m²]
+pub const P_SUN: f64 = 4.56e-6;
+
+/// Earth equatorial radius (WGS-84 / EGM96) [m]
+pub const R_EARTH: f64 = 6.378137e6;
+/// Earth flattening (WGS-84)
+pub const F_EARTH: f64 = 1.0 / 298.257223563;
+/// Moon reference radius (GRAIL) [m]
+pub const R_MOON: f64 = 1.738e6;
+/// Mars reference radius (GMM-3) [m]
+pub const R_MARS: f64 = 3.3962e6;
+
+/// Earth rotation rate (IERS) [rad/s]
+pub const OMEGA_EARTH: f64 = 7.292115e-5;
+/// Moon rotation rate (synchronous) [rad/s]
+pub const OMEGA_MOON: f64 = 2.6616995e-6;
+/// Mars rotation rate [rad/s]
+pub const OMEGA_MARS: f64 = 7.088218e-5;
+
+/// Central bodies with built-in physical constants.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CentralBody {
+    Earth = 0,
+    Moon = 1,
+    Mars = 2,
+}
+
+impl CentralBody {
+    /// Gravitational parameter [m³/s²]
+    pub fn mu(self) -> f64 {
+        match self {
+            CentralBody::Earth => MU_EARTH,
+            CentralBody::Moon => MU_MOON,
+            CentralBody::Mars => MU_MARS,
+        }
+    }
+
+    /// Sidereal rotation rate [rad/s]
+    pub fn rotation_rate(self) -> f64 {
+        match self {
+            CentralBody::Earth => OMEGA_EARTH,
+            CentralBody::Moon => OMEGA_MOON,
+            CentralBody::Mars => OMEGA_MARS,
+        }
+    }
+
+    /// Reference (equatorial) radius [m]
+    pub fn radius(self) -> f64 {
+        match self {
+            CentralBody::Earth => R_EARTH,
+            CentralBody::Moon => R_MOON,
+            CentralBody::Mars => R_MARS,
+        }
+    }
+}
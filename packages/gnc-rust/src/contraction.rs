@@ -0,0 +1,365 @@
+//! ID: WASM-CH-001
+//! Requirement: Answer repeated single-pair queries on one SparseGraph while
+//!   settling only a few hundred nodes, after a one-off preprocessing pass.
+//! Purpose: The web planner re-queries the same graph while the user drags a
+//!   target. A contraction hierarchy pays once at preprocess() so that each
+//!   drag costs a small bidirectional search.
+//! Rationale: Nodes are contracted one at a time, least important first.
+//!   Importance is the edge difference (shortcuts added − edges removed) plus
+//!   the count of already-contracted neighbours, which spreads contraction
+//!   evenly; priorities are refreshed lazily when popped. Contracting v adds a
+//!   shortcut u → x for each path u → v → x unless a witness search finds an
+//!   equally short path that avoids v. A query then runs Dijkstra upward only,
+//!   from the source over edges to higher-ranked nodes and from the target over
+//!   reversed ones. Each shortcut records the node it bypasses, so paths unpack
+//!   recursively back to original edges.
+//! Inputs: SparseGraph (CSR, weights ≥ 0), source, target
+//! Outputs: distance (+∞ if unreachable), node path over original edges,
+//!   nodes settled and edges relaxed
+//! Assumptions/Limits: witness searches stop after WITNESS_SETTLE_LIMIT nodes.
+//!   An abandoned search only adds a redundant shortcut, so queries stay exact.
+//!   Parallel edges collapse to the lightest one and self-loops are dropped.
+//! References: Geisberger, Sanders, Schultes & Delling (2008) "Contraction
+//!   Hierarchies: Faster and Simpler Hierarchical Routing in Road Networks",
+//!   WEA 2008, LNCS 5038
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::point_to_point::{Entry, PathSearch};
+use crate::sssp::{SearchStats, SparseGraph};
+
+/// Middle node of an original (non-shortcut) edge
+const ORIGINAL: u32 = u32::MAX;
+/// Nodes a witness search settles before giving up
+const WITNESS_SETTLE_LIMIT: usize = 500;
+/// Cheaper limit for the witness searches that only estimate priorities
+const PRIORITY_SETTLE_LIMIT: usize = 32;
+
+/// Edge to `node`; `middle` is the node a shortcut bypasses
+#[derive(Debug, Clone, Copy)]
+struct Arc {
+    node: u32,
+    weight: f64,
+    middle: u32,
+}
+
+/// Insert u → v, or lower its weight if the edge already exists.
+fn add_arc(out: &mut [Vec<Arc>], inc: &mut [Vec<Arc>], u: usize, v: usize, weight: f64, middle: u32) {
+    if u == v {
+        return;
+    }
+    match out[u].iter().position(|a| a.node as usize == v) {
+        Some(i) if out[u][i].weight <= weight => {}
+        Some(i) => {
+            out[u][i] = Arc { node: v as u32, weight, middle };
+            let j = inc[v].iter().position(|a| a.node as usize == u).unwrap();
+            inc[v][j] = Arc { node: u as u32, weight, middle };
+        }
+        None => {
+            out[u].push(Arc { node: v as u32, weight, middle });
+            inc[v].push(Arc { node: u as u32, weight, middle });
+        }
+    }
+}
+
+/// Bounded Dijkstra over the uncontracted graph with reusable arrays
+struct WitnessSearch {
+    distances: Vec<f64>,
+    touched: Vec<usize>,
+    heap: BinaryHeap<Entry>,
+    target_stamp: Vec<u32>,
+    stamp: u32,
+}
+
+impl WitnessSearch {
+    fn new(n: usize) -> Self {
+        WitnessSearch {
+            distances: vec![f64::INFINITY; n],
+            touched: Vec::new(),
+            heap: BinaryHeap::new(),
+            target_stamp: vec![0; n],
+            stamp: 0,
+        }
+    }
+
+    /// Distances from `source` avoiding `excluded`, exact up to `bound` or
+    /// until every target is settled or `limit` nodes are
+    fn run(&mut self, out: &[Vec<Arc>], source: usize, excluded: usize, targets: &[Arc], bound: f64, limit: usize) {
+        for &v in &self.touched {
+            self.distances[v] = f64::INFINITY;
+        }
+        self.stamp += 1;
+        let mut remaining = 0;
+        for t in targets {
+            if t.node as usize != source && self.target_stamp[t.node as usize] != self.stamp {
+                self.target_stamp[t.node as usize] = self.stamp;
+                remaining += 1;
+            }
+        }
+        self.touched.clear();
+        self.heap.clear();
+        self.distances[source] = 0.0;
+        self.touched.push(source);
+        self.heap.push(Entry { key: 0.0, node: source });
+        let mut settled = 0;
+        while let Some(Entry { key, node: u }) = self.heap.pop() {
+            if key > self.distances[u] {
+                continue;
+            }
+            settled += 1;
+            if key > bound || settled > limit {
+                break;
+            }
+            if self.target_stamp[u] == self.stamp {
+                remaining -= 1;
+                if remaining == 0 {
+                    break;
+                }
+            }
+            for a in &out[u] {
+                let v = a.node as usize;
+                if v != excluded && key + a.weight < self.distances[v] {
+                    if self.distances[v].is_infinite() {
+                        self.touched.push(v);
+                    }
+                    self.distances[v] = key + a.weight;
+                    self.heap.push(Entry { key: key + a.weight, node: v });
+                }
+            }
+        }
+    }
+}
+
+/// Shortcuts (u, x, weight) that contracting v would need
+fn shortcuts_for(out: &[Vec<Arc>], inc: &[Vec<Arc>], v: usize, limit: usize, witness: &mut WitnessSearch) -> Vec<(usize, usize, f64)> {
+    let mut shortcuts = Vec::new();
+    for a in &inc[v] {
+        let u = a.node as usize;
+        let max_out = out[v].iter().filter(|b| b.node as usize != u).map(|b| b.weight).fold(f64::NEG_INFINITY, f64::max);
+        if max_out == f64::NEG_INFINITY {
+            continue;
+        }
+        witness.run(out, u, v, &out[v], a.weight + max_out, limit);
+        for b in &out[v] {
+            let x = b.node as usize;
+            let via = a.weight + b.weight;
+            if x != u && witness.distances[x] > via {
+                shortcuts.push((u, x, via));
+            }
+        }
+    }
+    shortcuts
+}
+
+/// Upward CSR graph: arcs from each node to higher-ranked nodes
+#[derive(Debug)]
+struct UpwardGraph {
+    offsets: Vec<u32>,
+    arcs: Vec<Arc>,
+}
+
+impl UpwardGraph {
+    fn from_lists(lists: Vec<Vec<Arc>>) -> Self {
+        let mut offsets = Vec::with_capacity(lists.len() + 1);
+        offsets.push(0);
+        let mut arcs = Vec::new();
+        for list in lists {
+            arcs.extend(list);
+            offsets.push(arcs.len() as u32);
+        }
+        UpwardGraph { offsets, arcs }
+    }
+
+    fn arcs(&self, node: usize) -> &[Arc] {
+        &self.arcs[self.offsets[node] as usize..self.offsets[node + 1] as usize]
+    }
+}
+
+/// Contraction hierarchy over a SparseGraph
+#[derive(Debug)]
+pub(crate) struct ContractionHierarchy {
+    rank: Vec<u32>,
+    /// u → v with rank[v] > rank[u], stored at u
+    up: UpwardGraph,
+    /// u → v with rank[u] > rank[v], stored reversed at v
+    down: UpwardGraph,
+    pub(crate) shortcuts: usize,
+}
+
+impl ContractionHierarchy {
+    /// Contract every node in edge-difference order.
+    pub(crate) fn build(graph: &SparseGraph) -> Self {
+        let n = graph.node_count();
+        let mut out: Vec<Vec<Arc>> = vec![Vec::new(); n];
+        let mut inc: Vec<Vec<Arc>> = vec![Vec::new(); n];
+        for u in 0..n {
+            for (v, w) in graph.edges(u) {
+                add_arc(&mut out, &mut inc, u, v, w, ORIGINAL);
+            }
+        }
+
+        let mut witness = WitnessSearch::new(n);
+        // Contracted neighbours and hierarchy depth keep contraction spread out
+        let mut contracted_neighbors = vec![0i64; n];
+        let mut level = vec![0i64; n];
+        let priority = |out: &[Vec<Arc>], inc: &[Vec<Arc>], v: usize, shortcuts: usize| {
+            shortcuts as i64 - (out[v].len() + inc[v].len()) as i64
+        };
+        let mut queue: BinaryHeap<Reverse<(i64, usize)>> = (0..n)
+            .map(|v| Reverse((priority(&out, &inc, v, shortcuts_for(&out, &inc, v, PRIORITY_SETTLE_LIMIT, &mut witness).len()), v)))
+            .collect();
+
+        let mut rank = vec![u32::MAX; n];
+        let mut up_lists = vec![Vec::new(); n];
+        let mut down_lists = vec![Vec::new(); n];
+        let mut next_rank = 0;
+        let mut shortcuts = 0;
+        while let Some(Reverse((stale, v))) = queue.pop() {
+            if rank[v] != u32::MAX {
+                continue;
+            }
+            // Lazy update: requeue if v is no longer the least important
+            let estimate = shortcuts_for(&out, &inc, v, PRIORITY_SETTLE_LIMIT, &mut witness).len();
+            let current = priority(&out, &inc, v, estimate) + contracted_neighbors[v] + level[v];
+            if current > stale && queue.peek().is_some_and(|Reverse((p, _))| current > *p) {
+                queue.push(Reverse((current, v)));
+                continue;
+            }
+
+            let added = shortcuts_for(&out, &inc, v, WITNESS_SETTLE_LIMIT, &mut witness);
+            rank[v] = next_rank;
+            next_rank += 1;
+            for a in &out[v] {
+                let x = a.node as usize;
+                inc[x].retain(|b| b.node as usize != v);
+                contracted_neighbors[x] += 1;
+                level[x] = level[x].max(level[v] + 1);
+            }
+            for a in &inc[v] {
+                let u = a.node as usize;
+                out[u].retain(|b| b.node as usize != v);
+                contracted_neighbors[u] += 1;
+                level[u] = level[u].max(level[v] + 1);
+            }
+            up_lists[v] = std::mem::take(&mut out[v]);
+            down_lists[v] = std::mem::take(&mut inc[v]);
+            shortcuts += added.len();
+            for (u, x, weight) in added {
+                add_arc(&mut out, &mut inc, u, x, weight, v as u32);
+            }
+        }
+
+        ContractionHierarchy {
+            rank,
+            up: UpwardGraph::from_lists(up_lists),
+            down: UpwardGraph::from_lists(down_lists),
+            shortcuts,
+        }
+    }
+
+    /// Bypassed node of edge u → v, stored at its lower-ranked endpoint
+    fn middle(&self, u: usize, v: usize) -> u32 {
+        let arc = if self.rank[u] < self.rank[v] {
+            self.up.arcs(u).iter().find(|a| a.node as usize == v)
+        } else {
+            self.down.arcs(v).iter().find(|a| a.node as usize == u)
+        };
+        arc.expect("hierarchy edge").middle
+    }
+
+    /// Append the original-edge path of u → v (excluding u) to `path`.
+    fn unpack(&self, u: usize, v: usize, path: &mut Vec<u32>) {
+        let mut stack = vec![(u, v)];
+        while let Some((a, b)) = stack.pop() {
+            match self.middle(a, b) {
+                ORIGINAL => path.push(b as u32),
+                m => {
+                    stack.push((m as usize, b));
+                    stack.push((a, m as usize));
+                }
+            }
+        }
+    }
+
+    /// Bidirectional upward search from `source` and `target`.
+    pub(crate) fn query(&self, source: usize, target: usize) -> PathSearch {
+        let n = self.rank.len();
+        let mut stats = SearchStats::default();
+        let mut forward = UpwardSearch::new(n, source);
+        let mut backward = UpwardSearch::new(n, target);
+        let mut best = if source == target { (0.0, source) } else { (f64::INFINITY, usize::MAX) };
+
+        loop {
+            let (f, b) = (forward.min_key(), backward.min_key());
+            // Neither side can still improve on the best meeting point
+            if f >= best.0 && b >= best.0 {
+                break;
+            }
+            if f <= b {
+                forward.step(&self.up, &backward.distances, &mut best, &mut stats);
+            } else {
+                backward.step(&self.down, &forward.distances, &mut best, &mut stats);
+            }
+        }
+
+        let (distance, meet) = best;
+        if distance.is_infinite() {
+            return PathSearch { distance, path: Vec::new(), stats };
+        }
+        let mut hops = vec![meet];
+        while forward.predecessors[*hops.last().unwrap()] >= 0 {
+            hops.push(forward.predecessors[*hops.last().unwrap()] as usize);
+        }
+        hops.reverse();
+        let mut v = meet;
+        while backward.predecessors[v] >= 0 {
+            v = backward.predecessors[v] as usize;
+            hops.push(v);
+        }
+        let mut path = vec![source as u32];
+        for pair in hops.windows(2) {
+            self.unpack(pair[0], pair[1], &mut path);
+        }
+        PathSearch { distance, path, stats }
+    }
+}
+
+/// One direction of the upward query
+struct UpwardSearch {
+    distances: Vec<f64>,
+    predecessors: Vec<i32>,
+    heap: BinaryHeap<Entry>,
+}
+
+impl UpwardSearch {
+    fn new(n: usize, root: usize) -> Self {
+        let mut distances = vec![f64::INFINITY; n];
+        distances[root] = 0.0;
+        UpwardSearch { distances, predecessors: vec![-1; n], heap: BinaryHeap::from([Entry { key: 0.0, node: root }]) }
+    }
+
+    fn min_key(&self) -> f64 {
+        self.heap.peek().map_or(f64::INFINITY, |e| e.key)
+    }
+
+    fn step(&mut self, graph: &UpwardGraph, other: &[f64], best: &mut (f64, usize), stats: &mut SearchStats) {
+        let Some(Entry { key, node: u }) = self.heap.pop() else { return };
+        if key > self.distances[u] {
+            return;
+        }
+        stats.nodes_visited += 1;
+        for a in graph.arcs(u) {
+            let v = a.node as usize;
+            stats.edges_relaxed += 1;
+            if key + a.weight < self.distances[v] {
+                self.distances[v] = key + a.weight;
+                self.predecessors[v] = u as i32;
+                self.heap.push(Entry { key: key + a.weight, node: v });
+            }
+            if self.distances[v] + other[v] < best.0 {
+                *best = (self.distances[v] + other[v], v);
+            }
+        }
+    }
+}
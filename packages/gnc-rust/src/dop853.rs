@@ -0,0 +1,618 @@
+//! ID: WASM-DOP853-001
+//! Requirement: Provide an adaptive Dormand–Prince 8(5,3) integrator with a
+//!   7th-order continuous extension for long-arc precision propagation.
+//! Purpose: Propagate multi-week cislunar and interplanetary arcs to near
+//!   machine precision without the millions of steps fixed-step RK4 needs.
+//! Rationale: An 8th-order method's truncation error falls as h⁸, so at tight
+//!   tolerances it takes far fewer (larger) steps than RK4 or RK5(4); the dense
+//!   output lets callers sample the solution anywhere without restricting h.
+//! Inputs: state [rx,ry,rz,vx,vy,vz] (or flat N-body layout), tf [s], atol, rtol
+//! Outputs: final/accepted states, step statistics, dense samples at given times
+//! References: Hairer, Nørsett & Wanner "Solving ODEs I" §II.10 (DOP853);
+//!   Prince & Dormand (1981) J. Comp. Appl. Math. 7(1):67-75
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::forces::{force_model_rhs, ForceModelBuilder};
+use crate::rk4::nbody_deriv;
+use crate::time::Epoch;
+use crate::rk45::{
+    error_norm, initial_step, state6_from_js, two_body_rhs, validate_problem, AdaptiveOptions,
+    AdaptiveStats, AdaptivePropagation, IntegrationError, StepControl,
+};
+
+// DOP853 tableau (Hairer's dop853.f). Stages 1–12 advance the solution, stage 13
+// is f(t+h, y_new) (reused as the next step's first stage), and stages 14–16
+// exist only for the dense output.
+const C: [f64; 16] = [
+    0.0, 0.05260015195876773, 0.0789002279381516, 0.1183503419072274, 0.2816496580927726,
+    0.3333333333333333, 0.25, 0.3076923076923077, 0.6512820512820513, 0.6, 0.8571428571428571,
+    1.0, 1.0, 0.1, 0.2, 0.7777777777777778,
+];
+const A: [[f64; 15]; 16] = [
+    [
+        0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        0.05260015195876773, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+        0.0,
+    ],
+    [
+        0.0197250569845379, 0.0591751709536137, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        0.02958758547680685, 0.0, 0.08876275643042054, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        0.2413651341592667, 0.0, -0.8845494793282861, 0.924834003261792, 0.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        0.037037037037037035, 0.0, 0.0, 0.17082860872947386, 0.12546768756682242, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        0.037109375, 0.0, 0.0, 0.17025221101954405, 0.06021653898045596, -0.017578125, 0.0,
+        0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        0.03709200011850479, 0.0, 0.0, 0.17038392571223998, 0.10726203044637328,
+        -0.015319437748624402, 0.008273789163814023, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        0.6241109587160757, 0.0, 0.0, -3.3608926294469414, -0.868219346841726,
+        27.59209969944671, 20.154067550477894, -43.48988418106996, 0.0, 0.0, 0.0, 0.0, 0.0,
+        0.0, 0.0,
+    ],
+    [
+        0.47766253643826434, 0.0, 0.0, -2.4881146199716677, -0.590290826836843,
+        21.230051448181193, 15.279233632882423, -33.28821096898486, -0.020331201708508627, 0.0,
+        0.0, 0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        -0.9371424300859873, 0.0, 0.0, 5.186372428844064, 1.0914373489967295,
+        -8.149787010746927, -18.52006565999696, 22.739487099350505, 2.4936055526796523,
+        -3.0467644718982196, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        2.273310147516538, 0.0, 0.0, -10.53449546673725, -2.0008720582248625,
+        -17.9589318631188, 27.94888452941996, -2.8589982771350235, -8.87285693353063,
+        12.360567175794303, 0.6433927460157636, 0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+    ],
+    [
+        0.056167502283047954, 0.0, 0.0, 0.0, 0.0, 0.0, 0.25350021021662483,
+        -0.2462390374708025, -0.12419142326381637, 0.15329179827876568, 0.00820105229563469,
+        0.007567897660545699, -0.008298, 0.0, 0.0,
+    ],
+    [
+        0.03183464816350214, 0.0, 0.0, 0.0, 0.0, 0.028300909672366776, 0.053541988307438566,
+        -0.05492374857139099, 0.0, 0.0, -0.00010834732869724932, 0.0003825710908356584,
+        -0.00034046500868740456, 0.1413124436746325, 0.0,
+    ],
+    [
+        -0.42889630158379194, 0.0, 0.0, 0.0, 0.0, -4.697621415361164, 7.683421196062599,
+        4.06898981839711, 0.3567271874552811, 0.0, 0.0, 0.0, -0.0013990241651590145,
+        2.9475147891527724, -9.15095847217987,
+    ],
+];
+/// 8th-order solution weights.
+const B: [f64; 12] = [
+    0.054293734116568765, 0.0, 0.0, 0.0, 0.0, 4.450312892752409, 1.8915178993145003,
+    -5.801203960010585, 0.3111643669578199, -0.1521609496625161, 0.20136540080403034,
+    0.04471061572777259,
+];
+/// 3rd-order embedded weights (for the blended error estimate).
+const BHH: [f64; 3] = [
+    0.2440944881889764, 0.7338466882816118, 0.022058823529411766,
+];
+/// 5th-order error estimator weights.
+const E5: [f64; 12] = [
+    0.01312004499419488, 0.0, 0.0, 0.0, 0.0, -1.2251564463762044, -0.4957589496572502,
+    1.6643771824549864, -0.35032884874997366, 0.3341791187130175, 0.08192320648511571,
+    -0.022355307863886294,
+];
+/// Dense-output weights for the 7th-order interpolant.
+const D: [[f64; 16]; 4] = [
+    [
+        -8.428938276109013, 0.0, 0.0, 0.0, 0.0, 0.5667149535193777, -3.0689499459498917,
+        2.38466765651207, 2.117034582445028, -0.871391583777973, 2.2404374302607883,
+        0.6315787787694688, -0.08899033645133331, 18.148505520854727, -9.194632392478356,
+        -4.436036387594894,
+    ],
+    [
+        10.427508642579134, 0.0, 0.0, 0.0, 0.0, 242.28349177525817, 165.20045171727028,
+        -374.5467547226902, -22.113666853125306, 7.733432668472264, -30.674084731089398,
+        -9.332130526430229, 15.697238121770845, -31.139403219565178, -9.35292435884448,
+        35.81684148639408,
+    ],
+    [
+        19.985053242002433, 0.0, 0.0, 0.0, 0.0, -387.0373087493518, -189.17813819516758,
+        527.8081592054236, -11.57390253995963, 6.8812326946963, -1.0006050966910838,
+        0.7777137798053443, -2.778205752353508, -60.19669523126412, 84.32040550667716,
+        11.99229113618279,
+    ],
+    [
+        -25.69393346270375, 0.0, 0.0, 0.0, 0.0, -154.18974869023643, -231.5293791760455,
+        357.6391179106141, 93.40532418362432, -37.45832313645163, 104.0996495089623,
+        29.8402934266605, -43.53345659001114, 96.32455395918828, -39.17726167561544,
+        -149.72683625798564,
+    ],
+];
+
+/// Continuous extension of one accepted DOP853 step (7th order).
+#[derive(Debug, Clone)]
+pub struct Dop853Interpolant {
+    t_old: f64,
+    h: f64,
+    rcont: [Vec<f64>; 8],
+}
+
+impl Dop853Interpolant {
+    /// Start of the step [s]
+    pub fn t_start(&self) -> f64 { self.t_old }
+
+    /// End of the step [s]
+    pub fn t_end(&self) -> f64 { self.t_old + self.h }
+
+    /// Evaluate the interpolated state at t (within the step) into `out`.
+    pub fn eval(&self, t: f64, out: &mut [f64]) {
+        let s = (t - self.t_old) / self.h;
+        let s1 = 1.0 - s;
+        let r = &self.rcont;
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = r[0][i] + s * (r[1][i] + s1 * (r[2][i] + s * (r[3][i]
+                + s1 * (r[4][i] + s * (r[5][i] + s1 * (r[6][i] + s * r[7][i]))))));
+        }
+    }
+}
+
+/// y + h·Σ a_j·k_j over the stages supplied in `k`.
+fn stage_state(y: &[f64], h: f64, row: &[f64; 15], k: &[Vec<f64>], out: &mut [f64]) {
+    for (i, o) in out.iter_mut().enumerate() {
+        let mut acc = 0.0;
+        for (a, kj) in row.iter().zip(k) {
+            if *a != 0.0 {
+                acc += a * kj[i];
+            }
+        }
+        *o = y[i] + h * acc;
+    }
+}
+
+/// Integrate dy/dt = f(t, y) from t0 to tf with DOP853 step control.
+///
+/// `f(t, y, dy)` uses the same slice convention as `dopri5_integrate`.
+/// `observer(t, y, interp)` is called at t0 (with `None`) and after every
+/// accepted step; when `dense` is true the step's interpolant is passed along
+/// (costs three extra evaluations per step). Returning
+/// `ControlFlow::Break(())` from the observer stops early with y at that step.
+pub fn dop853_integrate<F, O, R>(
+    mut f: F,
+    t0: f64,
+    y0: &[f64],
+    tf: f64,
+    opts: &AdaptiveOptions,
+    dense: bool,
+    mut observer: O,
+) -> Result<(Vec<f64>, AdaptiveStats), IntegrationError>
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+    O: FnMut(f64, &[f64], Option<&Dop853Interpolant>) -> R,
+    R: StepControl,
+{
+    validate_problem(t0, y0, tf, opts)?;
+
+    let n = y0.len();
+    let mut stats = AdaptiveStats::default();
+    let mut t = t0;
+    let mut y = y0.to_vec();
+    if !observer(t, &y, None).keep_going() || tf == t0 {
+        return Ok((y, stats));
+    }
+
+    let dir = (tf - t0).signum();
+    let mut k: Vec<Vec<f64>> = vec![vec![0.0; n]; 16];
+    f(t, &y, &mut k[0]);
+    stats.evaluations += 1;
+
+    let mut h = if opts.h_init > 0.0 {
+        opts.h_init
+    } else {
+        stats.evaluations += 1;
+        initial_step(&mut f, t, &y, &k[0], dir, 8, opts)
+    };
+
+    let mut stage = vec![0.0; n];
+    let mut y_new = vec![0.0; n];
+    let mut err5 = vec![0.0; n];
+    let mut err3 = vec![0.0; n];
+    let mut interp = Dop853Interpolant {
+        t_old: t,
+        h: 0.0,
+        rcont: std::array::from_fn(|_| vec![0.0; n]),
+    };
+    let mut last_rejected = false;
+
+    while (tf - t) * dir > 0.0 {
+        if stats.accepted + stats.rejected >= opts.max_steps {
+            return Err(IntegrationError::MaxStepsExceeded { t });
+        }
+        if opts.h_max > 0.0 {
+            h = h.min(opts.h_max);
+        }
+        if h <= 16.0 * f64::EPSILON * t.abs().max(1.0) {
+            return Err(IntegrationError::StepSizeUnderflow { t });
+        }
+
+        let remaining = (tf - t).abs();
+        let last = h >= remaining;
+        let h_step = dir * if last { remaining } else { h };
+
+        for s in 1..12 {
+            stage_state(&y, h_step, &A[s], &k[..s], &mut stage);
+            f(t + C[s] * h_step, &stage, &mut k[s]);
+        }
+        stats.evaluations += 11;
+
+        for i in 0..n {
+            let mut b_sum = 0.0;
+            let mut e_sum = 0.0;
+            for j in 0..12 {
+                b_sum += B[j] * k[j][i];
+                e_sum += E5[j] * k[j][i];
+            }
+            y_new[i] = y[i] + h_step * b_sum;
+            err5[i] = e_sum;
+            err3[i] = b_sum - BHH[0] * k[0][i] - BHH[1] * k[8][i] - BHH[2] * k[11][i];
+        }
+
+        // Hairer's blended 5th/3rd-order error estimate, scaled like error_norm.
+        let e5 = error_norm(&err5, &y, &y_new, opts);
+        let e3 = error_norm(&err3, &y, &y_new, opts);
+        let deno = {
+            let d = e5 * e5 + 0.01 * e3 * e3;
+            if d > 0.0 { d } else { 1.0 }
+        };
+        let en = h_step.abs() * e5 * e5 / deno.sqrt();
+
+        if !en.is_finite() {
+            stats.rejected += 1;
+            h = h_step.abs() * 0.2;
+            last_rejected = true;
+            continue;
+        }
+
+        let fac = 0.9 * en.powf(-0.125);
+        if en <= 1.0 {
+            let t_new = if last { tf } else { t + h_step };
+            f(t_new, &y_new, &mut k[12]);
+            stats.evaluations += 1;
+
+            if dense {
+                build_interpolant(&mut f, t, &y, &y_new, h_step, &mut k, &mut stage, &mut interp);
+                stats.evaluations += 3;
+            }
+
+            t = t_new;
+            y.copy_from_slice(&y_new);
+            k.swap(0, 12);
+            stats.accepted += 1;
+            if !observer(t, &y, if dense { Some(&interp) } else { None }).keep_going() {
+                break;
+            }
+
+            let fac = fac.clamp(1.0 / 3.0, 6.0);
+            h = h_step.abs() * if last_rejected { fac.min(1.0) } else { fac };
+            last_rejected = false;
+        } else {
+            stats.rejected += 1;
+            h = h_step.abs() * fac.max(1.0 / 3.0);
+            last_rejected = true;
+        }
+    }
+
+    Ok((y, stats))
+}
+
+/// Fill the dense-output coefficients for the step (t, y) → (t+h, y_new).
+/// Requires stages 1–13 in `k`; evaluates stages 14–16.
+#[allow(clippy::too_many_arguments)]
+fn build_interpolant<F>(
+    f: &mut F,
+    t: f64,
+    y: &[f64],
+    y_new: &[f64],
+    h: f64,
+    k: &mut [Vec<f64>],
+    stage: &mut [f64],
+    interp: &mut Dop853Interpolant,
+)
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+{
+    for s in 13..16 {
+        stage_state(y, h, &A[s], &k[..s], stage);
+        f(t + C[s] * h, stage, &mut k[s]);
+    }
+
+    let r = &mut interp.rcont;
+    for i in 0..y.len() {
+        let dy = y_new[i] - y[i];
+        let bspl = h * k[0][i] - dy;
+        r[0][i] = y[i];
+        r[1][i] = dy;
+        r[2][i] = bspl;
+        r[3][i] = dy - h * k[12][i] - bspl;
+        for (row, d) in D.iter().enumerate() {
+            r[4 + row][i] = h * d.iter().zip(k.iter()).map(|(dj, kj)| dj * kj[i]).sum::<f64>();
+        }
+    }
+    interp.t_old = t;
+    interp.h = h;
+}
+
+fn dop853_run<F>(rhs: F, state_js: &Float64Array, t0: f64, tf: f64, atol: f64, rtol: f64) -> Result<AdaptivePropagation, JsValue>
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+{
+    let y0 = state6_from_js(state_js)?;
+    let opts = AdaptiveOptions::new(atol, rtol);
+    let (y, stats) = dop853_integrate(rhs, t0, &y0, tf, &opts, false, |_, _, _| {})?;
+
+    let mut states = y0;
+    states.extend_from_slice(&y);
+    Ok(AdaptivePropagation::new(vec![0.0, tf - t0], states, 6, stats))
+}
+
+/// Integrate from t = 0 and sample the dense output at `samples`, which must be
+/// finite and strictly monotonic in the direction of integration from t = 0.
+/// Returns the flat states, `dim` per sample.
+pub(crate) fn dop853_sample<F>(
+    rhs: F,
+    y0: &[f64],
+    samples: &[f64],
+    opts: &AdaptiveOptions,
+) -> Result<(Vec<f64>, AdaptiveStats), IntegrationError>
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+{
+    let tf = match samples.last() {
+        Some(&tf) => tf,
+        None => return Err(IntegrationError::InvalidInput("no sample times given".to_string())),
+    };
+    let dir = if tf >= 0.0 { 1.0 } else { -1.0 };
+    if let Some(i) = samples.iter().position(|s| !s.is_finite()) {
+        return Err(IntegrationError::InvalidInput(format!("sample time {} is {}", i, samples[i])));
+    }
+    if samples[0] * dir < 0.0 {
+        return Err(IntegrationError::InvalidInput(format!("first sample time {} is before t = 0", samples[0])));
+    }
+    if let Some(i) = samples.windows(2).position(|w| (w[1] - w[0]) * dir <= 0.0) {
+        return Err(IntegrationError::InvalidInput(format!(
+            "sample times must be strictly monotonic: {} follows {}",
+            samples[i + 1],
+            samples[i]
+        )));
+    }
+
+    let mut states: Vec<f64> = Vec::with_capacity(y0.len() * samples.len());
+    let mut next = 0usize;
+    let mut buf = vec![0.0; y0.len()];
+
+    let (_, stats) = dop853_integrate(rhs, 0.0, y0, tf, opts, true, |t, y, interp| {
+        while next < samples.len() && (samples[next] - t) * dir <= 0.0 {
+            match interp {
+                Some(p) => {
+                    p.eval(samples[next], &mut buf);
+                    states.extend_from_slice(&buf);
+                }
+                None => states.extend_from_slice(y),
+            }
+            next += 1;
+        }
+    })?;
+    Ok((states, stats))
+}
+
+fn dop853_dense_run<F>(
+    rhs: F,
+    state_js: &Float64Array,
+    sample_times_js: &Float64Array,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue>
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+{
+    let y0 = state6_from_js(state_js)?;
+    let samples = sample_times_js.to_vec();
+    let (states, stats) = dop853_sample(rhs, &y0, &samples, &AdaptiveOptions::new(atol, rtol))?;
+    Ok(AdaptivePropagation::new(samples, states, 6, stats))
+}
+
+/// Propagate a two-body state from t = 0 to `tf` with DOP853.
+/// Returns the initial and final states; tf < 0 propagates backward.
+#[wasm_bindgen]
+pub fn dop853_propagate(
+    state_js: &Float64Array,
+    tf: f64,
+    mu: f64,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    dop853_run(two_body_rhs(mu), state_js, 0.0, tf, atol, rtol)
+}
+
+/// Sample a two-body DOP853 solution at the requested times via dense output.
+/// sample_times_js must be finite and strictly monotonic in the direction of
+/// integration, starting at or after t = 0. Returns a flat Float64Array of length 6·len(sample_times).
+#[wasm_bindgen]
+pub fn dop853_dense(
+    state_js: &Float64Array,
+    sample_times_js: &Float64Array,
+    mu: f64,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    dop853_dense_run(two_body_rhs(mu), state_js, sample_times_js, atol, rtol)
+}
+
+/// dop853_propagate under a ForceModelBuilder's dynamics.
+#[wasm_bindgen]
+pub fn dop853_propagate_model(
+    state_js: &Float64Array,
+    tf: f64,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    dop853_run(force_model_rhs(model.model(), model.params()), state_js, 0.0, tf, atol, rtol)
+}
+
+/// dop853_dense under a ForceModelBuilder's dynamics.
+#[wasm_bindgen]
+pub fn dop853_dense_model(
+    state_js: &Float64Array,
+    sample_times_js: &Float64Array,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    dop853_dense_run(force_model_rhs(model.model(), model.params()), state_js, sample_times_js, atol, rtol)
+}
+
+/// dop853_propagate_model from `start` to `end`; model time is measured from the
+/// builder's epoch and the reported final time is seconds since `start`.
+#[wasm_bindgen]
+pub fn dop853_propagate_epoch(
+    state_js: &Float64Array,
+    start: &Epoch,
+    end: &Epoch,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    let (t0, tf) = (model.model_time(start), model.model_time(end));
+    dop853_run(force_model_rhs(model.model(), model.params()), state_js, t0, tf, atol, rtol)
+}
+
+/// Propagate a flat N-body system (nbody_rk4_step layout) from t = 0 to `tf`
+/// with DOP853, for direct comparison against repeated RK4 steps.
+#[wasm_bindgen]
+pub fn nbody_dop853_propagate(
+    bodies_js: &Float64Array,
+    masses_js: &Float64Array,
+    tf: f64,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    let y0 = bodies_js.to_vec();
+    let masses = masses_js.to_vec();
+    if y0.len() != 6 * masses.len() {
+        return Err(JsValue::from_str("Body state length must be 6 × number of masses"));
+    }
+
+    let opts = AdaptiveOptions::new(atol, rtol);
+    let rhs = |_t: f64, s: &[f64], ds: &mut [f64]| nbody_deriv(s, &masses, ds);
+    let (y, stats) = dop853_integrate(rhs, 0.0, &y0, tf, &opts, false, |_, _, _| {})?;
+
+    let dim = y0.len();
+    let mut states = y0;
+    states.extend_from_slice(&y);
+    Ok(AdaptivePropagation::new(vec![0.0, tf], states, dim, stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forces::{PointMass, SpacecraftParams};
+    use crate::rk4::{rk4_step_model, State6};
+
+    const MU: f64 = 3.986004418e14;
+
+    #[test]
+    fn tableau_rows_sum_to_nodes() {
+        // Stage 13 is evaluated at y_new, not through A.
+        for s in (0..16).filter(|&s| s != 12) {
+            let sum: f64 = A[s].iter().sum();
+            assert!((sum - C[s]).abs() < 1e-12, "row {}", s);
+        }
+    }
+
+    #[test]
+    fn beats_rk4_on_ten_eccentric_orbits() {
+        let rp = 7.0e6;
+        let a = 1.5e7;
+        let vp = (MU * (2.0 / rp - 1.0 / a)).sqrt();
+        let y0 = [rp, 0.0, 0.0, 0.0, vp, 0.0];
+        let tf = 10.0 * 2.0 * std::f64::consts::PI * (a * a * a / MU).sqrt();
+
+        let opts = AdaptiveOptions::new(1e-9, 1e-13);
+        let (y, stats) = dop853_integrate(two_body_rhs(MU), 0.0, &y0, tf, &opts, false, |_, _, _| {}).unwrap();
+
+        // RK4 with the same number of derivative evaluations
+        let steps = stats.evaluations / 4;
+        let dt = tf / steps as f64;
+        let mut s: State6 = y0;
+        for _ in 0..steps {
+            s = rk4_step_model(&PointMass { mu: MU }, &SpacecraftParams::default(), 0.0, &s, dt);
+        }
+
+        let err = |v: &[f64]| ((v[0] - y0[0]).powi(2) + (v[1] - y0[1]).powi(2)).sqrt();
+        assert!(err(&y) < 1.0, "DOP853 error {} m", err(&y));
+        assert!(err(&y) < 1e-2 * err(&s), "DOP853 {} m vs RK4 {} m", err(&y), err(&s));
+    }
+
+    #[test]
+    fn dense_output_matches_direct_integration() {
+        let r = 7.0e6;
+        let v = (MU / r).sqrt();
+        let y0 = [r, 0.0, 0.0, 0.0, v * 0.9, v * 0.3];
+        let opts = AdaptiveOptions::new(1e-6, 1e-12);
+        let t_mid = 1234.5;
+
+        let mut sampled = [0.0; 6];
+        dop853_integrate(two_body_rhs(MU), 0.0, &y0, 4000.0, &opts, true, |_, _, interp| {
+            if let Some(p) = interp {
+                if p.t_start() <= t_mid && t_mid <= p.t_end() {
+                    p.eval(t_mid, &mut sampled);
+                }
+            }
+        }).unwrap();
+
+        let (direct, _) = dop853_integrate(two_body_rhs(MU), 0.0, &y0, t_mid, &opts, false, |_, _, _| {}).unwrap();
+        for i in 0..3 {
+            assert!((sampled[i] - direct[i]).abs() < 1e-2, "axis {}: {} vs {}", i, sampled[i], direct[i]);
+        }
+    }
+
+    #[test]
+    fn dense_samples_must_be_finite_and_ordered() {
+        let r = 7.0e6;
+        let y0 = [r, 0.0, 0.0, 0.0, (MU / r).sqrt(), 0.0];
+        let opts = AdaptiveOptions::new(1e-6, 1e-12);
+        let sample = |times: &[f64]| dop853_sample(two_body_rhs(MU), &y0, times, &opts);
+        for times in [
+            &[0.0, 2000.0, 1000.0][..],
+            &[-100.0, -300.0, -200.0],
+            &[100.0, 100.0],
+            &[-10.0, 500.0],
+            &[0.0, f64::NAN, 500.0],
+            &[0.0, f64::INFINITY],
+            &[],
+        ] {
+            assert!(matches!(sample(times), Err(IntegrationError::InvalidInput(_))), "{:?}", times);
+        }
+        let (states, _) = sample(&[0.0, 1000.0, 2000.0]).unwrap();
+        assert_eq!(states.len(), 18);
+        assert_eq!(&states[..6], &y0);
+        let (states, _) = sample(&[-1000.0, -2000.0]).unwrap();
+        assert_eq!(states.len(), 12);
+    }
+}
@@ -0,0 +1,276 @@
+//! ID: WASM-DRAG-001
+//! Requirement: Model atmospheric drag on the spacecraft with selectable density
+//!   models (piecewise exponential, US Standard Atmosphere 1976) up to 1000 km.
+//! Purpose: Run LEO decay and reentry in WASM instead of the TS-only
+//!   computeAtmosphere/computeDrag in launch/guidance.ts.
+//! Rationale: a = −½ ρ |v_rel| v_rel / B with B = m/(C_d A) and the atmosphere
+//!   co-rotating with the body (v_rel = v − ω×r). The exponential table is cheap
+//!   and smooth; US76 follows the standard's layered hydrostatic model to 86 km
+//!   and its tabulated densities (log-linear interpolation) above.
+//! Inputs: inertial state [m, m/s], spacecraft mass/area/C_d or explicit B [kg/m²]
+//! Outputs: drag acceleration [m/s²]; density [kg/m³]
+//! Assumptions: altitude above a spherical Earth of equatorial radius; no
+//!   solar/geomagnetic activity dependence.
+//! References: Vallado §8.6 and Table 8-4; U.S. Standard Atmosphere 1976
+//!   (NOAA-S/T 76-1562), Tables I and IV
+
+use wasm_bindgen::prelude::*;
+
+use crate::constants::{OMEGA_EARTH, R_EARTH};
+use crate::forces::{ForceModel, ForceModelBuilder, SpacecraftParams};
+use crate::rk4::State6;
+
+/// Atmospheric density model selection.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DensityModel {
+    Exponential = 0,
+    Us76 = 1,
+}
+
+/// Vallado Table 8-4: base altitude [km], base density [kg/m³], scale height [km]
+const EXPONENTIAL_TABLE: [(f64, f64, f64); 28] = [
+    (0.0, 1.225, 7.249),
+    (25.0, 3.899e-2, 6.349),
+    (30.0, 1.774e-2, 6.682),
+    (40.0, 3.972e-3, 7.554),
+    (50.0, 1.057e-3, 8.382),
+    (60.0, 3.206e-4, 7.714),
+    (70.0, 8.770e-5, 6.549),
+    (80.0, 1.905e-5, 5.799),
+    (90.0, 3.396e-6, 5.382),
+    (100.0, 5.297e-7, 5.877),
+    (110.0, 9.661e-8, 7.263),
+    (120.0, 2.438e-8, 9.473),
+    (130.0, 8.484e-9, 12.636),
+    (140.0, 3.845e-9, 16.149),
+    (150.0, 2.070e-9, 22.523),
+    (180.0, 5.464e-10, 29.740),
+    (200.0, 2.789e-10, 37.105),
+    (250.0, 7.248e-11, 45.546),
+    (300.0, 2.418e-11, 53.628),
+    (350.0, 9.518e-12, 53.298),
+    (400.0, 3.725e-12, 58.515),
+    (450.0, 1.585e-12, 60.828),
+    (500.0, 6.967e-13, 63.822),
+    (600.0, 1.454e-13, 71.835),
+    (700.0, 3.614e-14, 88.667),
+    (800.0, 1.170e-14, 124.64),
+    (900.0, 5.245e-15, 181.05),
+    (1000.0, 3.019e-15, 268.00),
+];
+
+/// Piecewise exponential density [kg/m³] at geometric altitude [m].
+pub fn exponential_density(altitude: f64) -> f64 {
+    let h = altitude.max(0.0) / 1e3;
+    let (h0, rho0, scale) = EXPONENTIAL_TABLE
+        .iter()
+        .rev()
+        .find(|row| h >= row.0)
+        .copied()
+        .unwrap_or(EXPONENTIAL_TABLE[0]);
+    rho0 * (-(h - h0) / scale).exp()
+}
+
+/// US76 lower atmosphere: geopotential base altitude [km] and lapse rate [K/km]
+const US76_LAYERS: [(f64, f64); 7] = [
+    (0.0, -6.5),
+    (11.0, 0.0),
+    (20.0, 1.0),
+    (32.0, 2.8),
+    (47.0, 0.0),
+    (51.0, -2.8),
+    (71.0, -2.0),
+];
+/// Effective Earth radius used for geopotential altitude [km]
+const US76_R0: f64 = 6356.766;
+/// g₀·M₀/R* [K/km]
+const US76_GMR: f64 = 34.163195;
+/// Specific gas constant of air R*/M₀ [J/(kg·K)]
+const US76_R_AIR: f64 = 287.053;
+
+/// US76 densities above 86 km: geometric altitude [km], density [kg/m³]
+const US76_UPPER: [(f64, f64); 26] = [
+    (86.0, 6.958e-6),
+    (90.0, 3.416e-6),
+    (95.0, 1.393e-6),
+    (100.0, 5.604e-7),
+    (110.0, 9.708e-8),
+    (120.0, 2.222e-8),
+    (130.0, 8.152e-9),
+    (140.0, 3.831e-9),
+    (150.0, 2.076e-9),
+    (160.0, 1.233e-9),
+    (170.0, 7.815e-10),
+    (180.0, 5.194e-10),
+    (190.0, 3.581e-10),
+    (200.0, 2.541e-10),
+    (220.0, 1.367e-10),
+    (250.0, 6.073e-11),
+    (300.0, 1.916e-11),
+    (350.0, 7.014e-12),
+    (400.0, 2.803e-12),
+    (450.0, 1.184e-12),
+    (500.0, 5.215e-13),
+    (600.0, 1.137e-13),
+    (700.0, 3.070e-14),
+    (800.0, 1.136e-14),
+    (900.0, 5.759e-15),
+    (1000.0, 3.561e-15),
+];
+
+/// US Standard Atmosphere 1976 density [kg/m³] at geometric altitude [m].
+/// Above 1000 km the last table segment is extrapolated.
+pub fn us76_density(altitude: f64) -> f64 {
+    let z = altitude.max(0.0) / 1e3;
+    if z < US76_UPPER[0].0 {
+        let h = US76_R0 * z / (US76_R0 + z);
+        let (mut tb, mut pb) = (288.15, 101325.0);
+        for (i, &(hb, lapse)) in US76_LAYERS.iter().enumerate() {
+            let top = US76_LAYERS.get(i + 1).map_or(f64::INFINITY, |l| l.0);
+            let dh = h.min(top) - hb;
+            let t = tb + lapse * dh;
+            let p = if lapse == 0.0 {
+                pb * (-US76_GMR * dh / tb).exp()
+            } else {
+                pb * (tb / t).powf(US76_GMR / lapse)
+            };
+            if h <= top {
+                return p / (US76_R_AIR * t);
+            }
+            tb = t;
+            pb = p;
+        }
+    }
+    let i = US76_UPPER.iter().rposition(|row| z >= row.0).unwrap().min(US76_UPPER.len() - 2);
+    let ((z0, rho0), (z1, rho1)) = (US76_UPPER[i], US76_UPPER[i + 1]);
+    rho0 * (rho1 / rho0).powf((z - z0) / (z1 - z0))
+}
+
+/// Density [kg/m³] at geometric altitude [m] for the selected model.
+#[inline]
+pub fn atmospheric_density(model: DensityModel, altitude: f64) -> f64 {
+    match model {
+        DensityModel::Exponential => exponential_density(altitude),
+        DensityModel::Us76 => us76_density(altitude),
+    }
+}
+
+/// Atmospheric density [kg/m³] at geometric altitude [m]
+#[wasm_bindgen]
+pub fn atmosphere_density(altitude: f64, model: DensityModel) -> f64 {
+    atmospheric_density(model, altitude)
+}
+
+/// Drag in an atmosphere co-rotating with the central body about +z.
+#[derive(Debug, Clone, Copy)]
+pub struct AtmosphericDrag {
+    pub density: DensityModel,
+    /// Radius altitude is measured from [m]
+    pub body_radius: f64,
+    /// Atmosphere rotation rate [rad/s]
+    pub rotation_rate: f64,
+    /// Ballistic coefficient m/(C_d A) [kg/m²]; None derives it from SpacecraftParams
+    pub ballistic_coefficient: Option<f64>,
+}
+
+impl AtmosphericDrag {
+    /// Earth atmosphere rotating at the sidereal rate.
+    pub fn earth(density: DensityModel) -> Self {
+        AtmosphericDrag { density, body_radius: R_EARTH, rotation_rate: OMEGA_EARTH, ballistic_coefficient: None }
+    }
+}
+
+impl ForceModel for AtmosphericDrag {
+    fn acceleration(&self, _t: f64, state: &State6, params: &SpacecraftParams) -> [f64; 3] {
+        let r = (state[0] * state[0] + state[1] * state[1] + state[2] * state[2]).sqrt();
+        let rho = atmospheric_density(self.density, r - self.body_radius);
+        if rho == 0.0 {
+            return [0.0; 3];
+        }
+        let bc = self
+            .ballistic_coefficient
+            .unwrap_or(params.mass / (params.drag_coefficient * params.drag_area));
+        // v_rel = v − ω×r with ω = ω ẑ
+        let w = self.rotation_rate;
+        let vrel = [state[3] + w * state[1], state[4] - w * state[0], state[5]];
+        let vn = (vrel[0] * vrel[0] + vrel[1] * vrel[1] + vrel[2] * vrel[2]).sqrt();
+        let k = -0.5 * rho * vn / bc;
+        [k * vrel[0], k * vrel[1], k * vrel[2]]
+    }
+}
+
+#[wasm_bindgen]
+impl ForceModelBuilder {
+    /// Earth atmospheric drag; ballistic coefficient from set_spacecraft
+    #[wasm_bindgen]
+    pub fn add_drag(&mut self, density: DensityModel) {
+        self.push(AtmosphericDrag::earth(density));
+    }
+
+    /// Earth atmospheric drag with an explicit ballistic coefficient m/(C_d A) [kg/m²]
+    #[wasm_bindgen]
+    pub fn add_drag_ballistic(&mut self, density: DensityModel, ballistic_coefficient: f64) {
+        self.push(AtmosphericDrag { ballistic_coefficient: Some(ballistic_coefficient), ..AtmosphericDrag::earth(density) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MU_EARTH;
+    use crate::events::{dop853_events, EventSet};
+    use crate::forces::{force_model_rhs, CompositeForceModel, PointMass};
+    use crate::rk45::AdaptiveOptions;
+
+    #[test]
+    fn us76_matches_standard_tables() {
+        let cases = [(0.0, 1.2250), (5e3, 0.73643), (11e3, 0.36480), (20e3, 8.8910e-2), (50e3, 1.0269e-3), (80e3, 1.8458e-5)];
+        for (h, rho) in cases {
+            let got = us76_density(h);
+            assert!((got - rho).abs() < 2e-3 * rho, "{} m: {} vs {}", h, got, rho);
+        }
+        // Hydrostatic branch joins the upper table at 86 km
+        assert!((us76_density(85_999.0) / us76_density(86_000.0) - 1.0).abs() < 5e-3);
+        assert!((us76_density(400e3) - 2.803e-12).abs() < 1e-16);
+        assert!((exponential_density(300e3) - 2.418e-11).abs() < 1e-16);
+    }
+
+    #[test]
+    fn circular_300_km_decay_matches_lifetime_quadrature() {
+        let bc = 50.0;
+        let drag = AtmosphericDrag {
+            density: DensityModel::Exponential,
+            body_radius: R_EARTH,
+            rotation_rate: 0.0,
+            ballistic_coefficient: Some(bc),
+        };
+        let mut model = CompositeForceModel::new();
+        model.push(PointMass { mu: MU_EARTH });
+        model.push(drag);
+        let params = SpacecraftParams::default();
+
+        let a0 = R_EARTH + 300e3;
+        let v0 = (MU_EARTH / a0).sqrt();
+        let inc = 51.6f64.to_radians();
+        let y0 = [a0, 0.0, 0.0, 0.0, v0 * inc.cos(), v0 * inc.sin()];
+        let mut events = EventSet::new();
+        events.add_altitude(120e3, R_EARTH, -1, true);
+        let rhs = force_model_rhs(&model, &params);
+        let res = dop853_events(&rhs, &y0, 200.0 * 86400.0, &AdaptiveOptions::new(1e-4, 1e-10), &events).unwrap();
+        assert!(res.terminated());
+
+        // Circular decay da/dt = −ρ √(μa) / B, integrated in altitude
+        let mut lifetime = 0.0;
+        let dh = 100.0;
+        let mut h = 300e3;
+        while h > 120e3 + 0.5 * dh {
+            let hm = h - 0.5 * dh;
+            lifetime += dh * bc / (exponential_density(hm) * (MU_EARTH * (R_EARTH + hm)).sqrt());
+            h -= dh;
+        }
+        let days = res.final_time() / 86400.0;
+        assert!(days > 5.0 && days < 60.0, "{} days", days);
+        assert!((res.final_time() - lifetime).abs() < 0.05 * lifetime, "{} vs {} s", res.final_time(), lifetime);
+    }
+}
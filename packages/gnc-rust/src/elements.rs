@@ -0,0 +1,509 @@
+//! ID: WASM-ELEM-001
+//! Requirement: Convert Cartesian states to and from classical (Keplerian),
+//!   equinoctial and modified equinoctial elements, covering circular,
+//!   equatorial, parabolic and hyperbolic orbits, plus anomaly conversions.
+//! Purpose: Osculating-element plots and planning inputs without the ad hoc
+//!   element math currently duplicated in the TypeScript UI.
+//! Rationale: Classical elements are kept in terms of the semi-latus rectum p so
+//!   parabolic orbits stay representable; where Ω or ω is undefined it is set to
+//!   zero and the angle is carried by ω (longitude of periapsis) or ν (argument of
+//!   latitude / true longitude), so the round trip is exact in every special case.
+//!   The equinoctial sets are computed directly from the state via the
+//!   equinoctial frame, so they never pass through the singular angles.
+//! Inputs: State6 [m, m/s], μ [m³/s²]; angles [rad]
+//! Outputs: element sets (lengths [m], angles [rad] in [0, 2π))
+//! Assumptions: equinoctial sets use the prograde form (singular only at i = π);
+//!   Broucke–Cefola equinoctial elements require e < 1. The batch Keplerian
+//!   layout reports a, so parabolic rows there do not convert back.
+//! References: Vallado Algorithms 2–4, 9–10 and §2.2; Broucke & Cefola (1972)
+//!   Celest. Mech. 5; Walker, Ireland & Owens (1985) Celest. Mech. 36
+
+use std::f64::consts::{PI, TAU};
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::frames::{cross, mat_vec, mat_mul, rot1, rot3, transpose};
+use crate::rk4::State6;
+
+/// Below this, e is treated as circular and sin i as equatorial.
+const SINGULAR_TOL: f64 = 1e-11;
+/// |e − 1| below this is treated as parabolic.
+const PARABOLIC_TOL: f64 = 1e-9;
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: &[f64]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Angle between two vectors in [0, π], well conditioned near 0 and π.
+fn angle_between(a: &[f64], b: &[f64]) -> f64 {
+    norm(&cross(a, b)).atan2(dot(a, b))
+}
+
+/// Eccentric anomaly E (elliptic), H (hyperbolic) or D = tan(ν/2) (parabolic).
+pub fn true_to_eccentric(nu: f64, e: f64) -> f64 {
+    if (e - 1.0).abs() < PARABOLIC_TOL {
+        (nu / 2.0).tan()
+    } else if e < 1.0 {
+        let (s, c) = nu.sin_cos();
+        ((1.0 - e * e).sqrt() * s).atan2(e + c)
+    } else {
+        2.0 * (((e - 1.0) / (e + 1.0)).sqrt() * (nu / 2.0).tan()).atanh()
+    }
+}
+
+/// Inverse of true_to_eccentric.
+pub fn eccentric_to_true(ea: f64, e: f64) -> f64 {
+    if (e - 1.0).abs() < PARABOLIC_TOL {
+        2.0 * ea.atan()
+    } else if e < 1.0 {
+        let (s, c) = ea.sin_cos();
+        ((1.0 - e * e).sqrt() * s).atan2(c - e)
+    } else {
+        2.0 * (((e + 1.0) / (e - 1.0)).sqrt() * (ea / 2.0).tanh()).atan()
+    }
+}
+
+/// Mean anomaly from E, H or D (Kepler's and Barker's equations).
+pub fn eccentric_to_mean(ea: f64, e: f64) -> f64 {
+    if (e - 1.0).abs() < PARABOLIC_TOL {
+        ea + ea * ea * ea / 3.0
+    } else if e < 1.0 {
+        ea - e * ea.sin()
+    } else {
+        e * ea.sinh() - ea
+    }
+}
+
+/// Solve Kepler's (or Barker's) equation for E, H or D.
+pub fn mean_to_eccentric(m: f64, e: f64) -> f64 {
+    if (e - 1.0).abs() < PARABOLIC_TOL {
+        // Closed-form cubic root of D + D³/3 = M
+        let w = (1.5 * m + (1.0 + 2.25 * m * m).sqrt()).cbrt();
+        return w - 1.0 / w;
+    }
+    if e < 1.0 {
+        let m = (m + PI).rem_euclid(TAU) - PI;
+        let mut ea = if e > 0.8 { PI.copysign(m) } else { m + e * m.sin() };
+        for _ in 0..50 {
+            let step = (ea - e * ea.sin() - m) / (1.0 - e * ea.cos());
+            ea -= step;
+            if step.abs() < 1e-15 {
+                break;
+            }
+        }
+        return ea;
+    }
+    let mut h = (2.0 * m.abs() / e + 1.8).ln().copysign(m);
+    for _ in 0..100 {
+        let step = (e * h.sinh() - h - m) / (e * h.cosh() - 1.0);
+        h -= step;
+        if step.abs() < 1e-15 * h.abs().max(1.0) {
+            break;
+        }
+    }
+    h
+}
+
+#[wasm_bindgen]
+pub fn true_to_mean_anomaly(nu: f64, e: f64) -> f64 {
+    eccentric_to_mean(true_to_eccentric(nu, e), e)
+}
+
+#[wasm_bindgen]
+pub fn mean_to_true_anomaly(m: f64, e: f64) -> f64 {
+    eccentric_to_true(mean_to_eccentric(m, e), e)
+}
+
+#[wasm_bindgen]
+pub fn true_to_eccentric_anomaly(nu: f64, e: f64) -> f64 {
+    true_to_eccentric(nu, e)
+}
+
+#[wasm_bindgen]
+pub fn eccentric_to_true_anomaly(ea: f64, e: f64) -> f64 {
+    eccentric_to_true(ea, e)
+}
+
+/// Classical orbital elements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeplerianElements {
+    /// Semi-latus rectum p = a(1 − e²) [m]
+    pub p: f64,
+    pub e: f64,
+    /// Inclination [rad]
+    pub i: f64,
+    /// Right ascension of the ascending node [rad]; 0 for equatorial orbits
+    pub raan: f64,
+    /// Argument of periapsis [rad]; longitude of periapsis when equatorial, 0 when circular
+    pub argp: f64,
+    /// True anomaly [rad]; argument of latitude or true longitude when circular
+    pub nu: f64,
+}
+
+impl KeplerianElements {
+    /// Elements from semi-major axis (negative for hyperbolic orbits).
+    /// Parabolic orbits are rejected: a = ∞ loses p, so build them from p directly.
+    pub fn from_semi_major_axis(a: f64, e: f64, i: f64, raan: f64, argp: f64, nu: f64) -> Result<Self, String> {
+        if (e - 1.0).abs() < PARABOLIC_TOL {
+            return Err(format!("e = {} is parabolic: a does not determine the orbit size", e));
+        }
+        if e < 0.0 || (e < 1.0) != (a > 0.0) {
+            return Err(format!("a = {} and e = {} do not describe a conic", a, e));
+        }
+        Ok(KeplerianElements { p: a * (1.0 - e * e), e, i, raan, argp, nu })
+    }
+
+    /// Semi-major axis [m]: negative for hyperbolic, infinite for parabolic orbits.
+    pub fn semi_major_axis(&self) -> f64 {
+        if (self.e - 1.0).abs() < PARABOLIC_TOL {
+            f64::INFINITY
+        } else {
+            self.p / (1.0 - self.e * self.e)
+        }
+    }
+
+    pub fn mean_anomaly(&self) -> f64 {
+        true_to_mean_anomaly(self.nu, self.e)
+    }
+
+    /// Osculating elements of a state (Vallado RV2COE with special cases).
+    pub fn from_state(state: &State6, mu: f64) -> Result<Self, String> {
+        let (r, v) = (&state[..3], &state[3..]);
+        let (rn, vn) = (norm(r), norm(v));
+        let h = cross(r, v);
+        let hn = norm(&h);
+        if hn <= SINGULAR_TOL * rn * vn || rn == 0.0 {
+            return Err("rectilinear or zero state has no orbital elements".into());
+        }
+        let node = [-h[1], h[0], 0.0];
+        let rv = dot(r, v);
+        let ev: [f64; 3] = core::array::from_fn(|k| ((vn * vn - mu / rn) * r[k] - rv * v[k]) / mu);
+        let e = norm(&ev);
+        let i = angle_between(&[0.0, 0.0, 1.0], &h);
+
+        let equatorial = norm(&node) <= SINGULAR_TOL * hn;
+        let circular = e <= SINGULAR_TOL;
+        let wrap = |angle: f64, flip: bool| if flip { TAU - angle } else { angle };
+
+        let raan = if equatorial { 0.0 } else { wrap(angle_between(&[1.0, 0.0, 0.0], &node), node[1] < 0.0) };
+        let argp = if circular {
+            0.0
+        } else if equatorial {
+            // Longitude of periapsis, measured in the orbit's sense of motion
+            (ev[1].atan2(ev[0]) * h[2].signum()).rem_euclid(TAU)
+        } else {
+            wrap(angle_between(&node, &ev), ev[2] < 0.0)
+        };
+        let nu = match (circular, equatorial) {
+            (false, _) => wrap(angle_between(&ev, r), rv < 0.0),
+            (true, false) => wrap(angle_between(&node, r), r[2] < 0.0),
+            (true, true) => (r[1].atan2(r[0]) * h[2].signum()).rem_euclid(TAU),
+        };
+        Ok(KeplerianElements { p: hn * hn / mu, e, i, raan, argp, nu })
+    }
+
+    /// Cartesian state (Vallado COE2RV).
+    pub fn to_state(&self, mu: f64) -> State6 {
+        let (s, c) = self.nu.sin_cos();
+        let r = self.p / (1.0 + self.e * c);
+        let k = (mu / self.p).sqrt();
+        let q = transpose(&mat_mul(&rot3(self.argp), &mat_mul(&rot1(self.i), &rot3(self.raan))));
+        let pos = mat_vec(&q, &[r * c, r * s, 0.0]);
+        let vel = mat_vec(&q, &[-k * s, k * (self.e + c), 0.0]);
+        [pos[0], pos[1], pos[2], vel[0], vel[1], vel[2]]
+    }
+}
+
+/// Modified equinoctial elements (Walker et al.), prograde form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModifiedEquinoctialElements {
+    /// Semi-latus rectum [m]
+    pub p: f64,
+    /// e cos(ω + Ω)
+    pub f: f64,
+    /// e sin(ω + Ω)
+    pub g: f64,
+    /// tan(i/2) cos Ω
+    pub h: f64,
+    /// tan(i/2) sin Ω
+    pub k: f64,
+    /// True longitude Ω + ω + ν [rad]
+    pub l: f64,
+}
+
+impl ModifiedEquinoctialElements {
+    /// Equinoctial basis vectors f̂, ĝ (in the orbit plane, f̂ towards Ω + ω = 0).
+    fn basis(h: f64, k: f64) -> ([f64; 3], [f64; 3]) {
+        let s2 = 1.0 + h * h + k * k;
+        (
+            [(1.0 - k * k + h * h) / s2, 2.0 * h * k / s2, -2.0 * k / s2],
+            [2.0 * h * k / s2, (1.0 + k * k - h * h) / s2, 2.0 * h / s2],
+        )
+    }
+
+    pub fn from_state(state: &State6, mu: f64) -> Result<Self, String> {
+        let (r, v) = (&state[..3], &state[3..]);
+        let hv = cross(r, v);
+        let hn = norm(&hv);
+        if hn == 0.0 {
+            return Err("rectilinear state has no equinoctial elements".into());
+        }
+        let w: [f64; 3] = core::array::from_fn(|i| hv[i] / hn);
+        if w[2] <= -1.0 + SINGULAR_TOL {
+            return Err("retrograde equatorial orbit is singular in prograde equinoctial elements".into());
+        }
+        let (h, k) = (-w[1] / (1.0 + w[2]), w[0] / (1.0 + w[2]));
+        let (fb, gb) = Self::basis(h, k);
+        let (rn, vn, rv) = (norm(r), norm(v), dot(r, v));
+        let ev: [f64; 3] = core::array::from_fn(|i| ((vn * vn - mu / rn) * r[i] - rv * v[i]) / mu);
+        let l = dot(r, &gb).atan2(dot(r, &fb)).rem_euclid(TAU);
+        Ok(ModifiedEquinoctialElements { p: hn * hn / mu, f: dot(&ev, &fb), g: dot(&ev, &gb), h, k, l })
+    }
+
+    pub fn to_state(&self, mu: f64) -> State6 {
+        let (fb, gb) = Self::basis(self.h, self.k);
+        let (sl, cl) = self.l.sin_cos();
+        let r = self.p / (1.0 + self.f * cl + self.g * sl);
+        let c = (mu / self.p).sqrt();
+        let (x, y) = (r * cl, r * sl);
+        let (vx, vy) = (-c * (self.g + sl), c * (self.f + cl));
+        core::array::from_fn(|i| if i < 3 { x * fb[i] + y * gb[i] } else { vx * fb[i - 3] + vy * gb[i - 3] })
+    }
+}
+
+/// Equinoctial elements (Broucke & Cefola), prograde form, e < 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquinoctialElements {
+    /// Semi-major axis [m]
+    pub a: f64,
+    /// e sin(ω + Ω)
+    pub h: f64,
+    /// e cos(ω + Ω)
+    pub k: f64,
+    /// tan(i/2) sin Ω
+    pub p: f64,
+    /// tan(i/2) cos Ω
+    pub q: f64,
+    /// Mean longitude Ω + ω + M [rad]
+    pub lambda: f64,
+}
+
+impl EquinoctialElements {
+    pub fn from_state(state: &State6, mu: f64) -> Result<Self, String> {
+        let m = ModifiedEquinoctialElements::from_state(state, mu)?;
+        let e = m.f.hypot(m.g);
+        if e >= 1.0 {
+            return Err(format!("equinoctial elements need an elliptic orbit, e = {}", e));
+        }
+        let lp = m.g.atan2(m.f);
+        let mean = true_to_mean_anomaly(m.l - lp, e);
+        Ok(EquinoctialElements {
+            a: m.p / (1.0 - e * e),
+            h: m.g,
+            k: m.f,
+            p: m.k,
+            q: m.h,
+            lambda: (mean + lp).rem_euclid(TAU),
+        })
+    }
+
+    pub fn to_state(&self, mu: f64) -> State6 {
+        let e = self.h.hypot(self.k);
+        let lp = self.h.atan2(self.k);
+        let l = mean_to_true_anomaly(self.lambda - lp, e) + lp;
+        ModifiedEquinoctialElements { p: self.a * (1.0 - e * e), f: self.k, g: self.h, h: self.q, k: self.p, l }.to_state(mu)
+    }
+}
+
+/// Element sets available through the batch API.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementSet {
+    /// [a, e, i, Ω, ω, ν]; a is negative for hyperbolic, Infinity for parabolic
+    /// orbits. Parabolic rows carry no size, so they cannot be converted back;
+    /// use ModifiedEquinoctial (which keeps p) for arcs that may reach e = 1.
+    Keplerian = 0,
+    /// [a, h, k, p, q, λ] (Broucke–Cefola)
+    Equinoctial = 1,
+    /// [p, f, g, h, k, L] (Walker et al.)
+    ModifiedEquinoctial = 2,
+}
+
+/// Elements of one state in the layout of `set`.
+pub fn state_to_elements(state: &State6, mu: f64, set: ElementSet) -> Result<[f64; 6], String> {
+    Ok(match set {
+        ElementSet::Keplerian => {
+            let k = KeplerianElements::from_state(state, mu)?;
+            [k.semi_major_axis(), k.e, k.i, k.raan, k.argp, k.nu]
+        }
+        ElementSet::Equinoctial => {
+            let q = EquinoctialElements::from_state(state, mu)?;
+            [q.a, q.h, q.k, q.p, q.q, q.lambda]
+        }
+        ElementSet::ModifiedEquinoctial => {
+            let m = ModifiedEquinoctialElements::from_state(state, mu)?;
+            [m.p, m.f, m.g, m.h, m.k, m.l]
+        }
+    })
+}
+
+/// Inverse of state_to_elements.
+pub fn elements_to_state(el: &[f64], mu: f64, set: ElementSet) -> Result<State6, String> {
+    Ok(match set {
+        ElementSet::Keplerian => KeplerianElements::from_semi_major_axis(el[0], el[1], el[2], el[3], el[4], el[5])?.to_state(mu),
+        ElementSet::Equinoctial => {
+            EquinoctialElements { a: el[0], h: el[1], k: el[2], p: el[3], q: el[4], lambda: el[5] }.to_state(mu)
+        }
+        ElementSet::ModifiedEquinoctial => {
+            ModifiedEquinoctialElements { p: el[0], f: el[1], g: el[2], h: el[3], k: el[4], l: el[5] }.to_state(mu)
+        }
+    })
+}
+
+/// Osculating elements along a flat trajectory [rx,ry,rz,vx,vy,vz, …] (e.g.
+/// rk4_trajectory output): six values per state in the layout of `set`.
+#[wasm_bindgen]
+pub fn osculating_elements(states_js: &Float64Array, mu: f64, set: ElementSet) -> Result<Float64Array, JsValue> {
+    let states = states_js.to_vec();
+    if !states.len().is_multiple_of(6) {
+        return Err(JsValue::from_str("states length must be a multiple of 6"));
+    }
+    let mut out = Vec::with_capacity(states.len());
+    for s in states.chunks_exact(6) {
+        let el = state_to_elements(&[s[0], s[1], s[2], s[3], s[4], s[5]], mu, set).map_err(|e| JsValue::from_str(&e))?;
+        out.extend_from_slice(&el);
+    }
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+/// Cartesian states from flat element rows in the layout of `set`.
+/// Fails on parabolic Keplerian rows (a = Infinity).
+#[wasm_bindgen]
+pub fn elements_to_states(elements_js: &Float64Array, mu: f64, set: ElementSet) -> Result<Float64Array, JsValue> {
+    let elements = elements_js.to_vec();
+    if !elements.len().is_multiple_of(6) {
+        return Err(JsValue::from_str("elements length must be a multiple of 6"));
+    }
+    let mut out = Vec::with_capacity(elements.len());
+    for el in elements.chunks_exact(6) {
+        out.extend_from_slice(&elements_to_state(el, mu, set).map_err(|e| JsValue::from_str(&e))?);
+    }
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MU_EARTH;
+
+    fn assert_state_close(a: &State6, b: &State6) {
+        let (rs, vs) = (norm(&b[..3]), norm(&b[3..]));
+        for i in 0..6 {
+            let scale = if i < 3 { rs } else { vs };
+            assert!((a[i] - b[i]).abs() < 1e-11 * scale, "[{}] {} vs {}", i, a[i], b[i]);
+        }
+    }
+
+    #[test]
+    fn vallado_example_and_special_cases_round_trip() {
+        // Vallado Example 2-5
+        let s = [6524.834e3, 6862.875e3, 6448.296e3, 4.901327e3, 5.533756e3, -1.976341e3];
+        let k = KeplerianElements::from_state(&s, MU_EARTH).unwrap();
+        assert!((k.p - 11067.790e3).abs() < 1e2);
+        assert!((k.semi_major_axis() - 36127.343e3).abs() < 1e3);
+        assert!((k.e - 0.832853).abs() < 1e-5);
+        for (got, want) in [(k.i, 87.870), (k.raan, 227.898), (k.argp, 53.38), (k.nu, 92.335)] {
+            assert!((got.to_degrees() - want).abs() < 1e-2, "{} vs {}", got.to_degrees(), want);
+        }
+
+        let (r, vc) = (7.0e6, (MU_EARTH / 7.0e6).sqrt());
+        let cases: [State6; 7] = [
+            s,
+            [r, 0.0, 0.0, 0.0, vc, 0.0],                              // circular equatorial
+            [0.0, r, 0.0, 0.0, 0.0, vc],                              // circular polar
+            [r, 0.0, 0.0, 0.0, 1.1 * vc, 0.0],                        // elliptic equatorial
+            [-r, 0.3 * r, 0.0, 0.1 * vc, 0.9 * vc, 0.0],              // retrograde equatorial
+            [r, 0.0, 1e5, 0.0, 1.6 * vc, 0.4 * vc],                   // hyperbolic
+            [r, 0.0, 0.0, 0.0, (2.0f64).sqrt() * vc * 0.8, (2.0f64).sqrt() * vc * 0.6], // parabolic
+        ];
+        for (n, c) in cases.iter().enumerate() {
+            let k = KeplerianElements::from_state(c, MU_EARTH).unwrap();
+            assert_state_close(&k.to_state(MU_EARTH), c);
+            if n != 4 {
+                let m = ModifiedEquinoctialElements::from_state(c, MU_EARTH).unwrap();
+                assert_state_close(&m.to_state(MU_EARTH), c);
+            }
+        }
+        // Circular polar orbit: ν carries the argument of latitude, 0 at the node
+        let polar = KeplerianElements::from_state(&cases[2], MU_EARTH).unwrap();
+        assert_eq!((polar.argp, polar.e < 1e-12), (0.0, true));
+        assert!((polar.nu).abs() < 1e-12 && (polar.raan.to_degrees() - 90.0).abs() < 1e-9);
+        assert!((KeplerianElements::from_state(&cases[6], MU_EARTH).unwrap().e - 1.0).abs() < 1e-12);
+        assert!(KeplerianElements::from_state(&[r, 0.0, 0.0, 1e3, 0.0, 0.0], MU_EARTH).is_err());
+    }
+
+    #[test]
+    fn anomaly_conversions_invert() {
+        for e in [0.0, 0.3, 0.97, 1.0, 1.4, 6.0] {
+            for nu_deg in [-170.0, -45.0, 0.0, 10.0, 95.0, 179.0] {
+                let nu = f64::to_radians(nu_deg);
+                if e > 1.0 && nu.cos() <= -1.0 / e {
+                    continue; // beyond the hyperbolic asymptote
+                }
+                let m = true_to_mean_anomaly(nu, e);
+                let back = mean_to_true_anomaly(m, e);
+                assert!((back - nu).abs() < 1e-10, "e={} ν={}: {}", e, nu_deg, back.to_degrees());
+            }
+        }
+        // Kepler's equation at large M, high e (Vallado Example 2-1: M = 235.4°, e = 0.4)
+        let ea = mean_to_eccentric(235.4f64.to_radians(), 0.4);
+        assert!((ea.rem_euclid(TAU).to_degrees() - 220.512074767522).abs() < 1e-9);
+        // Hyperbolic: Vallado Example 2-3 (M = 235.4°, e = 2.4 → H = 1.601376144)
+        assert!((mean_to_eccentric(235.4f64.to_radians(), 2.4) - 1.601376144).abs() < 1e-8);
+    }
+
+    #[test]
+    fn equinoctial_sets_agree_with_classical() {
+        let k = KeplerianElements::from_semi_major_axis(2.6e7, 0.7, 1.1, 4.0, 5.5, 2.0).unwrap();
+        let s = k.to_state(MU_EARTH);
+        let q = EquinoctialElements::from_state(&s, MU_EARTH).unwrap();
+        let lp = k.raan + k.argp;
+        assert!((q.a - 2.6e7).abs() < 1e-5);
+        assert!((q.h - 0.7 * lp.sin()).abs() < 1e-12 && (q.k - 0.7 * lp.cos()).abs() < 1e-12);
+        assert!((q.p - (0.55f64).tan() * 4f64.sin()).abs() < 1e-12);
+        let dl = (q.lambda - lp - k.mean_anomaly() + PI).rem_euclid(TAU) - PI;
+        assert!(dl.abs() < 1e-12);
+        assert_state_close(&q.to_state(MU_EARTH), &s);
+
+        let m = ModifiedEquinoctialElements::from_state(&s, MU_EARTH).unwrap();
+        let dl = (m.l - lp - k.nu + PI).rem_euclid(TAU) - PI;
+        assert!(dl.abs() < 1e-12);
+        assert!((m.h - (0.55f64).tan() * 4f64.cos()).abs() < 1e-12);
+        for set in [ElementSet::Keplerian, ElementSet::Equinoctial, ElementSet::ModifiedEquinoctial] {
+            let el = state_to_elements(&s, MU_EARTH, set).unwrap();
+            assert_state_close(&elements_to_state(&el, MU_EARTH, set).unwrap(), &s);
+        }
+    }
+
+    #[test]
+    fn parabolic_rows_round_trip_only_through_modified_equinoctial() {
+        let r = 7.0e6;
+        let v = (2.0 * MU_EARTH / r).sqrt();
+        let s: State6 = [r, 0.0, 0.0, 0.0, 0.8 * v, 0.6 * v];
+
+        let row = state_to_elements(&s, MU_EARTH, ElementSet::Keplerian).unwrap();
+        assert_eq!(row[0], f64::INFINITY);
+        assert!((row[1] - 1.0).abs() < 1e-12);
+        let err = elements_to_state(&row, MU_EARTH, ElementSet::Keplerian).unwrap_err();
+        assert!(err.contains("parabolic"), "{}", err);
+
+        let row = state_to_elements(&s, MU_EARTH, ElementSet::ModifiedEquinoctial).unwrap();
+        assert!((row[0] - 2.0 * r).abs() < 1e-3);
+        assert_state_close(&elements_to_state(&row, MU_EARTH, ElementSet::ModifiedEquinoctial).unwrap(), &s);
+    }
+}
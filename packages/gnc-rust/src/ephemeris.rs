@@ -0,0 +1,513 @@
+//! ID: WASM-EPHEM-001
+//! Requirement: Provide analytic low-precision positions of the Sun, Moon and
+//!   planets by epoch, and a third-body perturbation model that uses them.
+//! Purpose: Remove the need to integrate the Sun and Moon with nbody_rk4_step
+//!   just to obtain their pull on a spacecraft, which is slow and drifts.
+//! Rationale: Truncated series for the Sun (≈0.01°) and Moon (≈ several arcmin,
+//!   ≈500 km) and Standish's mean Keplerian elements for the planets (≈ arcmin
+//!   1800–2050) are ample for perturbation forces and cost a few sin/cos calls.
+//! Inputs: epoch as Julian date on the TDB scale (TT is interchangeable here)
+//! Outputs: geometric positions [m] in the mean equator and equinox of J2000
+//!   (EME2000 ≈ GCRF to tens of mas), relative to a chosen centre body
+//! References: Meeus "Astronomical Algorithms" ch. 25 (Sun);
+//!   Montenbruck & Gill §3.3.2 (Moon series after Brown);
+//!   Standish "Keplerian Elements for Approximate Positions of the Major
+//!   Planets" (JPL SSD), Table 1
+
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::constants::*;
+use crate::forces::{ForceModel, ForceModelBuilder, SpacecraftParams};
+use crate::rk4::State6;
+use crate::spk::{SpkEphemeris, SpkKernel};
+use crate::stm::{two_body_gradient, Mat3};
+
+/// Bodies with built-in analytic ephemerides.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EphemerisBody {
+    Sun = 0,
+    Mercury = 1,
+    Venus = 2,
+    Earth = 3,
+    Moon = 4,
+    Mars = 5,
+    Jupiter = 6,
+    Saturn = 7,
+    Uranus = 8,
+    Neptune = 9,
+}
+
+impl EphemerisBody {
+    /// Gravitational parameter [m³/s²] (system values for the giant planets)
+    pub fn mu(self) -> f64 {
+        match self {
+            EphemerisBody::Sun => MU_SUN,
+            EphemerisBody::Mercury => MU_MERCURY,
+            EphemerisBody::Venus => MU_VENUS,
+            EphemerisBody::Earth => MU_EARTH,
+            EphemerisBody::Moon => MU_MOON,
+            EphemerisBody::Mars => MU_MARS,
+            EphemerisBody::Jupiter => MU_JUPITER,
+            EphemerisBody::Saturn => MU_SATURN,
+            EphemerisBody::Uranus => MU_URANUS,
+            EphemerisBody::Neptune => MU_NEPTUNE,
+        }
+    }
+
+    /// NAIF integer id; planets other than Earth map to their system barycentre.
+    pub fn naif_id(self) -> i32 {
+        match self {
+            EphemerisBody::Sun => 10,
+            EphemerisBody::Mercury => 1,
+            EphemerisBody::Venus => 2,
+            EphemerisBody::Earth => 399,
+            EphemerisBody::Moon => 301,
+            EphemerisBody::Mars => 4,
+            EphemerisBody::Jupiter => 5,
+            EphemerisBody::Saturn => 6,
+            EphemerisBody::Uranus => 7,
+            EphemerisBody::Neptune => 8,
+        }
+    }
+}
+
+const ARCSEC: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+#[inline]
+fn centuries(jd_tdb: f64) -> f64 {
+    (jd_tdb - JD_J2000) / DAYS_PER_CENTURY
+}
+
+/// Ecliptic of J2000 → mean equator of J2000.
+#[inline]
+pub(crate) fn ecliptic_to_equatorial(v: [f64; 3]) -> [f64; 3] {
+    let (s, c) = OBLIQUITY_J2000.sin_cos();
+    [v[0], c * v[1] - s * v[2], s * v[1] + c * v[2]]
+}
+
+fn spherical(lon: f64, lat: f64, r: f64) -> [f64; 3] {
+    [r * lon.cos() * lat.cos(), r * lon.sin() * lat.cos(), r * lat.sin()]
+}
+
+/// Geocentric Sun [m], EME2000 (Meeus ch. 25 low-accuracy series, ≈0.01°).
+pub fn sun_position(jd_tdb: f64) -> [f64; 3] {
+    let t = centuries(jd_tdb);
+    let l0 = 280.46646 + 36000.76983 * t + 0.0003032 * t * t;
+    let m = (357.52911 + 35999.05029 * t - 0.0001537 * t * t).to_radians();
+    let e = 0.016708634 - 0.000042037 * t - 0.0000001267 * t * t;
+    let c = (1.914602 - 0.004817 * t - 0.000014 * t * t) * m.sin()
+        + (0.019993 - 0.000101 * t) * (2.0 * m).sin()
+        + 0.000289 * (3.0 * m).sin();
+    // True longitude of date, referred back to the J2000 equinox
+    let lon = (l0 + c - 1.3972 * t).to_radians();
+    let nu = m + c.to_radians();
+    let r = 1.000001018 * (1.0 - e * e) / (1.0 + e * nu.cos()) * AU;
+    ecliptic_to_equatorial(spherical(lon, 0.0, r))
+}
+
+/// Geocentric Moon [m], EME2000.
+pub fn moon_position(jd_tdb: f64) -> [f64; 3] {
+    let t = centuries(jd_tdb);
+    let deg = |x: f64| x.to_radians();
+    // Mean longitude (referred to the J2000 equinox), anomalies, argument of latitude, elongation
+    let l0 = deg(218.31617 + 481267.88088 * t - 1.3972 * t);
+    let l = deg(134.96292 + 477198.86753 * t);
+    let lp = deg(357.52543 + 35999.04944 * t);
+    let f = deg(93.27283 + 483202.01873 * t);
+    let d = deg(297.85027 + 445267.11135 * t);
+
+    let dlon = 22640.0 * l.sin() + 769.0 * (2.0 * l).sin() - 4586.0 * (l - 2.0 * d).sin()
+        + 2370.0 * (2.0 * d).sin() - 668.0 * lp.sin() - 412.0 * (2.0 * f).sin()
+        - 212.0 * (2.0 * l - 2.0 * d).sin() - 206.0 * (l + lp - 2.0 * d).sin()
+        + 192.0 * (l + 2.0 * d).sin() - 165.0 * (lp - 2.0 * d).sin() + 148.0 * (l - lp).sin()
+        - 125.0 * d.sin() - 110.0 * (l + lp).sin() - 55.0 * (2.0 * f - 2.0 * d).sin();
+    let lon = l0 + dlon * ARCSEC;
+
+    let lat = (18520.0 * (f + lon - l0 + (412.0 * (2.0 * f).sin() + 541.0 * lp.sin()) * ARCSEC).sin()
+        - 526.0 * (f - 2.0 * d).sin() + 44.0 * (l + f - 2.0 * d).sin()
+        - 31.0 * (-l + f - 2.0 * d).sin() - 25.0 * (-2.0 * l + f).sin()
+        - 23.0 * (lp + f - 2.0 * d).sin() + 21.0 * (-l + f).sin()
+        + 11.0 * (-lp + f - 2.0 * d).sin())
+        * ARCSEC;
+
+    let r = (385000.0 - 20905.0 * l.cos() - 3699.0 * (2.0 * d - l).cos() - 2956.0 * (2.0 * d).cos()
+        - 570.0 * (2.0 * l).cos() + 246.0 * (2.0 * l - 2.0 * d).cos() - 205.0 * (lp - 2.0 * d).cos()
+        - 171.0 * (l + 2.0 * d).cos() - 152.0 * (l + lp - 2.0 * d).cos())
+        * 1e3;
+
+    ecliptic_to_equatorial(spherical(lon, lat, r))
+}
+
+/// Standish Table 1 (1800–2050): a [AU], e, I, L, ϖ, Ω [deg] and rates per century.
+#[rustfmt::skip]
+const PLANET_ELEMENTS: [([f64; 6], [f64; 6]); 8] = [
+    // Mercury
+    ([0.38709927, 0.20563593, 7.00497902, 252.25032350, 77.45779628, 48.33076593],
+     [0.00000037, 0.00001906, -0.00594749, 149472.67411175, 0.16047689, -0.12534081]),
+    // Venus
+    ([0.72333566, 0.00677672, 3.39467605, 181.97909950, 131.60246718, 76.67984255],
+     [0.00000390, -0.00004107, -0.00078890, 58517.81538729, 0.00268329, -0.27769418]),
+    // Earth–Moon barycentre
+    ([1.00000261, 0.01671123, -0.00001531, 100.46457166, 102.93768193, 0.0],
+     [0.00000562, -0.00004392, -0.01294668, 35999.37244981, 0.32327364, 0.0]),
+    // Mars
+    ([1.52371034, 0.09339410, 1.84969142, -4.55343205, -23.94362959, 49.55953891],
+     [0.00001847, 0.00007882, -0.00813131, 19140.30268499, 0.44441088, -0.29257343]),
+    // Jupiter
+    ([5.20288700, 0.04838624, 1.30439695, 34.39644051, 14.72847983, 100.47390909],
+     [-0.00011607, -0.00013253, -0.00183714, 3034.74612775, 0.21252668, 0.20469106]),
+    // Saturn
+    ([9.53667594, 0.05386179, 2.48599187, 49.95424423, 92.59887831, 113.66242448],
+     [-0.00125060, -0.00050991, 0.00193609, 1222.49362201, -0.41897216, -0.28867794]),
+    // Uranus
+    ([19.18916464, 0.04725744, 0.77263783, 313.23810451, 170.95427630, 74.01692503],
+     [-0.00196176, -0.00004397, -0.00242939, 428.48202785, 0.40805281, 0.04240589]),
+    // Neptune
+    ([30.06992276, 0.00859048, 1.77004347, -55.12002969, 44.96476227, 131.78422574],
+     [0.00026291, 0.00005105, 0.00035372, 218.45945325, -0.32241464, -0.00508664]),
+];
+
+/// Heliocentric position [m], EME2000, from Standish mean elements.
+/// `index` follows PLANET_ELEMENTS (2 = Earth–Moon barycentre).
+fn standish_heliocentric(index: usize, jd_tdb: f64) -> [f64; 3] {
+    let t = centuries(jd_tdb);
+    let (el0, rate) = PLANET_ELEMENTS[index];
+    let el: [f64; 6] = core::array::from_fn(|k| el0[k] + rate[k] * t);
+    let (a, e) = (el[0] * AU, el[1]);
+    let (inc, lon_peri, node) = (el[2].to_radians(), el[4].to_radians(), el[5].to_radians());
+    let omega = lon_peri - node;
+    let m = (el[3] - el[4]).to_radians().sin().atan2((el[3] - el[4]).to_radians().cos());
+
+    // Kepler's equation, Newton from E = M + e sin M
+    let mut ea = m + e * m.sin();
+    for _ in 0..10 {
+        let de = (ea - e * ea.sin() - m) / (1.0 - e * ea.cos());
+        ea -= de;
+        if de.abs() < 1e-14 {
+            break;
+        }
+    }
+    let xp = a * (ea.cos() - e);
+    let yp = a * (1.0 - e * e).sqrt() * ea.sin();
+
+    let (so, co) = omega.sin_cos();
+    let (sn, cn) = node.sin_cos();
+    let (si, ci) = inc.sin_cos();
+    ecliptic_to_equatorial([
+        (co * cn - so * sn * ci) * xp + (-so * cn - co * sn * ci) * yp,
+        (co * sn + so * cn * ci) * xp + (-so * sn + co * cn * ci) * yp,
+        so * si * xp + co * si * yp,
+    ])
+}
+
+fn planet_index(body: EphemerisBody) -> Option<usize> {
+    match body {
+        EphemerisBody::Mercury => Some(0),
+        EphemerisBody::Venus => Some(1),
+        EphemerisBody::Mars => Some(3),
+        EphemerisBody::Jupiter => Some(4),
+        EphemerisBody::Saturn => Some(5),
+        EphemerisBody::Uranus => Some(6),
+        EphemerisBody::Neptune => Some(7),
+        _ => None,
+    }
+}
+
+/// Geocentric position of `body` [m], EME2000.
+pub fn geocentric_position(body: EphemerisBody, jd_tdb: f64) -> [f64; 3] {
+    match body {
+        EphemerisBody::Earth => [0.0; 3],
+        EphemerisBody::Sun => sun_position(jd_tdb),
+        EphemerisBody::Moon => moon_position(jd_tdb),
+        planet => {
+            let h = standish_heliocentric(planet_index(planet).unwrap(), jd_tdb);
+            let s = sun_position(jd_tdb);
+            [h[0] + s[0], h[1] + s[1], h[2] + s[2]]
+        }
+    }
+}
+
+/// Position of `body` relative to `center` [m], EME2000.
+pub fn body_position(body: EphemerisBody, center: EphemerisBody, jd_tdb: f64) -> [f64; 3] {
+    let b = geocentric_position(body, jd_tdb);
+    let c = geocentric_position(center, jd_tdb);
+    [b[0] - c[0], b[1] - c[1], b[2] - c[2]]
+}
+
+/// Position and velocity [m, m/s] of `body` relative to `center`, EME2000.
+/// The series are position-only, so the velocity is a central difference over
+/// ±0.01 d, well below both the series error and the orbital curvature.
+pub fn body_state(body: EphemerisBody, center: EphemerisBody, jd_tdb: f64) -> State6 {
+    const H_DAYS: f64 = 0.01;
+    let r = body_position(body, center, jd_tdb);
+    let rp = body_position(body, center, jd_tdb + H_DAYS);
+    let rm = body_position(body, center, jd_tdb - H_DAYS);
+    let k = 1.0 / (2.0 * H_DAYS * 86400.0);
+    [r[0], r[1], r[2], (rp[0] - rm[0]) * k, (rp[1] - rm[1]) * k, (rp[2] - rm[2]) * k]
+}
+
+/// Where body positions come from.
+#[derive(Debug, Clone, Default)]
+pub enum EphemerisSource {
+    /// Built-in analytic series
+    #[default]
+    Analytic,
+    /// SPK kernel; epochs outside its coverage fall back to the analytic series
+    Spk(Arc<SpkKernel>),
+}
+
+impl EphemerisSource {
+    /// Position of `body` relative to `center` [m] at Julian date (TDB).
+    pub fn position(&self, body: EphemerisBody, center: EphemerisBody, jd_tdb: f64) -> [f64; 3] {
+        if let EphemerisSource::Spk(kernel) = self {
+            let et = (jd_tdb - JD_J2000) * 86400.0;
+            if let Ok(s) = kernel.state(body.naif_id(), center.naif_id(), et) {
+                return [s[0], s[1], s[2]];
+            }
+        }
+        body_position(body, center, jd_tdb)
+    }
+}
+
+/// Point-mass attraction of a third body, with the centre's own acceleration
+/// towards it removed: a = μ_b [(r_b − r)/|r_b − r|³ − r_b/|r_b|³].
+#[derive(Debug, Clone)]
+pub struct ThirdBody {
+    pub body: EphemerisBody,
+    /// Centre of the propagation frame
+    pub center: EphemerisBody,
+    /// Julian date (TDB) at propagation time t = 0
+    pub epoch_jd: f64,
+    pub source: EphemerisSource,
+}
+
+impl ThirdBody {
+    /// Validated constructor: the perturbing body cannot be the frame centre,
+    /// where r_b = 0 makes the indirect term 0/0.
+    pub fn new(body: EphemerisBody, center: EphemerisBody, epoch_jd: f64, source: EphemerisSource) -> Result<Self, String> {
+        if body == center {
+            return Err(format!("Third body {:?} is the propagation centre", body));
+        }
+        if !epoch_jd.is_finite() {
+            return Err(format!("Invalid third-body epoch {}", epoch_jd));
+        }
+        Ok(ThirdBody { body, center, epoch_jd, source })
+    }
+
+    fn relative(&self, t: f64, state: &State6) -> ([f64; 3], [f64; 3]) {
+        let rb = self.source.position(self.body, self.center, self.epoch_jd + t / 86400.0);
+        (rb, [rb[0] - state[0], rb[1] - state[1], rb[2] - state[2]])
+    }
+}
+
+impl ForceModel for ThirdBody {
+    fn acceleration(&self, t: f64, state: &State6, _params: &SpacecraftParams) -> [f64; 3] {
+        let mu = self.body.mu();
+        let (rb, d) = self.relative(t, state);
+        let d3 = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).powf(1.5);
+        let rb3 = (rb[0] * rb[0] + rb[1] * rb[1] + rb[2] * rb[2]).powf(1.5);
+        core::array::from_fn(|i| mu * (d[i] / d3 - rb[i] / rb3))
+    }
+
+    /// ∂a/∂r is the point-mass gradient evaluated at the spacecraft–body vector.
+    fn position_gradient(&self, t: f64, state: &State6, _params: &SpacecraftParams) -> Option<Mat3> {
+        let (_, d) = self.relative(t, state);
+        Some(two_body_gradient(&d, self.body.mu()))
+    }
+}
+
+#[wasm_bindgen]
+impl ForceModelBuilder {
+    /// Third-body gravity of `body` on a spacecraft propagated about `center`;
+    /// propagation time t = 0 corresponds to Julian date `epoch_jd` (TDB).
+    /// The first third body sets the builder epoch if set_epoch was not
+    /// called; fails if `epoch_jd` disagrees with the builder epoch or `body`
+    /// is `center`.
+    #[wasm_bindgen]
+    pub fn add_third_body(&mut self, body: EphemerisBody, center: EphemerisBody, epoch_jd: f64) -> Result<(), JsValue> {
+        self.try_add_third_body(body, center, epoch_jd, EphemerisSource::Analytic)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// add_third_body with positions from an SPK kernel (analytic outside its coverage)
+    #[wasm_bindgen]
+    pub fn add_third_body_spk(
+        &mut self,
+        body: EphemerisBody,
+        center: EphemerisBody,
+        epoch_jd: f64,
+        kernel: &SpkEphemeris,
+    ) -> Result<(), JsValue> {
+        self.try_add_third_body(body, center, epoch_jd, EphemerisSource::Spk(kernel.kernel()))
+            .map_err(|e| JsValue::from_str(&e))
+    }
+}
+
+impl ForceModelBuilder {
+    /// add_third_body without the JS error conversion. The model's t = 0 is
+    /// the builder epoch, so the `*_epoch` propagators place the body correctly.
+    pub fn try_add_third_body(
+        &mut self,
+        body: EphemerisBody,
+        center: EphemerisBody,
+        epoch_jd: f64,
+        source: EphemerisSource,
+    ) -> Result<(), String> {
+        let model = ThirdBody::new(body, center, epoch_jd, source)?;
+        let epoch_jd = self.fix_epoch_jd(epoch_jd)?;
+        self.push(ThirdBody { epoch_jd, ..model });
+        Ok(())
+    }
+}
+
+/// Position [m] of `body` relative to `center` at Julian date `jd_tdb`, EME2000.
+#[wasm_bindgen]
+pub fn ephemeris_position(body: EphemerisBody, center: EphemerisBody, jd_tdb: f64) -> Float64Array {
+    Float64Array::from(&body_position(body, center, jd_tdb)[..])
+}
+
+/// Positions at many epochs; flat [x₀,y₀,z₀, x₁,…] of length 3·N.
+#[wasm_bindgen]
+pub fn ephemeris_positions(body: EphemerisBody, center: EphemerisBody, jds_tdb: &Float64Array) -> Float64Array {
+    let out: Vec<f64> = jds_tdb.to_vec().into_iter().flat_map(|jd| body_position(body, center, jd)).collect();
+    Float64Array::from(out.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::{Epoch, TimeScale};
+
+    /// Ecliptic longitude/latitude [deg] and distance of an EME2000 vector.
+    fn ecliptic(v: [f64; 3]) -> (f64, f64, f64) {
+        let (s, c) = OBLIQUITY_J2000.sin_cos();
+        let e = [v[0], c * v[1] + s * v[2], -s * v[1] + c * v[2]];
+        let r = (e[0] * e[0] + e[1] * e[1] + e[2] * e[2]).sqrt();
+        (e[1].atan2(e[0]).to_degrees().rem_euclid(360.0), (e[2] / r).asin().to_degrees(), r)
+    }
+
+    #[test]
+    fn sun_and_moon_match_meeus_examples() {
+        // Meeus 25.a, 1992-10-13.0 TD: true λ☉ 199.90988° (of date), R 0.99760775 AU.
+        // Longitudes of date in 1992 are 0.1008° smaller than J2000 ones (precession).
+        let (lon, lat, r) = ecliptic(sun_position(2448908.5));
+        assert!((lon - (199.90988 + 0.1008)).abs() < 0.02, "λ☉ {}", lon);
+        assert!(lat.abs() < 1e-9);
+        assert!((r / AU - 0.99760775).abs() < 2e-4, "R {}", r / AU);
+
+        // Meeus 47.a, 1992-04-12.0 TD: λ 133.162655° (of date), β −3.229126°, Δ 368409.7 km
+        let (lon, lat, r) = ecliptic(moon_position(2448724.5));
+        assert!((lon - (133.162655 + 0.1079)).abs() < 0.1, "λ☾ {}", lon);
+        assert!((lat + 3.229126).abs() < 0.1, "β☾ {}", lat);
+        assert!((r / 1e3 - 368409.7).abs() < 500.0, "Δ {}", r / 1e3);
+    }
+
+    #[test]
+    fn standish_earth_agrees_with_solar_series() {
+        for jd in [2451545.0, 2455000.5, 2460000.5] {
+            let emb = standish_heliocentric(2, jd);
+            let sun = sun_position(jd);
+            let err = ((emb[0] + sun[0]).powi(2) + (emb[1] + sun[1]).powi(2) + (emb[2] + sun[2]).powi(2)).sqrt();
+            // Barycentre offset (≈4700 km) plus both models' errors
+            assert!(err < 1e-4 * AU, "JD {}: {} km", jd, err / 1e3);
+        }
+        // Mars at opposition 2003-08-28: ≈0.373 AU from Earth
+        let mars = geocentric_position(EphemerisBody::Mars, 2452879.5);
+        let dist = (mars[0] * mars[0] + mars[1] * mars[1] + mars[2] * mars[2]).sqrt() / AU;
+        assert!((dist - 0.373).abs() < 0.005, "{} AU", dist);
+    }
+
+    #[test]
+    fn third_body_gradient_matches_differences() {
+        let model = ThirdBody::new(EphemerisBody::Moon, EphemerisBody::Earth, 2460000.5, EphemerisSource::Analytic).unwrap();
+        let params = SpacecraftParams::default();
+        let s = [4.2e7, 1.0e6, -3.0e6, 0.0, 0.0, 0.0];
+        let a = model.acceleration(0.0, &s, &params);
+        let an = (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt();
+        assert!(an > 1e-6 && an < 2e-5, "{}", an);
+        let g = model.position_gradient(0.0, &s, &params).unwrap();
+        for j in 0..3 {
+            let (mut sp, mut sm) = (s, s);
+            sp[j] += 100.0;
+            sm[j] -= 100.0;
+            let (ap, am) = (model.acceleration(0.0, &sp, &params), model.acceleration(0.0, &sm, &params));
+            for i in 0..3 {
+                let fd = (ap[i] - am[i]) / 200.0;
+                assert!((fd - g[i][j]).abs() < 1e-6 * g[i][j].abs().max(1e-14), "{} {}: {} vs {}", i, j, g[i][j], fd);
+            }
+        }
+    }
+
+    #[test]
+    fn third_body_rejects_its_own_centre() {
+        let err = ThirdBody::new(EphemerisBody::Earth, EphemerisBody::Earth, 2460000.5, EphemerisSource::Analytic);
+        assert!(err.unwrap_err().contains("centre"));
+        assert!(ThirdBody::new(EphemerisBody::Sun, EphemerisBody::Moon, f64::NAN, EphemerisSource::Analytic).is_err());
+        let s = [7.0e6, 0.0, 0.0, 0.0, 7.5e3, 0.0];
+        let model = ThirdBody::new(EphemerisBody::Earth, EphemerisBody::Moon, 2460000.5, EphemerisSource::Analytic).unwrap();
+        assert!(model.acceleration(0.0, &s, &SpacecraftParams::default()).iter().all(|a| a.is_finite()));
+    }
+
+    #[test]
+    fn third_body_epoch_follows_the_builder() {
+        let jd = 2460000.5;
+        // Explicit builder epoch: third bodies must agree with it
+        let mut builder = ForceModelBuilder::new();
+        builder.try_set_epoch(Epoch::from_jd(jd, TimeScale::Tdb)).unwrap();
+        let err = builder.try_add_third_body(EphemerisBody::Sun, EphemerisBody::Earth, jd + 1.0, EphemerisSource::Analytic);
+        assert!(err.unwrap_err().contains("disagrees"));
+        builder.try_add_third_body(EphemerisBody::Sun, EphemerisBody::Earth, jd, EphemerisSource::Analytic).unwrap();
+        assert_eq!(builder.length(), 1);
+
+        // No epoch set: the first third body fixes it and set_epoch can only confirm it
+        let mut builder = ForceModelBuilder::new();
+        builder.try_add_third_body(EphemerisBody::Moon, EphemerisBody::Earth, jd, EphemerisSource::Analytic).unwrap();
+        assert!((builder.epoch().jd(TimeScale::Tdb) - jd).abs() < 1e-9);
+        assert!(builder.try_add_third_body(EphemerisBody::Sun, EphemerisBody::Earth, jd - 0.5, EphemerisSource::Analytic).is_err());
+        assert!(builder.try_set_epoch(Epoch::from_jd(jd + 0.5, TimeScale::Tdb)).is_err());
+        builder.try_set_epoch(Epoch::from_jd(jd, TimeScale::Tdb)).unwrap();
+
+        // The model sees the body where the ephemeris puts it at the builder epoch
+        let s = [4.2e7, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let params = SpacecraftParams::default();
+        let direct = ThirdBody::new(EphemerisBody::Moon, EphemerisBody::Earth, jd, EphemerisSource::Analytic).unwrap();
+        assert_eq!(builder.model().acceleration(3600.0, &s, &params), direct.acceleration(3600.0, &s, &params));
+    }
+
+    #[test]
+    fn third_body_uses_spk_positions_inside_coverage() {
+        use crate::spk::tests::{earth_wrt_emb, moon_wrt_emb, FIXTURE};
+        let kernel = Arc::new(SpkKernel::from_bytes(FIXTURE).unwrap());
+        let et0 = 7.5e8 + 86400.0;
+        let model = ThirdBody {
+            body: EphemerisBody::Moon,
+            center: EphemerisBody::Earth,
+            epoch_jd: JD_J2000 + et0 / 86400.0,
+            source: EphemerisSource::Spk(kernel),
+        };
+        let params = SpacecraftParams::default();
+        let s = [4.2e7, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let t = 3600.0;
+        let (m, e) = (moon_wrt_emb(et0 + t), earth_wrt_emb(et0 + t));
+        let rb: [f64; 3] = core::array::from_fn(|i| (m[i] - e[i]) * 1e3);
+        let d: [f64; 3] = core::array::from_fn(|i| rb[i] - s[i]);
+        let (d3, rb3) = (
+            (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).powf(1.5),
+            (rb[0] * rb[0] + rb[1] * rb[1] + rb[2] * rb[2]).powf(1.5),
+        );
+        let a = model.acceleration(t, &s, &params);
+        for i in 0..3 {
+            let want = MU_MOON * (d[i] / d3 - rb[i] / rb3);
+            // Epoch round trip through JD costs ~1e-5 s of time resolution
+            assert!((a[i] - want).abs() < 1e-6 * want.abs().max(1e-7), "{}: {} vs {}", i, a[i], want);
+        }
+        // Outside coverage: analytic series
+        let late = ThirdBody { epoch_jd: JD_J2000 + 1.0e9 / 86400.0, ..model };
+        let analytic = ThirdBody { source: EphemerisSource::Analytic, ..late.clone() };
+        assert_eq!(late.acceleration(0.0, &s, &params), analytic.acceleration(0.0, &s, &params));
+    }
+}
@@ -0,0 +1,728 @@
+//! ID: WASM-EVENT-001
+//! Requirement: Detect and locate discrete events (altitude threshold, apsides,
+//!   ascending/descending node, sphere-of-influence boundary, eclipse entry/exit)
+//!   during propagation, optionally terminating at the first one.
+//! Purpose: Let mission logic stop at burn points or phase transitions instead of
+//!   scanning every sampled state of rk4_trajectory after the fact.
+//! Rationale: Each event is the zero of a scalar function g(t, x). A sign change
+//!   of g across an accepted step is bracketed and refined with Brent's method,
+//!   re-stepping from the start of the step with the same integrator so the
+//!   event state is consistent with the propagated trajectory.
+//! Inputs: state [m, m/s], propagation settings, an EventSet built from JS
+//! Outputs: event records (event index, time, state, crossing direction) and the
+//!   final time/state (the terminating event's if one fired)
+//! Failure Modes: two crossings of the same g inside one step cancel and are
+//!   missed; keep the step (or h_max) small relative to the event period.
+//! References: Brent (1973) "Algorithms for Minimization without Derivatives"
+//!   ch. 4; Montenbruck & Gill "Satellite Orbits" §4.1.5 (event location)
+
+use std::ops::ControlFlow;
+
+use wasm_bindgen::prelude::*;
+use js_sys::{Float64Array, Int32Array, Uint32Array};
+
+use crate::dop853::dop853_integrate;
+use crate::forces::{force_model_rhs, ForceModel, ForceModelBuilder, PointMass, SpacecraftParams};
+use crate::rk4::{rk4_step_model, State6};
+use crate::rk45::{
+    dopri5_integrate, dopri5_single_step, state6_from_js, two_body_rhs, AdaptiveOptions,
+    IntegrationError,
+};
+
+/// Scalar event functions. Each is positive on one side of the event surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventKind {
+    /// g = |r| − radius
+    Radius { radius: f64 },
+    /// g = r·v; rising at periapsis, falling at apoapsis
+    Apsis,
+    /// g = z; rising at the ascending node, falling at the descending node
+    Node,
+    /// g = |r| − R_soi around the central body
+    SphereOfInfluence { radius: f64 },
+    /// Cylindrical shadow of a body of given radius, sun along `sun_dir`;
+    /// falling at eclipse entry, rising at exit
+    Eclipse { body_radius: f64, sun_dir: [f64; 3] },
+}
+
+impl EventKind {
+    /// Eclipse by a body of radius `body_radius` with the Sun along `sun`
+    /// (any length). A zero or non-finite direction would make g NaN everywhere.
+    pub fn eclipse(body_radius: f64, sun: [f64; 3]) -> Result<EventKind, String> {
+        let n = (sun[0] * sun[0] + sun[1] * sun[1] + sun[2] * sun[2]).sqrt();
+        if !(n.is_finite() && n > 0.0) {
+            return Err(format!("Sun direction {:?} must be finite and non-zero", sun));
+        }
+        if !(body_radius.is_finite() && body_radius > 0.0) {
+            return Err(format!("Eclipsing body radius {} must be positive", body_radius));
+        }
+        Ok(EventKind::Eclipse { body_radius, sun_dir: [sun[0] / n, sun[1] / n, sun[2] / n] })
+    }
+
+    pub fn eval(&self, s: &[f64]) -> f64 {
+        let r = (s[0] * s[0] + s[1] * s[1] + s[2] * s[2]).sqrt();
+        match *self {
+            EventKind::Radius { radius } => r - radius,
+            EventKind::Apsis => s[0] * s[3] + s[1] * s[4] + s[2] * s[5],
+            EventKind::Node => s[2],
+            EventKind::SphereOfInfluence { radius } => r - radius,
+            EventKind::Eclipse { body_radius, sun_dir } => {
+                let along = s[0] * sun_dir[0] + s[1] * sun_dir[1] + s[2] * sun_dir[2];
+                if along >= 0.0 {
+                    // Sunward half: continuous with the perpendicular distance at along = 0
+                    r - body_radius
+                } else {
+                    (r * r - along * along).max(0.0).sqrt() - body_radius
+                }
+            }
+        }
+    }
+}
+
+/// An event function with its crossing-direction filter.
+#[derive(Debug, Clone, Copy)]
+pub struct EventSpec {
+    pub kind: EventKind,
+    /// +1 only rising crossings, −1 only falling, 0 both
+    pub direction: i32,
+    /// Stop propagation at the first crossing of this event
+    pub terminal: bool,
+}
+
+/// One located event crossing.
+#[derive(Debug, Clone)]
+pub struct EventRecord {
+    /// Index of the event in its EventSet
+    pub index: usize,
+    pub t: f64,
+    pub state: Vec<f64>,
+    /// +1 rising, −1 falling
+    pub direction: i32,
+}
+
+/// Brent's termination test needs a positive, finite tolerance.
+pub(crate) fn checked_time_tolerance(tol: f64) -> Result<f64, String> {
+    if tol.is_finite() && tol > 0.0 {
+        Ok(tol)
+    } else {
+        Err(format!("Event time tolerance must be positive and finite, got {}", tol))
+    }
+}
+
+/// Brent's method on a bracketed root of g in [a, b] (g(a)·g(b) ≤ 0).
+pub(crate) fn brent_root<G>(mut g: G, mut a: f64, mut b: f64, mut fa: f64, mut fb: f64, tol: f64) -> f64
+where
+    G: FnMut(f64) -> f64,
+{
+    if fa == 0.0 {
+        return a;
+    }
+    if fb == 0.0 {
+        return b;
+    }
+    let mut c = a;
+    let mut fc = fa;
+    let mut d = b - a;
+    let mut e = d;
+
+    for _ in 0..100 {
+        if (fb > 0.0) == (fc > 0.0) {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol1 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tol;
+        let xm = 0.5 * (c - b);
+        if xm.abs() <= tol1 || fb == 0.0 {
+            return b;
+        }
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            // Inverse quadratic interpolation (secant when only two points)
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * xm * s, 1.0 - s)
+            } else {
+                let qq = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * xm * qq * (qq - r) - (b - a) * (r - 1.0)),
+                    (qq - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+            let min1 = 3.0 * xm * q - (tol1 * q).abs();
+            let min2 = (e * q).abs();
+            if 2.0 * p < min1.min(min2) {
+                e = d;
+                d = p / q;
+            } else {
+                d = xm;
+                e = d;
+            }
+        } else {
+            d = xm;
+            e = d;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol1 { d } else { tol1.copysign(xm) };
+        fb = g(b);
+    }
+    b
+}
+
+/// Tracks event functions across consecutive steps of any integrator.
+pub struct EventDetector {
+    specs: Vec<EventSpec>,
+    time_tol: f64,
+    t_prev: f64,
+    y_prev: Vec<f64>,
+    g_prev: Vec<f64>,
+    pub records: Vec<EventRecord>,
+    /// Set once a terminal event has fired
+    pub terminated: bool,
+}
+
+impl EventDetector {
+    pub fn new(specs: &[EventSpec], time_tol: f64, t0: f64, y0: &[f64]) -> Self {
+        EventDetector {
+            specs: specs.to_vec(),
+            time_tol,
+            t_prev: t0,
+            y_prev: y0.to_vec(),
+            g_prev: specs.iter().map(|e| e.kind.eval(y0)).collect(),
+            records: Vec::new(),
+            terminated: false,
+        }
+    }
+
+    /// Check the step from the previous point to (t, y).
+    /// `advance(t_start, y_start, dt)` must return the state at t_start + dt
+    /// using the same integrator that produced the step.
+    /// Returns `Break` if a terminal event fired inside the step.
+    pub fn check_step<A>(&mut self, t: f64, y: &[f64], mut advance: A) -> ControlFlow<()>
+    where
+        A: FnMut(f64, &[f64], f64) -> Vec<f64>,
+    {
+        let h = t - self.t_prev;
+        let mut found: Vec<(usize, f64, i32)> = Vec::new();
+
+        for i in 0..self.specs.len() {
+            let spec = self.specs[i];
+            let g0 = self.g_prev[i];
+            let g1 = spec.kind.eval(y);
+            let direction = if g0 < 0.0 && g1 >= 0.0 {
+                1
+            } else if g0 > 0.0 && g1 <= 0.0 {
+                -1
+            } else {
+                0
+            };
+            self.g_prev[i] = g1;
+            if direction == 0 || (spec.direction != 0 && spec.direction != direction) {
+                continue;
+            }
+
+            let (t0, y0) = (self.t_prev, &self.y_prev);
+            let dt = brent_root(
+                |dt| spec.kind.eval(&advance(t0, y0, dt)),
+                0.0, h, g0, g1, self.time_tol,
+            );
+            found.push((i, dt, direction));
+        }
+
+        found.sort_by(|a, b| a.1.abs().partial_cmp(&b.1.abs()).unwrap_or(std::cmp::Ordering::Equal));
+        let mut flow = ControlFlow::Continue(());
+        for (i, dt, direction) in found {
+            let state = advance(self.t_prev, &self.y_prev, dt);
+            self.records.push(EventRecord { index: i, t: self.t_prev + dt, state, direction });
+            if self.specs[i].terminal {
+                self.terminated = true;
+                flow = ControlFlow::Break(());
+                break;
+            }
+        }
+
+        self.t_prev = t;
+        self.y_prev.clear();
+        self.y_prev.extend_from_slice(y);
+        flow
+    }
+
+    /// Time and state at which propagation ended: the terminal event if one
+    /// fired, otherwise the last checked point.
+    pub fn final_point(&self) -> (f64, Vec<f64>) {
+        match self.records.last() {
+            Some(rec) if self.terminated => (rec.t, rec.state.clone()),
+            _ => (self.t_prev, self.y_prev.clone()),
+        }
+    }
+}
+
+/// Event functions assembled from JavaScript.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct EventSet {
+    specs: Vec<EventSpec>,
+    time_tol: f64,
+}
+
+impl Default for EventSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl EventSet {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> EventSet {
+        EventSet { specs: Vec::new(), time_tol: 1e-3 }
+    }
+
+    /// Root-finding tolerance on event times [s] (default 1 ms); must be > 0
+    #[wasm_bindgen]
+    pub fn set_time_tolerance(&mut self, tol: f64) -> Result<(), JsValue> {
+        self.time_tol = checked_time_tolerance(tol).map_err(|e| JsValue::from_str(&e))?;
+        Ok(())
+    }
+
+    /// Number of events in the set
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.specs.len()
+    }
+
+    /// Altitude above a spherical body of radius `body_radius` [m].
+    /// direction: +1 ascending through the altitude, −1 descending, 0 both.
+    #[wasm_bindgen]
+    pub fn add_altitude(&mut self, altitude: f64, body_radius: f64, direction: i32, terminal: bool) -> usize {
+        self.push(EventKind::Radius { radius: body_radius + altitude }, direction, terminal)
+    }
+
+    #[wasm_bindgen]
+    pub fn add_periapsis(&mut self, terminal: bool) -> usize {
+        self.push(EventKind::Apsis, 1, terminal)
+    }
+
+    #[wasm_bindgen]
+    pub fn add_apoapsis(&mut self, terminal: bool) -> usize {
+        self.push(EventKind::Apsis, -1, terminal)
+    }
+
+    #[wasm_bindgen]
+    pub fn add_ascending_node(&mut self, terminal: bool) -> usize {
+        self.push(EventKind::Node, 1, terminal)
+    }
+
+    #[wasm_bindgen]
+    pub fn add_descending_node(&mut self, terminal: bool) -> usize {
+        self.push(EventKind::Node, -1, terminal)
+    }
+
+    /// Sphere-of-influence boundary of the central body; +1 exit, −1 entry, 0 both.
+    #[wasm_bindgen]
+    pub fn add_sphere_of_influence(&mut self, radius: f64, direction: i32, terminal: bool) -> usize {
+        self.push(EventKind::SphereOfInfluence { radius }, direction, terminal)
+    }
+
+    /// Cylindrical eclipse by the central body with the Sun along (sx, sy, sz).
+    /// Records −1 at entry and +1 at exit. Fails for a zero Sun vector.
+    #[wasm_bindgen]
+    pub fn add_eclipse(&mut self, body_radius: f64, sx: f64, sy: f64, sz: f64, terminal: bool) -> Result<usize, JsValue> {
+        let kind = EventKind::eclipse(body_radius, [sx, sy, sz]).map_err(|e| JsValue::from_str(&e))?;
+        Ok(self.push(kind, 0, terminal))
+    }
+}
+
+impl EventSet {
+    fn push(&mut self, kind: EventKind, direction: i32, terminal: bool) -> usize {
+        self.specs.push(EventSpec { kind, direction, terminal });
+        self.specs.len() - 1
+    }
+
+    pub fn specs(&self) -> &[EventSpec] {
+        &self.specs
+    }
+
+    pub fn time_tolerance(&self) -> f64 {
+        self.time_tol
+    }
+}
+
+/// Result of a propagation with event detection.
+#[wasm_bindgen]
+pub struct EventPropagation {
+    records: Vec<EventRecord>,
+    final_time: f64,
+    final_state: Vec<f64>,
+    terminated: bool,
+}
+
+impl EventPropagation {
+    pub(crate) fn from_detector(detector: EventDetector) -> Self {
+        let (final_time, final_state) = detector.final_point();
+        EventPropagation {
+            final_time,
+            final_state,
+            terminated: detector.terminated,
+            records: detector.records,
+        }
+    }
+
+    pub fn records(&self) -> &[EventRecord] {
+        &self.records
+    }
+}
+
+#[wasm_bindgen]
+impl EventPropagation {
+    /// Index of each event (as returned by the EventSet add_* call)
+    #[wasm_bindgen(getter)]
+    pub fn event_indices(&self) -> Uint32Array {
+        let v: Vec<u32> = self.records.iter().map(|r| r.index as u32).collect();
+        Uint32Array::from(v.as_slice())
+    }
+
+    /// Event times [s]
+    #[wasm_bindgen(getter)]
+    pub fn event_times(&self) -> Float64Array {
+        let v: Vec<f64> = self.records.iter().map(|r| r.t).collect();
+        Float64Array::from(v.as_slice())
+    }
+
+    /// Event states, flat 6 per event
+    #[wasm_bindgen(getter)]
+    pub fn event_states(&self) -> Float64Array {
+        let v: Vec<f64> = self.records.iter().flat_map(|r| r.state[..6].iter().copied()).collect();
+        Float64Array::from(v.as_slice())
+    }
+
+    /// Crossing direction per event: +1 rising, −1 falling
+    #[wasm_bindgen(getter)]
+    pub fn event_directions(&self) -> Int32Array {
+        let v: Vec<i32> = self.records.iter().map(|r| r.direction).collect();
+        Int32Array::from(v.as_slice())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn final_time(&self) -> f64 { self.final_time }
+
+    #[wasm_bindgen(getter)]
+    pub fn final_state(&self) -> Float64Array {
+        Float64Array::from(self.final_state.as_slice())
+    }
+
+    /// True if a terminal event stopped the propagation
+    #[wasm_bindgen(getter)]
+    pub fn terminated(&self) -> bool { self.terminated }
+}
+
+/// Fixed-step RK4 propagation under a force model with event detection.
+pub fn rk4_events<M: ForceModel + ?Sized>(
+    model: &M,
+    params: &SpacecraftParams,
+    y0: &State6,
+    dt: f64,
+    steps: u32,
+    events: &EventSet,
+) -> EventPropagation {
+    let mut detector = EventDetector::new(events.specs(), events.time_tolerance(), 0.0, y0);
+    let mut state = *y0;
+    let advance = |t: f64, y: &[f64], h: f64| -> Vec<f64> {
+        let s: State6 = [y[0], y[1], y[2], y[3], y[4], y[5]];
+        rk4_step_model(model, params, t, &s, h).to_vec()
+    };
+    for k in 0..steps {
+        state = rk4_step_model(model, params, k as f64 * dt, &state, dt);
+        if detector.check_step((k + 1) as f64 * dt, &state, advance).is_break() {
+            break;
+        }
+    }
+    EventPropagation::from_detector(detector)
+}
+
+/// Dormand–Prince 5(4) propagation of dy/dt = rhs(t, y) with event detection.
+pub fn rk45_events<F>(
+    rhs: &F,
+    y0: &[f64],
+    tf: f64,
+    opts: &AdaptiveOptions,
+    events: &EventSet,
+) -> Result<EventPropagation, IntegrationError>
+where
+    F: Fn(f64, &[f64], &mut [f64]),
+{
+    let mut detector = EventDetector::new(events.specs(), events.time_tolerance(), 0.0, y0);
+    let mut restep = |t: f64, y: &[f64], dy: &mut [f64]| rhs(t, y, dy);
+
+    dopri5_integrate(|t, y, dy| rhs(t, y, dy), 0.0, y0, tf, opts, |t, y| {
+        if t == 0.0 {
+            return ControlFlow::Continue(());
+        }
+        detector.check_step(t, y, |t0, y0, dt| dopri5_single_step(&mut restep, t0, y0, dt))
+    })?;
+
+    Ok(EventPropagation::from_detector(detector))
+}
+
+/// DOP853 propagation of dy/dt = rhs(t, y) with event detection on the dense output.
+pub fn dop853_events<F>(
+    rhs: &F,
+    y0: &[f64],
+    tf: f64,
+    opts: &AdaptiveOptions,
+    events: &EventSet,
+) -> Result<EventPropagation, IntegrationError>
+where
+    F: Fn(f64, &[f64], &mut [f64]),
+{
+    let mut detector = EventDetector::new(events.specs(), events.time_tolerance(), 0.0, y0);
+
+    dop853_integrate(|t, y, dy| rhs(t, y, dy), 0.0, y0, tf, opts, true, |t, y, interp| {
+        match interp {
+            Some(p) => detector.check_step(t, y, |t0, _, dt| {
+                let mut out = vec![0.0; y.len()];
+                p.eval(t0 + dt, &mut out);
+                out
+            }),
+            None => ControlFlow::Continue(()),
+        }
+    })?;
+
+    Ok(EventPropagation::from_detector(detector))
+}
+
+fn state6_array(state_js: &Float64Array) -> Result<State6, JsValue> {
+    let sv = state6_from_js(state_js)?;
+    Ok([sv[0], sv[1], sv[2], sv[3], sv[4], sv[5]])
+}
+
+/// RK4 propagation that stops at the first terminal event.
+#[wasm_bindgen]
+pub fn rk4_propagate_events(
+    state_js: &Float64Array,
+    dt: f64,
+    steps: u32,
+    mu: f64,
+    events: &EventSet,
+) -> Result<EventPropagation, JsValue> {
+    let y0 = state6_array(state_js)?;
+    Ok(rk4_events(&PointMass { mu }, &SpacecraftParams::default(), &y0, dt, steps, events))
+}
+
+/// Dormand–Prince 5(4) propagation to `tf` with event detection.
+#[wasm_bindgen]
+pub fn rk45_propagate_events(
+    state_js: &Float64Array,
+    tf: f64,
+    mu: f64,
+    atol: f64,
+    rtol: f64,
+    events: &EventSet,
+) -> Result<EventPropagation, JsValue> {
+    let y0 = state6_from_js(state_js)?;
+    Ok(rk45_events(&two_body_rhs(mu), &y0, tf, &AdaptiveOptions::new(atol, rtol), events)?)
+}
+
+/// DOP853 propagation to `tf` with event detection on the dense output.
+#[wasm_bindgen]
+pub fn dop853_propagate_events(
+    state_js: &Float64Array,
+    tf: f64,
+    mu: f64,
+    atol: f64,
+    rtol: f64,
+    events: &EventSet,
+) -> Result<EventPropagation, JsValue> {
+    let y0 = state6_from_js(state_js)?;
+    Ok(dop853_events(&two_body_rhs(mu), &y0, tf, &AdaptiveOptions::new(atol, rtol), events)?)
+}
+
+/// rk4_propagate_events under a ForceModelBuilder's dynamics.
+#[wasm_bindgen]
+pub fn rk4_propagate_events_model(
+    state_js: &Float64Array,
+    dt: f64,
+    steps: u32,
+    model: &ForceModelBuilder,
+    events: &EventSet,
+) -> Result<EventPropagation, JsValue> {
+    let y0 = state6_array(state_js)?;
+    Ok(rk4_events(model.model(), model.params(), &y0, dt, steps, events))
+}
+
+/// rk45_propagate_events under a ForceModelBuilder's dynamics.
+#[wasm_bindgen]
+pub fn rk45_propagate_events_model(
+    state_js: &Float64Array,
+    tf: f64,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+    events: &EventSet,
+) -> Result<EventPropagation, JsValue> {
+    let y0 = state6_from_js(state_js)?;
+    let rhs = force_model_rhs(model.model(), model.params());
+    Ok(rk45_events(&rhs, &y0, tf, &AdaptiveOptions::new(atol, rtol), events)?)
+}
+
+/// dop853_propagate_events under a ForceModelBuilder's dynamics.
+#[wasm_bindgen]
+pub fn dop853_propagate_events_model(
+    state_js: &Float64Array,
+    tf: f64,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+    events: &EventSet,
+) -> Result<EventPropagation, JsValue> {
+    let y0 = state6_from_js(state_js)?;
+    let rhs = force_model_rhs(model.model(), model.params());
+    Ok(dop853_events(&rhs, &y0, tf, &AdaptiveOptions::new(atol, rtol), events)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{MU_EARTH, R_EARTH};
+
+    fn two_body_events(y0: &State6, dt: f64, steps: u32, events: &EventSet) -> EventPropagation {
+        rk4_events(&PointMass { mu: MU_EARTH }, &SpacecraftParams::default(), y0, dt, steps, events)
+    }
+
+    fn inclined_ellipse() -> (State6, f64) {
+        let rp = 7.0e6;
+        let a = 1.2e7;
+        let vp = (MU_EARTH * (2.0 / rp - 1.0 / a)).sqrt();
+        let inc = 0.5f64;
+        // Periapsis on +x at t = 0, orbit plane tilted about x
+        let y0 = [rp, 0.0, 0.0, 0.0, vp * inc.cos(), vp * inc.sin()];
+        let period = 2.0 * std::f64::consts::PI * (a * a * a / MU_EARTH).sqrt();
+        (y0, period)
+    }
+
+    #[test]
+    fn apoapsis_found_at_half_period() {
+        let (y0, period) = inclined_ellipse();
+        let mut events = EventSet::new();
+        events.add_apoapsis(true);
+        let dt = 30.0;
+        let res = two_body_events(&y0, dt, (2.0 * period / dt) as u32, &events);
+        assert!(res.terminated);
+        assert_eq!(res.records.len(), 1);
+        assert!((res.final_time - 0.5 * period).abs() < 0.5, "{} vs {}", res.final_time, 0.5 * period);
+    }
+
+    #[test]
+    fn nodes_and_altitude_are_located_with_direction() {
+        let (y0, period) = inclined_ellipse();
+        let mut events = EventSet::new();
+        let asc = events.add_ascending_node(false);
+        let desc = events.add_descending_node(false);
+        let alt = events.add_altitude(2000e3, R_EARTH, 0, false);
+        let res = two_body_events(&y0, 20.0, (period / 20.0) as u32 + 1, &events);
+
+        let descending: Vec<&EventRecord> = res.records.iter().filter(|r| r.index == desc).collect();
+        assert_eq!(descending.len(), 1);
+        assert!(descending[0].state[2].abs() < 1.0);
+        assert!((descending[0].t - 0.5 * period).abs() < 1.0);
+        // Starts at the ascending node (z = 0 is not a crossing), so the only
+        // ascending crossing is the return after one orbit
+        let ascending: Vec<&EventRecord> = res.records.iter().filter(|r| r.index == asc).collect();
+        assert_eq!(ascending.len(), 1);
+        assert!(ascending[0].state[2].abs() < 1.0, "{}", ascending[0].state[2]);
+        assert!((ascending[0].t - period).abs() < 1.0, "{} vs {}", ascending[0].t, period);
+
+        let alt_hits: Vec<&EventRecord> = res.records.iter().filter(|r| r.index == alt).collect();
+        assert_eq!(alt_hits.len(), 2);
+        assert_eq!(alt_hits[0].direction, 1);
+        assert_eq!(alt_hits[1].direction, -1);
+        for hit in alt_hits {
+            let r = (hit.state[0].powi(2) + hit.state[1].powi(2) + hit.state[2].powi(2)).sqrt();
+            assert!((r - R_EARTH - 2000e3).abs() < 10.0);
+        }
+    }
+
+    #[test]
+    fn sphere_of_influence_exit_on_hyperbolic_escape() {
+        // Escape from a 7000 km periapsis with v∞ = 3 km/s; Earth SOI ≈ 9.24e8 m
+        let (rp, v_inf, r_soi) = (7.0e6, 3.0e3, 9.24e8);
+        let y0 = [rp, 0.0, 0.0, 0.0, (v_inf * v_inf + 2.0 * MU_EARTH / rp).sqrt(), 0.0];
+        let mut events = EventSet::new();
+        events.add_sphere_of_influence(r_soi, 1, true);
+        let res = dop853_events(&two_body_rhs(MU_EARTH), &y0, 1.0e7, &AdaptiveOptions::new(1e-6, 1e-12), &events).unwrap();
+        assert!(res.terminated);
+        assert_eq!(res.records.len(), 1);
+        assert_eq!(res.records[0].direction, 1);
+
+        // Hyperbolic Kepler equation: r = a(e cosh F − 1), t = sqrt(a³/μ)(e sinh F − F)
+        let a = MU_EARTH / (v_inf * v_inf);
+        let e = 1.0 + rp / a;
+        let f = ((r_soi / a + 1.0) / e).acosh();
+        let t_exit = (a * a * a / MU_EARTH).sqrt() * (e * f.sinh() - f);
+        assert!((res.final_time - t_exit).abs() < 0.1, "{} vs {}", res.final_time, t_exit);
+        let r = (res.final_state[0].powi(2) + res.final_state[1].powi(2) + res.final_state[2].powi(2)).sqrt();
+        assert!((r - r_soi).abs() < 10.0, "{}", r - r_soi);
+    }
+
+    #[test]
+    fn eclipse_entry_and_exit_on_circular_leo() {
+        let r = 7.0e6;
+        let n = (MU_EARTH / (r * r * r)).sqrt();
+        let period = 2.0 * std::f64::consts::PI / n;
+        let y0 = [r, 0.0, 0.0, 0.0, r * n, 0.0];
+        let mut events = EventSet::new();
+        // Sun along +x (unnormalised on purpose): shadow cylinder |y| < R_E on the −x side
+        events.add_eclipse(R_EARTH, 2.0, 0.0, 0.0, false).unwrap();
+        let res = two_body_events(&y0, 10.0, (period / 10.0) as u32 + 1, &events);
+
+        assert_eq!(res.records.len(), 2);
+        let (entry, exit) = (&res.records[0], &res.records[1]);
+        assert_eq!((entry.direction, exit.direction), (-1, 1));
+        let half_width = (R_EARTH / r).asin();
+        let t_entry = (std::f64::consts::PI - half_width) / n;
+        let t_exit = (std::f64::consts::PI + half_width) / n;
+        assert!((entry.t - t_entry).abs() < 0.01, "{} vs {}", entry.t, t_entry);
+        assert!((exit.t - t_exit).abs() < 0.01, "{} vs {}", exit.t, t_exit);
+        for rec in [entry, exit] {
+            assert!(rec.state[0] < 0.0);
+            assert!((rec.state[1].abs() - R_EARTH).abs() < 1.0, "{}", rec.state[1]);
+        }
+    }
+
+    #[test]
+    fn degenerate_event_settings_are_rejected() {
+        for sun in [[0.0; 3], [f64::NAN, 0.0, 1.0], [f64::INFINITY, 0.0, 0.0]] {
+            assert!(EventKind::eclipse(R_EARTH, sun).is_err(), "{:?}", sun);
+        }
+        assert!(EventKind::eclipse(-1.0, [1.0, 0.0, 0.0]).is_err());
+        match EventKind::eclipse(R_EARTH, [0.0, 3.0, 4.0]).unwrap() {
+            EventKind::Eclipse { sun_dir, .. } => assert_eq!(sun_dir, [0.0, 0.6, 0.8]),
+            kind => panic!("{:?}", kind),
+        }
+        for tol in [0.0, -1e-3, f64::NAN, f64::INFINITY] {
+            assert!(checked_time_tolerance(tol).is_err(), "{}", tol);
+        }
+        assert_eq!(checked_time_tolerance(1e-6), Ok(1e-6));
+    }
+
+    #[test]
+    fn brent_converges_on_polynomial() {
+        let g = |x: f64| x * x * x - 2.0 * x - 5.0;
+        let root = brent_root(g, 2.0, 3.0, g(2.0), g(3.0), 1e-12);
+        assert!((root - 2.0945514815423265).abs() < 1e-10);
+    }
+}
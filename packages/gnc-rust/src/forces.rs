@@ -0,0 +1,321 @@
+//! ID: WASM-FORCE-001
+//! Requirement: Provide a pluggable force-model interface so propagators are not
+//!   tied to hardcoded point-mass gravity, plus a composite that sums models.
+//! Purpose: Let the web app assemble dynamics (point mass, perturbations, thrust)
+//!   at run time through ForceModelBuilder and hand them to any propagator.
+//! Rationale: Every perturbation is an additive acceleration a(t, x, p), so a
+//!   trait object per contribution and a summing composite cover all cases; the
+//!   optional position gradient lets the same models drive STM propagation.
+//! Inputs: time [s], inertial state [m, m/s], spacecraft parameters
+//! Outputs: acceleration [m/s²]; optional ∂a/∂r [1/s²]
+//! References: Montenbruck & Gill "Satellite Orbits" ch. 3 (force models)
+
+use wasm_bindgen::prelude::*;
+
+use crate::rk4::{two_body_deriv, State6};
+use crate::stm::{two_body_gradient, Mat3};
+use crate::time::{Epoch, TimeScale};
+
+/// Physical properties of the spacecraft used by non-gravitational models.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpacecraftParams {
+    /// Mass [kg]
+    pub mass: f64,
+    /// Drag reference area [m²]
+    pub drag_area: f64,
+    /// Drag coefficient C_d [-]
+    pub drag_coefficient: f64,
+    /// Solar radiation pressure area [m²]
+    pub srp_area: f64,
+    /// Radiation pressure coefficient C_r [-]
+    pub reflectivity: f64,
+}
+
+impl Default for SpacecraftParams {
+    fn default() -> Self {
+        SpacecraftParams {
+            mass: 1000.0,
+            drag_area: 10.0,
+            drag_coefficient: 2.2,
+            srp_area: 10.0,
+            reflectivity: 1.3,
+        }
+    }
+}
+
+/// An additive acceleration contribution.
+pub trait ForceModel: Send + Sync {
+    /// Acceleration [m/s²] at time t [s] for the inertial state.
+    fn acceleration(&self, t: f64, state: &State6, params: &SpacecraftParams) -> [f64; 3];
+
+    /// Position gradient ∂a/∂r, used by the variational equations.
+    /// Models that omit it are treated as having zero gradient.
+    fn position_gradient(&self, _t: f64, _state: &State6, _params: &SpacecraftParams) -> Option<Mat3> {
+        None
+    }
+}
+
+/// Point-mass gravity of the central body.
+#[derive(Debug, Clone, Copy)]
+pub struct PointMass {
+    /// Gravitational parameter [m³/s²]
+    pub mu: f64,
+}
+
+impl ForceModel for PointMass {
+    #[inline]
+    fn acceleration(&self, _t: f64, state: &State6, _params: &SpacecraftParams) -> [f64; 3] {
+        let d = two_body_deriv(state, self.mu);
+        [d[3], d[4], d[5]]
+    }
+
+    fn position_gradient(&self, _t: f64, state: &State6, _params: &SpacecraftParams) -> Option<Mat3> {
+        Some(two_body_gradient(&state[..3], self.mu))
+    }
+}
+
+/// Constant-magnitude thrust along the velocity direction (negative = retrograde).
+/// Mass is held at `SpacecraftParams::mass`; propellant depletion is not modelled.
+#[derive(Debug, Clone, Copy)]
+pub struct TangentialThrust {
+    /// Thrust [N]
+    pub thrust: f64,
+}
+
+impl ForceModel for TangentialThrust {
+    fn acceleration(&self, _t: f64, state: &State6, params: &SpacecraftParams) -> [f64; 3] {
+        let v = (state[3] * state[3] + state[4] * state[4] + state[5] * state[5]).sqrt();
+        if v == 0.0 {
+            return [0.0; 3];
+        }
+        let k = self.thrust / (params.mass * v);
+        [k * state[3], k * state[4], k * state[5]]
+    }
+}
+
+/// Constant thrust force fixed in the inertial frame.
+#[derive(Debug, Clone, Copy)]
+pub struct InertialThrust {
+    /// Force [N]
+    pub force: [f64; 3],
+}
+
+impl ForceModel for InertialThrust {
+    fn acceleration(&self, _t: f64, _state: &State6, params: &SpacecraftParams) -> [f64; 3] {
+        let m = params.mass;
+        [self.force[0] / m, self.force[1] / m, self.force[2] / m]
+    }
+}
+
+/// Sum of any number of force models.
+#[derive(Default)]
+pub struct CompositeForceModel {
+    models: Vec<Box<dyn ForceModel>>,
+}
+
+impl CompositeForceModel {
+    pub fn new() -> Self {
+        CompositeForceModel { models: Vec::new() }
+    }
+
+    pub fn push<M: ForceModel + 'static>(&mut self, model: M) {
+        self.models.push(Box::new(model));
+    }
+
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+}
+
+impl ForceModel for CompositeForceModel {
+    fn acceleration(&self, t: f64, state: &State6, params: &SpacecraftParams) -> [f64; 3] {
+        let mut a = [0.0; 3];
+        for m in &self.models {
+            let ai = m.acceleration(t, state, params);
+            a[0] += ai[0];
+            a[1] += ai[1];
+            a[2] += ai[2];
+        }
+        a
+    }
+
+    fn position_gradient(&self, t: f64, state: &State6, params: &SpacecraftParams) -> Option<Mat3> {
+        let mut g = [[0.0; 3]; 3];
+        let mut any = false;
+        for m in &self.models {
+            if let Some(gi) = m.position_gradient(t, state, params) {
+                any = true;
+                for i in 0..3 {
+                    for j in 0..3 {
+                        g[i][j] += gi[i][j];
+                    }
+                }
+            }
+        }
+        any.then_some(g)
+    }
+}
+
+/// State derivative [v, a] under a force model.
+#[inline]
+pub fn state_derivative<M: ForceModel + ?Sized>(model: &M, t: f64, s: &State6, params: &SpacecraftParams) -> State6 {
+    let a = model.acceleration(t, s, params);
+    [s[3], s[4], s[5], a[0], a[1], a[2]]
+}
+
+/// Force model right-hand side in the slice form used by the adaptive integrators.
+pub(crate) fn force_model_rhs<'a, M: ForceModel + ?Sized>(
+    model: &'a M,
+    params: &'a SpacecraftParams,
+) -> impl Fn(f64, &[f64], &mut [f64]) + 'a {
+    move |t, y, dy| {
+        let s: State6 = [y[0], y[1], y[2], y[3], y[4], y[5]];
+        dy[..6].copy_from_slice(&state_derivative(model, t, &s, params));
+    }
+}
+
+/// Run-time assembled dynamics for the `*_model` propagators.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct ForceModelBuilder {
+    model: CompositeForceModel,
+    params: SpacecraftParams,
+    /// Epoch of model time t = 0 (J2000 by default)
+    epoch: Epoch,
+    /// Set by set_epoch or by the first epoch-dependent model (third-body
+    /// gravity); later models must agree with it
+    epoch_set: bool,
+    /// An epoch-dependent model captured the epoch, so it can no longer change
+    epoch_in_use: bool,
+}
+
+/// Largest disagreement [s] tolerated between the builder epoch and an
+/// epoch passed as a TDB Julian date (whose f64 resolution is ~40 µs)
+const EPOCH_MATCH_TOLERANCE: f64 = 1e-3;
+
+#[wasm_bindgen]
+impl ForceModelBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ForceModelBuilder {
+        ForceModelBuilder::default()
+    }
+
+    /// Set spacecraft mass [kg], drag area [m²], C_d, SRP area [m²] and C_r
+    #[wasm_bindgen]
+    pub fn set_spacecraft(&mut self, mass: f64, drag_area: f64, drag_coefficient: f64, srp_area: f64, reflectivity: f64) {
+        self.params = SpacecraftParams { mass, drag_area, drag_coefficient, srp_area, reflectivity };
+    }
+
+    /// Epoch at which model time t = 0; used by the `*_epoch` propagators.
+    /// Fails if third-body models were already added for a different epoch.
+    #[wasm_bindgen]
+    pub fn set_epoch(&mut self, epoch: &Epoch) -> Result<(), JsValue> {
+        self.try_set_epoch(*epoch).map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    /// Number of force models added
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.model.len()
+    }
+
+    /// Central-body point-mass gravity, mu [m³/s²]
+    #[wasm_bindgen]
+    pub fn add_point_mass(&mut self, mu: f64) {
+        self.model.push(PointMass { mu });
+    }
+
+    /// Constant thrust [N] along the velocity vector
+    #[wasm_bindgen]
+    pub fn add_tangential_thrust(&mut self, thrust: f64) {
+        self.model.push(TangentialThrust { thrust });
+    }
+
+    /// Constant inertial thrust force [N]
+    #[wasm_bindgen]
+    pub fn add_inertial_thrust(&mut self, fx: f64, fy: f64, fz: f64) {
+        self.model.push(InertialThrust { force: [fx, fy, fz] });
+    }
+}
+
+impl ForceModelBuilder {
+    pub fn model(&self) -> &CompositeForceModel {
+        &self.model
+    }
+
+    pub fn params(&self) -> &SpacecraftParams {
+        &self.params
+    }
+
+    pub fn push<M: ForceModel + 'static>(&mut self, model: M) {
+        self.model.push(model);
+    }
+
+    /// Model time [s] of an absolute epoch.
+    pub fn model_time(&self, epoch: &Epoch) -> f64 {
+        epoch.seconds_since(&self.epoch)
+    }
+
+    /// set_epoch without the JS error conversion.
+    pub fn try_set_epoch(&mut self, epoch: Epoch) -> Result<(), String> {
+        if self.epoch_in_use && epoch.seconds_since(&self.epoch).abs() > EPOCH_MATCH_TOLERANCE {
+            return Err(format!(
+                "Epoch JD {} (TDB) differs from JD {} already used by the force models",
+                epoch.jd(TimeScale::Tdb),
+                self.epoch.jd(TimeScale::Tdb)
+            ));
+        }
+        self.epoch = epoch;
+        self.epoch_set = true;
+        Ok(())
+    }
+
+    /// Reconcile an epoch-dependent model's t = 0 Julian date (TDB) with the
+    /// builder epoch: adopt it if no epoch is set yet, otherwise require it
+    /// to agree. Returns the builder epoch as a TDB Julian date.
+    pub fn fix_epoch_jd(&mut self, jd_tdb: f64) -> Result<f64, String> {
+        let epoch = Epoch::from_jd(jd_tdb, TimeScale::Tdb);
+        if self.epoch_set && epoch.seconds_since(&self.epoch).abs() > EPOCH_MATCH_TOLERANCE {
+            return Err(format!(
+                "Epoch JD {} (TDB) disagrees with the builder epoch JD {}",
+                jd_tdb,
+                self.epoch.jd(TimeScale::Tdb)
+            ));
+        }
+        if !self.epoch_set {
+            self.epoch = epoch;
+            self.epoch_set = true;
+        }
+        self.epoch_in_use = true;
+        Ok(self.epoch.jd(TimeScale::Tdb))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MU: f64 = 3.986004418e14;
+
+    #[test]
+    fn composite_sums_contributions() {
+        let mut model = CompositeForceModel::new();
+        model.push(PointMass { mu: MU });
+        model.push(InertialThrust { force: [0.0, 0.0, 100.0] });
+        let params = SpacecraftParams { mass: 500.0, ..Default::default() };
+        let s = [7.0e6, 0.0, 0.0, 0.0, 7500.0, 0.0];
+        let a = model.acceleration(0.0, &s, &params);
+        assert!((a[0] + MU / 4.9e13).abs() < 1e-12);
+        assert!((a[2] - 0.2).abs() < 1e-15);
+        assert!(model.position_gradient(0.0, &s, &params).is_some());
+    }
+}
@@ -0,0 +1,432 @@
+//! ID: WASM-FRAME-001
+//! Requirement: Convert position/velocity states between GCRF, J2000 (FK5),
+//!   TEME, ITRF and the Moon and Mars body-fixed frames, singly or in batches.
+//! Purpose: Propagators work in an inertial frame, but ground tracks, sensors and
+//!   SGP4 element sets need Earth-fixed, TEME or planet-fixed coordinates.
+//! Rationale: Every frame F is described by a rotation R_F with r_F = R_F r_GCRF
+//!   and the angular velocity ω_F of its axes (in F), so any pair converts through
+//!   GCRF with v_F = R_F v_GCRF − ω_F × r_F. Earth uses the IAU-76/FK5 reduction:
+//!   frame bias, IAU-76 precession, IAU-1980 nutation, GAST (IAU-82 GMST plus the
+//!   1994 equation of the equinoxes) and polar motion; TEME is the pseudo-Earth-
+//!   fixed frame rotated back by GMST. Moon and Mars use the IAU/WGCCRE 2009
+//!   pole and prime-meridian models.
+//! Inputs: states [m, m/s] centred on the frame's body, Epoch, Earth orientation
+//!   parameters (UT1 − UTC, pole coordinates, LOD)
+//! Outputs: states [m, m/s] in the requested frame
+//! Limits: nutation keeps the 30 largest IAU-1980 terms (omitted terms < 1.5 mas
+//!   each, ≈ 0.1 m at GEO); precession and nutation rates are neglected in
+//!   velocity transformations, as in the standard FK5 reduction.
+//! References: Vallado §3.7 (FK5 reduction, Example 3-15); IERS Conventions 2010
+//!   ch. 5; Archinal et al. 2011 (WGCCRE 2009); Meeus Table 22.A
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::constants::DAYS_PER_CENTURY;
+use crate::rk4::State6;
+use crate::stm::Mat3;
+use crate::time::{Epoch, TimeScale};
+
+const ARCSEC: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+/// Reference frames supported by the transformations.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frame {
+    /// Geocentric Celestial Reference Frame (ICRF axes)
+    Gcrf = 0,
+    /// Mean equator and equinox of J2000.0 (FK5)
+    J2000 = 1,
+    /// True equator, mean equinox (SGP4 output frame)
+    Teme = 2,
+    /// International Terrestrial Reference Frame (Earth-fixed)
+    Itrf = 3,
+    /// Moon mean-Earth/polar-axis body-fixed frame (IAU model)
+    MoonFixed = 4,
+    /// Mars body-fixed frame (IAU model)
+    MarsFixed = 5,
+}
+
+/// Earth orientation parameters from IERS Bulletin A/B.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EarthOrientation {
+    /// UT1 − UTC [s]
+    pub ut1_minus_utc: f64,
+    /// Pole x coordinate [rad]
+    pub x_pole: f64,
+    /// Pole y coordinate [rad]
+    pub y_pole: f64,
+    /// Excess length of day [s]
+    pub lod: f64,
+}
+
+#[wasm_bindgen]
+impl EarthOrientation {
+    /// UT1 − UTC [s], pole coordinates [arcsec], LOD [s]
+    #[wasm_bindgen(constructor)]
+    pub fn new(ut1_minus_utc: f64, x_pole_arcsec: f64, y_pole_arcsec: f64, lod: f64) -> EarthOrientation {
+        EarthOrientation { ut1_minus_utc, x_pole: x_pole_arcsec * ARCSEC, y_pole: y_pole_arcsec * ARCSEC, lod }
+    }
+}
+
+pub(crate) fn rot1(a: f64) -> Mat3 {
+    let (s, c) = a.sin_cos();
+    [[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]]
+}
+
+pub(crate) fn rot2(a: f64) -> Mat3 {
+    let (s, c) = a.sin_cos();
+    [[c, 0.0, -s], [0.0, 1.0, 0.0], [s, 0.0, c]]
+}
+
+pub(crate) fn rot3(a: f64) -> Mat3 {
+    let (s, c) = a.sin_cos();
+    [[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]]
+}
+
+pub(crate) fn mat_mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    core::array::from_fn(|i| core::array::from_fn(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+pub(crate) fn transpose(a: &Mat3) -> Mat3 {
+    core::array::from_fn(|i| core::array::from_fn(|j| a[j][i]))
+}
+
+pub(crate) fn mat_vec(a: &Mat3, v: &[f64]) -> [f64; 3] {
+    core::array::from_fn(|i| a[i][0] * v[0] + a[i][1] * v[1] + a[i][2] * v[2])
+}
+
+pub(crate) fn cross(a: &[f64], b: &[f64]) -> [f64; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+/// IAU-1980 nutation terms: multipliers of (l, l′, F, D, Ω) and
+/// Δψ = (a + b·T) sin, Δε = (c + d·T) cos, in units of 0.0001″.
+#[rustfmt::skip]
+const NUTATION_1980: [([i8; 5], f64, f64, f64, f64); 30] = [
+    ([ 0, 0, 0,  0, 1], -171996.0, -174.2, 92025.0,  8.9),
+    ([ 0, 0, 2, -2, 2],  -13187.0,   -1.6,  5736.0, -3.1),
+    ([ 0, 0, 2,  0, 2],   -2274.0,   -0.2,   977.0, -0.5),
+    ([ 0, 0, 0,  0, 2],    2062.0,    0.2,  -895.0,  0.5),
+    ([ 0, 1, 0,  0, 0],    1426.0,   -3.4,    54.0, -0.1),
+    ([ 1, 0, 0,  0, 0],     712.0,    0.1,    -7.0,  0.0),
+    ([ 0, 1, 2, -2, 2],    -517.0,    1.2,   224.0, -0.6),
+    ([ 0, 0, 2,  0, 1],    -386.0,   -0.4,   200.0,  0.0),
+    ([ 1, 0, 2,  0, 2],    -301.0,    0.0,   129.0, -0.1),
+    ([ 0,-1, 2, -2, 2],     217.0,   -0.5,   -95.0,  0.3),
+    ([ 1, 0, 0, -2, 0],    -158.0,    0.0,    -1.0,  0.0),
+    ([ 0, 0, 2, -2, 1],     129.0,    0.1,   -70.0,  0.0),
+    ([-1, 0, 2,  0, 2],     123.0,    0.0,   -53.0,  0.0),
+    ([ 1, 0, 0,  0, 1],      63.0,    0.1,   -33.0,  0.0),
+    ([ 0, 0, 0,  2, 0],      63.0,    0.0,    -2.0,  0.0),
+    ([-1, 0, 2,  2, 2],     -59.0,    0.0,    26.0,  0.0),
+    ([-1, 0, 0,  0, 1],     -58.0,   -0.1,    32.0,  0.0),
+    ([ 1, 0, 2,  0, 1],     -51.0,    0.0,    27.0,  0.0),
+    ([ 2, 0, 0, -2, 0],      48.0,    0.0,     1.0,  0.0),
+    ([-2, 0, 2,  0, 1],      46.0,    0.0,   -24.0,  0.0),
+    ([ 0, 0, 2,  2, 2],     -38.0,    0.0,    16.0,  0.0),
+    ([ 2, 0, 2,  0, 2],     -31.0,    0.0,    13.0,  0.0),
+    ([ 2, 0, 0,  0, 0],      29.0,    0.0,    -1.0,  0.0),
+    ([ 1, 0, 2, -2, 2],      29.0,    0.0,   -12.0,  0.0),
+    ([ 0, 0, 2,  0, 0],      26.0,    0.0,    -1.0,  0.0),
+    ([ 0, 0, 2, -2, 0],     -22.0,    0.0,     0.0,  0.0),
+    ([-1, 0, 2,  0, 1],      21.0,    0.0,   -10.0,  0.0),
+    ([ 0, 2, 0,  0, 0],      17.0,   -0.1,     0.0,  0.0),
+    ([ 0, 2, 2, -2, 2],     -16.0,    0.1,     7.0,  0.0),
+    ([-1, 0, 0,  2, 1],      16.0,    0.0,    -8.0,  0.0),
+];
+
+/// Nutation in longitude and obliquity, mean obliquity and Ω [rad] at TT centuries T.
+fn nutation(t: f64) -> (f64, f64, f64, f64) {
+    let poly = |c: [f64; 4]| (c[0] + t * (c[1] + t * (c[2] + t * c[3]))).to_radians();
+    let args = [
+        poly([134.96298, 477198.867398, 0.0086972, 1.0 / 56250.0]),
+        poly([357.52772, 35999.050340, -0.0001603, -1.0 / 300000.0]),
+        poly([93.27191, 483202.017538, -0.0036825, 1.0 / 327270.0]),
+        poly([297.85036, 445267.111480, -0.0019142, 1.0 / 189474.0]),
+        poly([125.04452, -1934.136261, 0.0020708, 1.0 / 450000.0]),
+    ];
+    let (mut dpsi, mut deps) = (0.0, 0.0);
+    for (k, a, b, c, d) in NUTATION_1980.iter() {
+        let arg: f64 = k.iter().zip(&args).map(|(&m, x)| m as f64 * x).sum();
+        dpsi += (a + b * t) * arg.sin();
+        deps += (c + d * t) * arg.cos();
+    }
+    let eps0 = (84381.448 - 46.8150 * t - 0.00059 * t * t + 0.001813 * t * t * t) * ARCSEC;
+    (dpsi * 1e-4 * ARCSEC, deps * 1e-4 * ARCSEC, eps0, args[4])
+}
+
+/// Frame bias: r_J2000 = B r_GCRF (IERS 2010 eq. 5.21 angles).
+fn frame_bias() -> Mat3 {
+    let (da0, xi0, eta0) = (-0.01460 * ARCSEC, -0.016617 * ARCSEC, -0.0068192 * ARCSEC);
+    mat_mul(&rot1(-eta0), &mat_mul(&rot2(xi0), &rot3(da0)))
+}
+
+/// Intermediate Earth rotations at an epoch.
+struct EarthRotation {
+    /// J2000 → true of date (P then N)
+    precession_nutation: Mat3,
+    gmst: f64,
+    gast: f64,
+    /// PEF → ITRF
+    polar_motion: Mat3,
+    /// Earth angular velocity [rad/s]
+    omega: f64,
+}
+
+impl EarthRotation {
+    fn new(epoch: &Epoch, eop: &EarthOrientation) -> Self {
+        let t = epoch.seconds_since_j2000(TimeScale::Tt) / (86400.0 * DAYS_PER_CENTURY);
+        let (zeta, z, theta) = (
+            (2306.2181 * t + 0.30188 * t * t + 0.017998 * t * t * t) * ARCSEC,
+            (2306.2181 * t + 1.09468 * t * t + 0.018203 * t * t * t) * ARCSEC,
+            (2004.3109 * t - 0.42665 * t * t - 0.041833 * t * t * t) * ARCSEC,
+        );
+        let p = mat_mul(&rot3(-z), &mat_mul(&rot2(theta), &rot3(-zeta)));
+        let (dpsi, deps, eps0, om) = nutation(t);
+        let n = mat_mul(&rot1(-(eps0 + deps)), &mat_mul(&rot3(-dpsi), &rot1(eps0)));
+
+        // IAU-82 GMST from UT1 centuries, in seconds of time
+        // (seconds rather than JD keep µs resolution in the fast-moving angle)
+        let tu = (epoch.seconds_since_j2000(TimeScale::Utc) + eop.ut1_minus_utc) / (86400.0 * DAYS_PER_CENTURY);
+        let gmst_s = 67310.54841 + (876600.0 * 3600.0 + 8640184.812866) * tu + 0.093104 * tu * tu - 6.2e-6 * tu * tu * tu;
+        let gmst = (gmst_s / 240.0).to_radians().rem_euclid(std::f64::consts::TAU);
+        let eqe = dpsi * eps0.cos() + (0.00264 * om.sin() + 0.000063 * (2.0 * om).sin()) * ARCSEC;
+
+        EarthRotation {
+            precession_nutation: mat_mul(&n, &p),
+            gmst,
+            gast: gmst + eqe,
+            // IERS W = R2(xp)·R1(yp) maps ITRF → PEF; its transpose goes back
+            polar_motion: transpose(&mat_mul(&rot2(eop.x_pole), &rot1(eop.y_pole))),
+            omega: 7.292115146706979e-5 * (1.0 - eop.lod / 86400.0),
+        }
+    }
+}
+
+/// IAU/WGCCRE pole right ascension, declination and prime meridian [rad].
+fn body_orientation(frame: Frame, epoch: &Epoch) -> (f64, f64, f64) {
+    let d = epoch.seconds_since_j2000(TimeScale::Tdb) / 86400.0;
+    let t = d / DAYS_PER_CENTURY;
+    match frame {
+        Frame::MoonFixed => {
+            let e = |a: f64, b: f64| (a + b * d).to_radians();
+            let e1 = e(125.045, -0.0529921);
+            let e2 = e(250.089, -0.1059842);
+            let e3 = e(260.008, 13.0120009);
+            let e4 = e(176.625, 13.3407154);
+            let e5 = e(357.529, 0.9856003);
+            let e6 = e(311.589, 26.4057084);
+            let e7 = e(134.963, 13.0649930);
+            let e8 = e(276.617, 0.3287146);
+            let e9 = e(34.226, 1.7484877);
+            let e10 = e(15.134, -0.1589763);
+            let e11 = e(119.743, 0.0036096);
+            let e12 = e(239.961, 0.1643573);
+            let e13 = e(25.053, 12.9590088);
+            let ra = 269.9949 + 0.0031 * t - 3.8787 * e1.sin() - 0.1204 * e2.sin() + 0.0700 * e3.sin()
+                - 0.0172 * e4.sin() + 0.0072 * e6.sin() - 0.0052 * e10.sin() + 0.0043 * e13.sin();
+            let dec = 66.5392 + 0.0130 * t + 1.5419 * e1.cos() + 0.0239 * e2.cos() - 0.0278 * e3.cos()
+                + 0.0068 * e4.cos() - 0.0029 * e6.cos() + 0.0009 * e7.cos() + 0.0008 * e10.cos()
+                - 0.0009 * e13.cos();
+            let w = 38.3213 + 13.17635815 * d - 1.4e-12 * d * d + 3.5610 * e1.sin() + 0.1208 * e2.sin()
+                - 0.0642 * e3.sin() + 0.0158 * e4.sin() + 0.0252 * e5.sin() - 0.0066 * e6.sin()
+                - 0.0047 * e7.sin() - 0.0046 * e8.sin() + 0.0028 * e9.sin() + 0.0052 * e10.sin()
+                + 0.0040 * e11.sin() + 0.0019 * e12.sin() - 0.0044 * e13.sin();
+            (ra.to_radians(), dec.to_radians(), w.to_radians())
+        }
+        _ => (
+            (317.68143 - 0.1061 * t).to_radians(),
+            (52.88650 - 0.0609 * t).to_radians(),
+            (176.630 + 350.89198226 * d).to_radians(),
+        ),
+    }
+}
+
+fn body_fixed_rotation(frame: Frame, epoch: &Epoch) -> Mat3 {
+    let (ra, dec, w) = body_orientation(frame, epoch);
+    mat_mul(&rot3(w), &mat_mul(&rot1(std::f64::consts::FRAC_PI_2 - dec), &rot3(std::f64::consts::FRAC_PI_2 + ra)))
+}
+
+/// Rotation R_F (r_F = R_F r_GCRF) and angular velocity of the frame's axes in F.
+pub fn frame_orientation(frame: Frame, epoch: &Epoch, eop: &EarthOrientation) -> (Mat3, [f64; 3]) {
+    match frame {
+        Frame::Gcrf => (rot3(0.0), [0.0; 3]),
+        Frame::J2000 => (frame_bias(), [0.0; 3]),
+        Frame::Teme | Frame::Itrf => {
+            let e = EarthRotation::new(epoch, eop);
+            let tod = mat_mul(&e.precession_nutation, &frame_bias());
+            let pef = mat_mul(&rot3(e.gast), &tod);
+            if frame == Frame::Teme {
+                (mat_mul(&rot3(-e.gmst), &pef), [0.0; 3])
+            } else {
+                let w = e.polar_motion;
+                (mat_mul(&w, &pef), mat_vec(&w, &[0.0, 0.0, e.omega]))
+            }
+        }
+        Frame::MoonFixed | Frame::MarsFixed => {
+            let r = body_fixed_rotation(frame, epoch);
+            // ω from Ṙ Rᵀ = −[ω×], which keeps the libration and pole rates that a
+            // constant spin about z would drop
+            let h = 1.0;
+            let (rp, rm) = (body_fixed_rotation(frame, &epoch.add_seconds(h)), body_fixed_rotation(frame, &epoch.add_seconds(-h)));
+            let rdot: Mat3 = core::array::from_fn(|i| core::array::from_fn(|j| (rp[i][j] - rm[i][j]) / (2.0 * h)));
+            let s = mat_mul(&rdot, &transpose(&r));
+            (r, [s[1][2], s[2][0], s[0][1]])
+        }
+    }
+}
+
+/// Convert a state between frames at `epoch`. Only axes change: the state must
+/// already be centred on the frame's body (Earth, Moon or Mars).
+pub fn transform_state(state: &State6, from: Frame, to: Frame, epoch: &Epoch, eop: &EarthOrientation) -> State6 {
+    if from == to {
+        return *state;
+    }
+    let (ra, wa) = frame_orientation(from, epoch, eop);
+    let (rb, wb) = frame_orientation(to, epoch, eop);
+    // Inertial velocity in GCRF, then into the target frame
+    let wxr = cross(&wa, &state[..3]);
+    let r = mat_vec(&transpose(&ra), &state[..3]);
+    let v = mat_vec(&transpose(&ra), &[state[3] + wxr[0], state[4] + wxr[1], state[5] + wxr[2]]);
+    let r_b = mat_vec(&rb, &r);
+    let v_b = mat_vec(&rb, &v);
+    let wxr = cross(&wb, &r_b);
+    [r_b[0], r_b[1], r_b[2], v_b[0] - wxr[0], v_b[1] - wxr[1], v_b[2] - wxr[2]]
+}
+
+fn transform_flat(
+    states_js: &Float64Array,
+    time_of: impl Fn(usize) -> f64,
+    epoch: &Epoch,
+    from: Frame,
+    to: Frame,
+    eop: &EarthOrientation,
+) -> Result<Float64Array, JsValue> {
+    let states = states_js.to_vec();
+    if !states.len().is_multiple_of(6) {
+        return Err(JsValue::from_str("states length must be a multiple of 6"));
+    }
+    let mut out = Vec::with_capacity(states.len());
+    for (k, s) in states.chunks_exact(6).enumerate() {
+        let s: State6 = [s[0], s[1], s[2], s[3], s[4], s[5]];
+        out.extend_from_slice(&transform_state(&s, from, to, &epoch.add_seconds(time_of(k)), eop));
+    }
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+/// Transform a flat trajectory [rx,ry,rz,vx,vy,vz, …] whose k-th state is at
+/// `start` + k·dt, as returned by rk4_trajectory.
+#[wasm_bindgen]
+pub fn transform_trajectory(
+    states_js: &Float64Array,
+    start: &Epoch,
+    dt: f64,
+    from: Frame,
+    to: Frame,
+    eop: &EarthOrientation,
+) -> Result<Float64Array, JsValue> {
+    transform_flat(states_js, |k| k as f64 * dt, start, from, to, eop)
+}
+
+/// Transform flat states sampled at `times_js` seconds after `epoch` (e.g. the
+/// times of an AdaptivePropagation); one time per state.
+#[wasm_bindgen]
+pub fn transform_states(
+    states_js: &Float64Array,
+    times_js: &Float64Array,
+    epoch: &Epoch,
+    from: Frame,
+    to: Frame,
+    eop: &EarthOrientation,
+) -> Result<Float64Array, JsValue> {
+    let times = times_js.to_vec();
+    if 6 * times.len() != states_js.length() as usize {
+        return Err(JsValue::from_str("need one time per state"));
+    }
+    transform_flat(states_js, |k| times[k], epoch, from, to, eop)
+}
+
+/// Row-major 3×3 rotation taking `from` coordinates to `to` coordinates at `epoch`.
+#[wasm_bindgen]
+pub fn frame_rotation(from: Frame, to: Frame, epoch: &Epoch, eop: &EarthOrientation) -> Float64Array {
+    let (ra, _) = frame_orientation(from, epoch, eop);
+    let (rb, _) = frame_orientation(to, epoch, eop);
+    let m = mat_mul(&rb, &transpose(&ra));
+    Float64Array::from(&m.concat()[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vallado_epoch() -> (Epoch, EarthOrientation) {
+        let epoch = Epoch::parse_iso("2004-04-06T07:51:28.386009Z").unwrap();
+        (epoch, EarthOrientation::new(-0.4399619, -0.140682, 0.333309, 0.0015563))
+    }
+
+    #[test]
+    fn fk5_reduction_matches_vallado_example() {
+        // Vallado Example 3-15, ITRF → J2000 / TEME / PEF [km, km/s]
+        let (epoch, eop) = vallado_epoch();
+        let itrf = [-1033.4793830e3, 7901.2952754e3, 6380.3565958e3, -3.225636520e3, -2.872451450e3, 5.531924446e3];
+        let check = |frame, want: [f64; 6], tol_r: f64, tol_v: f64| {
+            let got = transform_state(&itrf, Frame::Itrf, frame, &epoch, &eop);
+            for i in 0..6 {
+                let tol = if i < 3 { tol_r } else { tol_v };
+                assert!((got[i] - want[i] * 1e3).abs() < tol, "{:?}[{}]: {} vs {}", frame, i, got[i], want[i] * 1e3);
+            }
+        };
+        // Vallado's J2000 omits the EOP nutation corrections, as does this model
+        check(Frame::J2000, [5102.5096, 6123.01152, 6378.1363, -4.743219, 0.790536, 5.533756], 0.5, 1e-3);
+        check(Frame::Teme, [5094.18016210, 6127.64465950, 6380.34453272, -4.746131487, 0.785818041, 5.531931288], 0.5, 1e-3);
+        // ITRF → TEME differs only by polar motion and GMST, so it is tighter
+        let teme = transform_state(&itrf, Frame::Itrf, Frame::Teme, &epoch, &eop);
+        let back = transform_state(&teme, Frame::Teme, Frame::Itrf, &epoch, &eop);
+        for i in 0..6 {
+            assert!((back[i] - itrf[i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn rotating_frame_velocity_matches_finite_difference() {
+        let (epoch, eop) = vallado_epoch();
+        // Inertially fixed point: its body-fixed velocity is pure −ω × r
+        let fixed = [7.0e6, -1.0e6, 2.0e6, 0.0, 0.0, 0.0];
+        for frame in [Frame::Itrf, Frame::MoonFixed, Frame::MarsFixed] {
+            let at = |dt: f64| transform_state(&fixed, Frame::Gcrf, frame, &epoch.add_seconds(dt), &eop);
+            let (a, b, mid) = (at(-0.5), at(0.5), at(0.0));
+            for i in 0..3 {
+                // Neglected pole/precession rates leave ~1e-6 relative residuals
+                assert!((b[i] - a[i] - mid[i + 3]).abs() < 1e-3, "{:?}[{}] {}", frame, i, b[i] - a[i] - mid[i + 3]);
+            }
+            let back = transform_state(&mid, frame, Frame::Gcrf, &epoch, &eop);
+            for i in 0..6 {
+                assert!((back[i] - fixed[i]).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn body_fixed_pole_follows_iau_model() {
+        let epoch = Epoch::j2000();
+        let eop = EarthOrientation::default();
+        let (r, _) = frame_orientation(Frame::MarsFixed, &epoch, &eop);
+        // Body z axis expressed in GCRF is the third row of R
+        let (ra, dec) = (317.68143f64.to_radians(), 52.88650f64.to_radians());
+        let pole = [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()];
+        for i in 0..3 {
+            assert!((r[2][i] - pole[i]).abs() < 1e-12);
+        }
+        // Moon pole within 1.6° of its mean direction (physical libration terms)
+        let (r, _) = frame_orientation(Frame::MoonFixed, &epoch, &eop);
+        let (ra, dec) = (269.9949f64.to_radians(), 66.5392f64.to_radians());
+        let mean = [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()];
+        let cosang: f64 = (0..3).map(|i| r[2][i] * mean[i]).sum();
+        assert!(cosang.acos().to_degrees() < 1.6);
+        // GCRF ↔ J2000 bias is a few tens of milliarcseconds
+        let b = frame_bias();
+        assert!(b[0][1].abs() > 5e-8 && b[0][1].abs() < 1e-7);
+    }
+}
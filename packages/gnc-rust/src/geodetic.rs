@@ -0,0 +1,265 @@
+//! ID: WASM-GEO-001
+//! Requirement: Convert between WGS-84 geodetic coordinates and ECEF, give the
+//!   geocentric latitude, and compute topocentric (SEZ/ENU) look angles —
+//!   azimuth, elevation, range and range rate — from a ground site.
+//! Purpose: Ground tracks and station visibility for the UI straight from
+//!   propagated trajectories, instead of per-point conversions in TypeScript.
+//! Rationale: ECEF → geodetic uses Bowring's parametric-latitude iteration, which
+//!   converges to sub-millimetre in two or three passes from any altitude; height
+//!   is taken from the form that stays well conditioned at the poles. Look angles
+//!   rotate the site-to-satellite vector into SEZ (Vallado) or ENU.
+//! Inputs: latitude/longitude [rad], height above the ellipsoid [m], ECEF (ITRF)
+//!   states [m, m/s]
+//! Outputs: ECEF position [m]; geodetic triples; [az, el, range, range rate]
+//!   [rad, rad, m, m/s], azimuth clockwise from north
+//! References: Vallado §3.2–3.4 and Algorithm 12, 27; Bowring (1976) Survey
+//!   Review 23(181); NIMA TR8350.2 (WGS-84)
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::constants::{F_EARTH, R_EARTH};
+use crate::frames::{mat_vec, transform_state, EarthOrientation, Frame};
+use crate::rk4::State6;
+use crate::stm::Mat3;
+use crate::time::Epoch;
+
+const E2: f64 = F_EARTH * (2.0 - F_EARTH);
+
+/// WGS-84 geodetic latitude, longitude [rad] and height [m] → ECEF [m].
+pub fn geodetic_to_ecef(lat: f64, lon: f64, height: f64) -> [f64; 3] {
+    let (sl, cl) = lat.sin_cos();
+    let n = R_EARTH / (1.0 - E2 * sl * sl).sqrt();
+    let (so, co) = lon.sin_cos();
+    [(n + height) * cl * co, (n + height) * cl * so, (n * (1.0 - E2) + height) * sl]
+}
+
+/// ECEF [m] → WGS-84 (latitude, longitude [rad], height [m]).
+pub fn ecef_to_geodetic(r: &[f64]) -> (f64, f64, f64) {
+    let p = r[0].hypot(r[1]);
+    let lon = r[1].atan2(r[0]);
+    let b = R_EARTH * (1.0 - F_EARTH);
+    let ep2 = E2 / (1.0 - E2);
+
+    let mut beta = r[2].atan2((1.0 - F_EARTH) * p);
+    let mut lat = 0.0;
+    for _ in 0..5 {
+        let (sb, cb) = beta.sin_cos();
+        let next = (r[2] + ep2 * b * sb * sb * sb).atan2(p - E2 * R_EARTH * cb * cb * cb);
+        let converged = (next - lat).abs() < 1e-14;
+        lat = next;
+        if converged {
+            break;
+        }
+        beta = ((1.0 - F_EARTH) * lat.sin()).atan2(lat.cos());
+    }
+    let (sl, cl) = lat.sin_cos();
+    let n = R_EARTH / (1.0 - E2 * sl * sl).sqrt();
+    let height = p * cl + (r[2] + E2 * n * sl) * sl - n;
+    (lat, lon, height)
+}
+
+/// Geocentric latitude of a point on the ellipsoid with geodetic latitude `lat`.
+pub fn geocentric_latitude(lat: f64) -> f64 {
+    ((1.0 - E2) * lat.tan()).atan()
+}
+
+/// Topocentric axis conventions.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopocentricFrame {
+    /// South, east, zenith
+    Sez = 0,
+    /// East, north, up
+    Enu = 1,
+}
+
+/// A site fixed to the WGS-84 ellipsoid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroundSite {
+    /// Geodetic latitude [rad]
+    pub lat: f64,
+    /// Longitude [rad]
+    pub lon: f64,
+    /// Height above the ellipsoid [m]
+    pub height: f64,
+}
+
+impl GroundSite {
+    pub fn new(lat: f64, lon: f64, height: f64) -> Self {
+        GroundSite { lat, lon, height }
+    }
+
+    pub fn ecef(&self) -> [f64; 3] {
+        geodetic_to_ecef(self.lat, self.lon, self.height)
+    }
+
+    /// Rotation ECEF → topocentric axes.
+    pub fn rotation(&self, frame: TopocentricFrame) -> Mat3 {
+        let (sl, cl) = self.lat.sin_cos();
+        let (so, co) = self.lon.sin_cos();
+        match frame {
+            TopocentricFrame::Sez => [[sl * co, sl * so, -cl], [-so, co, 0.0], [cl * co, cl * so, sl]],
+            TopocentricFrame::Enu => [[-so, co, 0.0], [-sl * co, -sl * so, cl], [cl * co, cl * so, sl]],
+        }
+    }
+
+    /// Satellite state relative to the site in topocentric axes [m, m/s].
+    pub fn topocentric(&self, ecef_state: &State6, frame: TopocentricFrame) -> State6 {
+        let site = self.ecef();
+        let q = self.rotation(frame);
+        let rho = mat_vec(&q, &[ecef_state[0] - site[0], ecef_state[1] - site[1], ecef_state[2] - site[2]]);
+        let rate = mat_vec(&q, &ecef_state[3..]);
+        [rho[0], rho[1], rho[2], rate[0], rate[1], rate[2]]
+    }
+
+    /// [azimuth, elevation, range, range rate] of an ECEF satellite state.
+    pub fn look_angles(&self, ecef_state: &State6) -> [f64; 4] {
+        let [s, e, z, ds, de, dz] = self.topocentric(ecef_state, TopocentricFrame::Sez);
+        let range = (s * s + e * e + z * z).sqrt();
+        let az = e.atan2(-s).rem_euclid(std::f64::consts::TAU);
+        [az, (z / range).asin(), range, (s * ds + e * de + z * dz) / range]
+    }
+}
+
+fn states_from_js(states_js: &Float64Array) -> Result<Vec<f64>, JsValue> {
+    let v = states_js.to_vec();
+    if !v.len().is_multiple_of(6) {
+        return Err(JsValue::from_str("states length must be a multiple of 6"));
+    }
+    Ok(v)
+}
+
+/// Geodetic latitude, longitude [rad] and height [m] → ECEF [m].
+#[wasm_bindgen(js_name = geodetic_to_ecef)]
+pub fn geodetic_to_ecef_js(lat: f64, lon: f64, height: f64) -> Float64Array {
+    Float64Array::from(&geodetic_to_ecef(lat, lon, height)[..])
+}
+
+/// ECEF [m] → [latitude, longitude, height] [rad, rad, m].
+#[wasm_bindgen(js_name = ecef_to_geodetic)]
+pub fn ecef_to_geodetic_js(x: f64, y: f64, z: f64) -> Float64Array {
+    let (lat, lon, h) = ecef_to_geodetic(&[x, y, z]);
+    Float64Array::from(&[lat, lon, h][..])
+}
+
+/// Flat ECEF states [rx,ry,rz,vx,vy,vz, …] → flat [lat, lon, height, …], one triple per state.
+#[wasm_bindgen]
+pub fn geodetic_trajectory(states_js: &Float64Array) -> Result<Float64Array, JsValue> {
+    let states = states_from_js(states_js)?;
+    let mut out = Vec::with_capacity(states.len() / 2);
+    for s in states.chunks_exact(6) {
+        let (lat, lon, h) = ecef_to_geodetic(s);
+        out.extend_from_slice(&[lat, lon, h]);
+    }
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+/// Ground track of a trajectory in any Earth frame whose k-th state is at
+/// `start` + k·dt (rk4_trajectory layout): flat [lat, lon, height, …].
+#[wasm_bindgen]
+pub fn ground_track(
+    states_js: &Float64Array,
+    start: &Epoch,
+    dt: f64,
+    frame: Frame,
+    eop: &EarthOrientation,
+) -> Result<Float64Array, JsValue> {
+    let states = states_from_js(states_js)?;
+    let mut out = Vec::with_capacity(states.len() / 2);
+    for (k, s) in states.chunks_exact(6).enumerate() {
+        let s: State6 = [s[0], s[1], s[2], s[3], s[4], s[5]];
+        let ecef = transform_state(&s, frame, Frame::Itrf, &start.add_seconds(k as f64 * dt), eop);
+        let (lat, lon, h) = ecef_to_geodetic(&ecef);
+        out.extend_from_slice(&[lat, lon, h]);
+    }
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+/// Look angles from a site to flat ECEF states: [az, el, range, range rate] per state.
+#[wasm_bindgen]
+pub fn look_angles(states_js: &Float64Array, site_lat: f64, site_lon: f64, site_height: f64) -> Result<Float64Array, JsValue> {
+    let states = states_from_js(states_js)?;
+    let site = GroundSite::new(site_lat, site_lon, site_height);
+    let mut out = Vec::with_capacity(4 * states.len() / 6);
+    for s in states.chunks_exact(6) {
+        out.extend_from_slice(&site.look_angles(&[s[0], s[1], s[2], s[3], s[4], s[5]]));
+    }
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+/// Satellite states relative to a site in SEZ or ENU axes, flat 6 per state.
+#[wasm_bindgen]
+pub fn topocentric_states(
+    states_js: &Float64Array,
+    site_lat: f64,
+    site_lon: f64,
+    site_height: f64,
+    frame: TopocentricFrame,
+) -> Result<Float64Array, JsValue> {
+    let states = states_from_js(states_js)?;
+    let site = GroundSite::new(site_lat, site_lon, site_height);
+    let mut out = Vec::with_capacity(states.len());
+    for s in states.chunks_exact(6) {
+        out.extend_from_slice(&site.topocentric(&[s[0], s[1], s[2], s[3], s[4], s[5]], frame));
+    }
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frames::transpose;
+
+    #[test]
+    fn geodetic_round_trip_and_vallado_example() {
+        // Vallado Example 3-3
+        let (lat, lon, h) = ecef_to_geodetic(&[6524.834e3, 6862.875e3, 6448.296e3]);
+        assert!((lat.to_degrees() - 34.352496).abs() < 1e-5);
+        assert!((lon.to_degrees() - 46.4464).abs() < 1e-4);
+        assert!((h - 5085.22e3).abs() < 10.0);
+
+        for lat_deg in [-90.0, -60.0, -0.5, 0.0, 33.3, 89.999, 90.0] {
+            for h in [-5e3, 0.0, 400e3, 3.6e7] {
+                let lat = f64::to_radians(lat_deg);
+                let r = geodetic_to_ecef(lat, 2.0, h);
+                let (lat2, lon2, h2) = ecef_to_geodetic(&r);
+                assert!((lat2 - lat).abs() < 1e-12, "{} {}", lat_deg, h);
+                assert!((h2 - h).abs() < 1e-6, "{} {}: {}", lat_deg, h, h2);
+                if lat_deg.abs() < 90.0 {
+                    assert!((lon2 - 2.0).abs() < 1e-12);
+                }
+            }
+        }
+        // Geocentric latitude is smaller in magnitude, by at most ~0.19° at 45°
+        let d = 45f64.to_radians() - geocentric_latitude(45f64.to_radians());
+        assert!((d.to_degrees() - 0.1924).abs() < 1e-3);
+    }
+
+    #[test]
+    fn look_angles_match_geometry() {
+        let site = GroundSite::new(0.4, -1.2, 100.0);
+        let up = geodetic_to_ecef(0.4, -1.2, 500e3);
+        let [_, el, range, rr] = site.look_angles(&[up[0], up[1], up[2], 0.0, 0.0, 0.0]);
+        assert!((el - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        assert!((range - 499.9e3).abs() < 1e-6);
+        assert_eq!(rr, 0.0);
+
+        // Displacements along ENU axes give the expected azimuths; ENU and SEZ agree
+        let enu_to_ecef = transpose(&site.rotation(TopocentricFrame::Enu));
+        let base = site.ecef();
+        for (dir, az) in [([0.0, 1.0, 0.0], 0.0), ([1.0, 0.0, 0.0], 90.0), ([0.0, -1.0, 0.0], 180.0), ([-1.0, 0.0, 0.0], 270.0)] {
+            let d = mat_vec(&enu_to_ecef, &[dir[0] * 1e5, dir[1] * 1e5, 1e5]);
+            let v = mat_vec(&enu_to_ecef, &[dir[0] * 10.0, dir[1] * 10.0, 0.0]);
+            let s = [base[0] + d[0], base[1] + d[1], base[2] + d[2], v[0], v[1], v[2]];
+            let [a, el, range, rr] = site.look_angles(&s);
+            let diff = (a.to_degrees() - az + 180.0).rem_euclid(360.0) - 180.0;
+            assert!(diff.abs() < 1e-9, "{} vs {}", a.to_degrees(), az);
+            assert!((el.to_degrees() - 45.0).abs() < 1e-9);
+            assert!((range - 2f64.sqrt() * 1e5).abs() < 1e-6);
+            assert!((rr - 10.0 / 2f64.sqrt()).abs() < 1e-9);
+            let (sez, enu) = (site.topocentric(&s, TopocentricFrame::Sez), site.topocentric(&s, TopocentricFrame::Enu));
+            assert!((sez[0] + enu[1]).abs() < 1e-6 && (sez[1] - enu[0]).abs() < 1e-6 && (sez[2] - enu[2]).abs() < 1e-6);
+        }
+    }
+}
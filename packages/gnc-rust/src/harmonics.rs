@@ -0,0 +1,430 @@
+//! ID: WASM-SHGRAV-001
+//! Requirement: Evaluate a spherical-harmonic gravity field up to degree/order N
+//!   with coefficients loaded from standard coefficient files.
+//! Purpose: Tesseral and sectoral terms (lunar mascons, Earth's C22/S22) that
+//!   the zonal-only model cannot represent, e.g. for low lunar orbit stability.
+//! Rationale: Cunningham's V/W recursion with fully normalised Legendre factors
+//!   is free of the 1/cos φ pole singularity of the spherical-coordinate gradient
+//!   and stays bounded to high degree, where unnormalised terms overflow.
+//! Inputs: inertial position [m]; body rotation about +z as θ(t) = θ₀ + ω t;
+//!   fully normalised C̄nm, S̄nm with their μ [m³/s²] and reference radius [m]
+//! Outputs: total gravitational acceleration [m/s²] (includes the C00 central term)
+//! Formats: ICGEM .gfc (EGM96/EGM2008, GRGM) and PDS SHADR (GRAIL, MGS)
+//! References: Montenbruck & Gill §3.2.4–3.2.5 (eqs. 3.29–3.33);
+//!   ICGEM format spec (Barthelmes & Förste 2011); PDS SHADR label description
+
+use std::path::Path;
+
+use wasm_bindgen::prelude::*;
+
+use crate::constants::OMEGA_EARTH;
+use crate::forces::{ForceModel, ForceModelBuilder, SpacecraftParams};
+use crate::rk4::State6;
+use crate::stm::Mat3;
+
+const EGM96_4X4: &str = include_str!("../data/egm96_4x4.gfc");
+
+#[inline]
+fn tri(n: usize, m: usize) -> usize {
+    n * (n + 1) / 2 + m
+}
+
+/// Normalisation N with P̄nm = N·Pnm: √((2−δm0)(2n+1)(n−m)!/(n+m)!)
+fn norm_factor(n: usize, m: usize) -> f64 {
+    let mut ratio = 1.0;
+    for k in (n - m + 1)..=(n + m) {
+        ratio /= k as f64;
+    }
+    let delta = if m == 0 { 1.0 } else { 2.0 };
+    (delta * (2 * n + 1) as f64 * ratio).sqrt()
+}
+
+fn parse_number(token: &str) -> Result<f64, String> {
+    token
+        .replace(['D', 'd'], "e")
+        .parse::<f64>()
+        .map_err(|_| format!("invalid number '{}'", token))
+}
+
+fn parse_index(token: &str) -> Result<usize, String> {
+    token.parse::<usize>().map_err(|_| format!("invalid degree/order '{}'", token))
+}
+
+/// Fully normalised gravity field coefficients.
+#[derive(Debug, Clone)]
+pub struct GravityField {
+    /// Gravitational parameter the coefficients refer to [m³/s²]
+    pub mu: f64,
+    /// Reference radius [m]
+    pub radius: f64,
+    degree: usize,
+    c: Vec<f64>,
+    s: Vec<f64>,
+}
+
+impl GravityField {
+    fn from_entries(
+        mu: f64,
+        radius: f64,
+        normalized: bool,
+        entries: &[(usize, usize, f64, f64)],
+    ) -> Result<Self, String> {
+        if !(mu > 0.0 && radius > 0.0) {
+            return Err("gravity field needs positive GM and reference radius".into());
+        }
+        let degree = entries.iter().map(|e| e.0).max().ok_or("no coefficients found")?;
+        let mut c = vec![0.0; tri(degree, degree) + 1];
+        let mut s = vec![0.0; c.len()];
+        for &(n, m, cnm, snm) in entries {
+            if m > n {
+                return Err(format!("order {} exceeds degree {}", m, n));
+            }
+            let k = if normalized { 1.0 } else { 1.0 / norm_factor(n, m) };
+            c[tri(n, m)] = cnm * k;
+            s[tri(n, m)] = snm * k;
+        }
+        Ok(GravityField { mu, radius, degree, c, s })
+    }
+
+    /// Parse an ICGEM `.gfc` file. Only static `gfc` lines are used.
+    pub fn parse_gfc(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+        let (mut mu, mut radius, mut normalized) = (None, None, true);
+        for line in lines.by_ref() {
+            let mut tok = line.split_whitespace();
+            match tok.next() {
+                Some("end_of_head") => break,
+                Some("earth_gravity_constant") | Some("gravity_constant") => {
+                    mu = Some(parse_number(tok.next().unwrap_or(""))?);
+                }
+                Some("radius") => radius = Some(parse_number(tok.next().unwrap_or(""))?),
+                Some("norm") => normalized = tok.next() != Some("unnormalized"),
+                _ => {}
+            }
+        }
+        let mut entries = Vec::new();
+        for line in lines {
+            let tok: Vec<&str> = line.split_whitespace().collect();
+            if tok.first() != Some(&"gfc") {
+                continue;
+            }
+            if tok.len() < 5 {
+                return Err(format!("short gfc line '{}'", line));
+            }
+            entries.push((parse_index(tok[1])?, parse_index(tok[2])?, parse_number(tok[3])?, parse_number(tok[4])?));
+        }
+        let mu = mu.ok_or("gfc header lacks earth_gravity_constant")?;
+        let radius = radius.ok_or("gfc header lacks radius")?;
+        Self::from_entries(mu, radius, normalized, &entries)
+    }
+
+    /// Parse a PDS SHADR table: a header record
+    /// `R_ref [km], GM [km³/s²], σGM, degree, order, normalisation, lon, lat`
+    /// followed by `n, m, C, S[, σC, σS]` records.
+    pub fn parse_shadr(text: &str) -> Result<Self, String> {
+        let split = |line: &str| -> Vec<String> {
+            line.split(|ch: char| ch == ',' || ch.is_whitespace())
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+        let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+        let header = split(lines.next().ok_or("empty SHADR file")?);
+        if header.len() < 6 {
+            return Err("SHADR header needs at least 6 fields".into());
+        }
+        let radius = parse_number(&header[0])? * 1e3;
+        let mu = parse_number(&header[1])? * 1e9;
+        let normalized = parse_number(&header[5])? != 0.0;
+        let mut entries = Vec::new();
+        for line in lines {
+            let tok = split(line);
+            if tok.len() < 4 {
+                return Err(format!("short SHADR record '{}'", line));
+            }
+            entries.push((parse_index(&tok[0])?, parse_index(&tok[1])?, parse_number(&tok[2])?, parse_number(&tok[3])?));
+        }
+        Self::from_entries(mu, radius, normalized, &entries)
+    }
+
+    /// Parse either format, detected from the ICGEM `end_of_head` marker.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        if text.contains("end_of_head") {
+            Self::parse_gfc(text)
+        } else {
+            Self::parse_shadr(text)
+        }
+    }
+
+    /// Read and parse a local coefficient file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("{}: {}", path.as_ref().display(), e))?;
+        Self::parse(&text)
+    }
+
+    /// Bundled EGM96 truncated to degree/order 4.
+    pub fn egm96_4x4() -> Self {
+        Self::parse_gfc(EGM96_4X4).expect("bundled EGM96 coefficients")
+    }
+
+    /// Highest degree present.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Normalised (C̄nm, S̄nm); zero beyond the field's degree.
+    pub fn coefficient(&self, n: usize, m: usize) -> (f64, f64) {
+        if n > self.degree || m > n {
+            return (0.0, 0.0);
+        }
+        (self.c[tri(n, m)], self.s[tri(n, m)])
+    }
+}
+
+/// Spherical-harmonic gravity of a body rotating uniformly about inertial +z.
+#[derive(Debug, Clone)]
+pub struct SphericalHarmonicGravity {
+    field: GravityField,
+    degree: usize,
+    order: usize,
+    /// Body rotation rate [rad/s]
+    pub rotation_rate: f64,
+    /// Body-fixed x-axis angle from inertial x at t = 0 [rad]
+    pub initial_angle: f64,
+}
+
+impl SphericalHarmonicGravity {
+    /// Field truncated to `degree` × `order` (clamped to what the field provides).
+    pub fn new(field: GravityField, degree: usize, order: usize, rotation_rate: f64, initial_angle: f64) -> Self {
+        let degree = degree.min(field.degree);
+        let order = order.min(degree);
+        SphericalHarmonicGravity { field, degree, order, rotation_rate, initial_angle }
+    }
+
+    /// Acceleration in the body-fixed frame for a body-fixed position.
+    pub fn body_fixed_acceleration(&self, r: &[f64; 3]) -> [f64; 3] {
+        let (nmax, mmax) = (self.degree, self.order);
+        let re = self.field.radius;
+        let r2 = r[0] * r[0] + r[1] * r[1] + r[2] * r[2];
+        let (x0, y0, z0, rho) = (re * r[0] / r2, re * r[1] / r2, re * r[2] / r2, re * re / r2);
+
+        // V̄nm, W̄nm to degree nmax+1, order mmax+1
+        let mut v = vec![0.0; tri(nmax + 1, nmax + 1) + 1];
+        let mut w = vec![0.0; v.len()];
+        v[0] = re / r2.sqrt();
+        for m in 0..=(mmax + 1).min(nmax + 1) {
+            let mf = m as f64;
+            if m > 0 {
+                let f = if m == 1 { 3f64.sqrt() } else { ((2.0 * mf + 1.0) / (2.0 * mf)).sqrt() };
+                let (vp, wp) = (v[tri(m - 1, m - 1)], w[tri(m - 1, m - 1)]);
+                v[tri(m, m)] = f * (x0 * vp - y0 * wp);
+                w[tri(m, m)] = f * (x0 * wp + y0 * vp);
+            }
+            for n in (m + 1)..=(nmax + 1) {
+                let nf = n as f64;
+                let a = ((2.0 * nf - 1.0) * (2.0 * nf + 1.0) / ((nf - mf) * (nf + mf))).sqrt();
+                let mut vn = a * z0 * v[tri(n - 1, m)];
+                let mut wn = a * z0 * w[tri(n - 1, m)];
+                if n >= m + 2 {
+                    let b = ((2.0 * nf + 1.0) * (nf + mf - 1.0) * (nf - mf - 1.0)
+                        / ((2.0 * nf - 3.0) * (nf + mf) * (nf - mf)))
+                        .sqrt();
+                    vn -= b * rho * v[tri(n - 2, m)];
+                    wn -= b * rho * w[tri(n - 2, m)];
+                }
+                v[tri(n, m)] = vn;
+                w[tri(n, m)] = wn;
+            }
+        }
+
+        let mut a = [0.0; 3];
+        for n in 0..=nmax {
+            let nf = n as f64;
+            let k = 2.0 * nf + 1.0;
+            let q = 2.0 * nf + 3.0;
+            for m in 0..=n.min(mmax) {
+                let mf = m as f64;
+                let (c, s) = (self.field.c[tri(n, m)], self.field.s[tri(n, m)]);
+                if c == 0.0 && s == 0.0 {
+                    continue;
+                }
+                let up = tri(n + 1, m);
+                if m == 0 {
+                    let f = (k * (nf + 1.0) * (nf + 2.0) / (2.0 * q)).sqrt();
+                    a[0] -= c * f * v[tri(n + 1, 1)];
+                    a[1] -= c * f * w[tri(n + 1, 1)];
+                } else {
+                    let f1 = 0.5 * (k * (nf + mf + 1.0) * (nf + mf + 2.0) / q).sqrt();
+                    let d = if m == 1 { 2.0 } else { 1.0 };
+                    let f2 = 0.5 * (d * k * (nf - mf + 1.0) * (nf - mf + 2.0) / q).sqrt();
+                    let (vp, wp) = (v[tri(n + 1, m + 1)], w[tri(n + 1, m + 1)]);
+                    let (vm, wm) = (v[tri(n + 1, m - 1)], w[tri(n + 1, m - 1)]);
+                    a[0] += f1 * (-c * vp - s * wp) + f2 * (c * vm + s * wm);
+                    a[1] += f1 * (-c * wp + s * vp) + f2 * (-c * wm + s * vm);
+                }
+                let f3 = (k * (nf + mf + 1.0) * (nf - mf + 1.0) / q).sqrt();
+                a[2] += f3 * (-c * v[up] - s * w[up]);
+            }
+        }
+        let g = self.field.mu / (re * re);
+        [g * a[0], g * a[1], g * a[2]]
+    }
+
+    fn rotation(&self, t: f64) -> (f64, f64) {
+        let theta = self.initial_angle + self.rotation_rate * t;
+        (theta.cos(), theta.sin())
+    }
+}
+
+impl ForceModel for SphericalHarmonicGravity {
+    fn acceleration(&self, t: f64, state: &State6, _params: &SpacecraftParams) -> [f64; 3] {
+        let (c, s) = self.rotation(t);
+        let r_bf = [c * state[0] + s * state[1], -s * state[0] + c * state[1], state[2]];
+        let a = self.body_fixed_acceleration(&r_bf);
+        [c * a[0] - s * a[1], s * a[0] + c * a[1], a[2]]
+    }
+
+    /// Central differences of the acceleration; step ≈ 1e-6 r.
+    fn position_gradient(&self, t: f64, state: &State6, params: &SpacecraftParams) -> Option<Mat3> {
+        let h = 1e-6 * (state[0] * state[0] + state[1] * state[1] + state[2] * state[2]).sqrt();
+        let mut g = [[0.0; 3]; 3];
+        for j in 0..3 {
+            let (mut sp, mut sm) = (*state, *state);
+            sp[j] += h;
+            sm[j] -= h;
+            let (ap, am) = (self.acceleration(t, &sp, params), self.acceleration(t, &sm, params));
+            for i in 0..3 {
+                g[i][j] = (ap[i] - am[i]) / (2.0 * h);
+            }
+        }
+        Some(g)
+    }
+}
+
+#[wasm_bindgen]
+impl ForceModelBuilder {
+    /// Spherical-harmonic gravity from `.gfc` or SHADR file text, truncated to
+    /// degree × order. The body rotates about +z at `rotation_rate` [rad/s] from
+    /// `initial_angle` [rad]. Includes the central term: use instead of add_point_mass.
+    #[wasm_bindgen]
+    pub fn add_gravity_field(
+        &mut self,
+        text: &str,
+        degree: u32,
+        order: u32,
+        rotation_rate: f64,
+        initial_angle: f64,
+    ) -> Result<(), JsValue> {
+        let field = GravityField::parse(text).map_err(|e| JsValue::from_str(&e))?;
+        self.push(SphericalHarmonicGravity::new(field, degree as usize, order as usize, rotation_rate, initial_angle));
+        Ok(())
+    }
+
+    /// Bundled EGM96 4×4 Earth field, Earth-fixed x-axis along inertial x at t = 0.
+    #[wasm_bindgen]
+    pub fn add_earth_gravity_4x4(&mut self, degree: u32, order: u32) {
+        let field = GravityField::egm96_4x4();
+        self.push(SphericalHarmonicGravity::new(field, degree as usize, order as usize, OMEGA_EARTH, 0.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forces::PointMass;
+    use crate::zonal::ZonalHarmonics;
+
+    /// Brute-force potential μ/r Σ (R/r)ⁿ P̄nm(sin φ)(C̄ cos mλ + S̄ sin mλ).
+    fn potential(field: &GravityField, r: &[f64; 3]) -> f64 {
+        let n_max = field.degree();
+        let rn = (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).sqrt();
+        let (sphi, cphi) = (r[2] / rn, (r[0] * r[0] + r[1] * r[1]).sqrt() / rn);
+        let lon = r[1].atan2(r[0]);
+        let mut p = vec![0.0; tri(n_max, n_max) + 1];
+        p[0] = 1.0;
+        let mut u = 0.0;
+        for m in 0..=n_max {
+            let mf = m as f64;
+            if m > 0 {
+                let f = if m == 1 { 3f64.sqrt() } else { ((2.0 * mf + 1.0) / (2.0 * mf)).sqrt() };
+                p[tri(m, m)] = f * cphi * p[tri(m - 1, m - 1)];
+            }
+            for n in (m + 1)..=n_max {
+                let nf = n as f64;
+                let a = ((2.0 * nf - 1.0) * (2.0 * nf + 1.0) / ((nf - mf) * (nf + mf))).sqrt();
+                p[tri(n, m)] = a * sphi * p[tri(n - 1, m)];
+                if n >= m + 2 {
+                    let b = ((2.0 * nf + 1.0) * (nf + mf - 1.0) * (nf - mf - 1.0)
+                        / ((2.0 * nf - 3.0) * (nf + mf) * (nf - mf)))
+                        .sqrt();
+                    p[tri(n, m)] -= b * p[tri(n - 2, m)];
+                }
+            }
+        }
+        for n in 0..=n_max {
+            for m in 0..=n {
+                let (c, s) = field.coefficient(n, m);
+                let ml = m as f64 * lon;
+                u += (field.radius / rn).powi(n as i32) * p[tri(n, m)] * (c * ml.cos() + s * ml.sin());
+            }
+        }
+        field.mu / rn * u
+    }
+
+    #[test]
+    fn acceleration_is_gradient_of_potential() {
+        // Perturbation only, so finite differences of U are not swamped by μ/r
+        let mut field = GravityField::egm96_4x4();
+        field.c[0] = 0.0;
+        let model = SphericalHarmonicGravity::new(field.clone(), 4, 4, 0.0, 0.0);
+        for r in [[4.1e6, -3.3e6, 4.4e6], [-2.0e6, 1.5e6, -6.6e6], [1.0, 2.0, 7.0e6]] {
+            let a = model.body_fixed_acceleration(&r);
+            let scale = (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt();
+            for j in 0..3 {
+                let (mut rp, mut rm) = (r, r);
+                rp[j] += 1.0;
+                rm[j] -= 1.0;
+                let fd = (potential(&field, &rp) - potential(&field, &rm)) / 2.0;
+                assert!((fd - a[j]).abs() < 1e-6 * scale, "{:?} a{}: {} vs {}", r, j, a[j], fd);
+            }
+        }
+    }
+
+    #[test]
+    fn zonal_terms_match_closed_form_model() {
+        let field = GravityField::egm96_4x4();
+        let j: Vec<f64> = (2..=4).map(|n| -((2 * n + 1) as f64).sqrt() * field.coefficient(n, 0).0).collect();
+        let zonal = ZonalHarmonics::new(field.mu, field.radius, &j).unwrap();
+        let sh = SphericalHarmonicGravity::new(field.clone(), 4, 0, 7.29e-5, 1.3);
+        let params = SpacecraftParams::default();
+        for s in [[7.0e6, 0.0, 0.0, 0.0, 0.0, 0.0], [3.0e6, -4.0e6, 5.0e6, 0.0, 0.0, 0.0], [0.0, 1.0, -7.2e6, 0.0, 0.0, 0.0]] {
+            let a_sh = sh.acceleration(1234.0, &s, &params);
+            let a_pm = PointMass { mu: field.mu }.acceleration(0.0, &s, &params);
+            let a_j = zonal.acceleration(0.0, &s, &params);
+            for i in 0..3 {
+                let expected = a_pm[i] + a_j[i];
+                assert!((a_sh[i] - expected).abs() < 1e-11, "{:?}[{}]: {} vs {}", s, i, a_sh[i], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn parses_shadr_and_unnormalised_gfc() {
+        let shadr = "1738.0, 4902.80007, 0.0001, 2, 2, 1, 0.0, 0.0\n\
+                     0, 0, 1.0, 0.0, 0.0, 0.0\n\
+                     2, 0, -9.0880D-05, 0.0, 1e-12, 0.0\n\
+                     2, 2, 3.4673e-05, 1.6e-08, 1e-12, 1e-12\n";
+        let moon = GravityField::parse(shadr).unwrap();
+        assert_eq!(moon.degree(), 2);
+        assert!((moon.radius - 1.738e6).abs() < 1e-6);
+        assert!((moon.mu - 4.90280007e12).abs() < 1.0);
+        assert_eq!(moon.coefficient(2, 2), (3.4673e-5, 1.6e-8));
+
+        let gfc = "earth_gravity_constant 3.986004418e14\nradius 6378136.3\nnorm unnormalized\nend_of_head ===\n\
+                   gfc 0 0 1.0 0.0\ngfc 2 0 -1.0826D-03 0.0\n";
+        let earth = GravityField::parse(gfc).unwrap();
+        assert!((earth.coefficient(2, 0).0 + 1.0826e-3 / 5f64.sqrt()).abs() < 1e-15);
+        assert!(GravityField::parse("end_of_head\n").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! ID: WASM-KEPLER-001
+//! Requirement: Propagate two-body states analytically with a universal-variable
+//!   f and g solution valid for elliptic, parabolic and hyperbolic orbits, forward
+//!   or backward in time.
+//! Purpose: Exact, step-free fast path for pure two-body arcs, and the truth
+//!   reference for the numerical integrators' accuracy tests.
+//! Rationale: Kepler's equation in the universal anomaly χ, written with the
+//!   Stumpff functions c₂(ψ), c₃(ψ), has one form for every conic. It is solved
+//!   with the Laguerre–Conway iteration, which converges from the standard
+//!   initial guesses where Newton's method can overshoot on hyperbolic arcs.
+//!   Elliptic time spans are first reduced modulo the period.
+//! Inputs: state [rx,ry,rz,vx,vy,vz] [m, m/s], dt [s], steps [count], μ [m³/s²]
+//! Outputs: state at t₀ + dt (or every step for trajectories)
+//! Failure Modes: rectilinear orbits and non-convergence yield an error (NaN
+//!   states through the JS entry points).
+//! References: Vallado Algorithm 8 (KEPLER) and §2.3; Conway (1986) Celest.
+//!   Mech. 39 (Laguerre iteration); Bate, Mueller & White ch. 4
+
+use std::f64::consts::TAU;
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::rk4::State6;
+
+/// Stumpff functions c₂(ψ), c₃(ψ).
+pub fn stumpff(psi: f64) -> (f64, f64) {
+    if psi > 1e-6 {
+        let s = psi.sqrt();
+        ((1.0 - s.cos()) / psi, (s - s.sin()) / (s * psi))
+    } else if psi < -1e-6 {
+        let s = (-psi).sqrt();
+        ((1.0 - s.cosh()) / psi, (s.sinh() - s) / (s * -psi))
+    } else {
+        (1.0 / 2.0 - psi / 24.0 + psi * psi / 720.0, 1.0 / 6.0 - psi / 120.0 + psi * psi / 5040.0)
+    }
+}
+
+/// Two-body state after `dt` seconds (negative dt propagates backward).
+pub fn kepler_propagate_state(state: &State6, dt: f64, mu: f64) -> Result<State6, String> {
+    if dt == 0.0 {
+        return Ok(*state);
+    }
+    let (r0, v0) = (&state[..3], &state[3..]);
+    let r0n = (r0[0] * r0[0] + r0[1] * r0[1] + r0[2] * r0[2]).sqrt();
+    let v2 = v0[0] * v0[0] + v0[1] * v0[1] + v0[2] * v0[2];
+    let rv = r0[0] * v0[0] + r0[1] * v0[1] + r0[2] * v0[2];
+    let h2 = r0n * r0n * v2 - rv * rv;
+    if h2 <= 1e-20 * r0n * r0n * v2 {
+        return Err("rectilinear orbit".into());
+    }
+    let smu = mu.sqrt();
+    let alpha = 2.0 / r0n - v2 / mu; // 1/a
+    let sigma = rv / smu;
+
+    let mut dt = dt;
+    let mut chi = if alpha * r0n > 1e-9 {
+        dt %= TAU / (smu * alpha.powf(1.5));
+        smu * dt * alpha
+    } else if alpha * r0n < -1e-9 {
+        let a = 1.0 / alpha;
+        let arg = -2.0 * mu * alpha * dt / (rv + dt.signum() * (-mu * a).sqrt() * (1.0 - r0n * alpha));
+        dt.signum() * (-a).sqrt() * arg.abs().ln()
+    } else {
+        // Near-parabolic: Barker's equation for the initial guess
+        let p = h2 / mu;
+        let s = 0.5 * (1.0 / (3.0 * (mu / (p * p * p)).sqrt() * dt)).atan();
+        let w = s.tan().cbrt().atan();
+        p.sqrt() * 2.0 / (2.0 * w).tan()
+    };
+    if !chi.is_finite() {
+        chi = smu * dt / r0n;
+    }
+
+    // Laguerre–Conway iteration on F(χ) = σχ²c₂ + (1 − αr₀)χ³c₃ + r₀χ − √μ·dt
+    const N: f64 = 5.0;
+    let mut converged = false;
+    for _ in 0..100 {
+        let psi = chi * chi * alpha;
+        let (c2, c3) = stumpff(psi);
+        let f = sigma * chi * chi * c2 + (1.0 - alpha * r0n) * chi * chi * chi * c3 + r0n * chi - smu * dt;
+        let df = sigma * chi * (1.0 - psi * c3) + (1.0 - alpha * r0n) * chi * chi * c2 + r0n;
+        let ddf = sigma * (1.0 - psi * c2) + (1.0 - alpha * r0n) * chi * (1.0 - psi * c3);
+        let disc = ((N - 1.0) * (N - 1.0) * df * df - N * (N - 1.0) * f * ddf).abs().sqrt();
+        let step = N * f / (df + df.signum() * disc);
+        chi -= step;
+        if step.abs() <= 1e-13 * chi.abs().max(1.0) {
+            converged = true;
+            break;
+        }
+    }
+    if !converged || !chi.is_finite() {
+        return Err(format!("universal Kepler iteration did not converge (dt = {})", dt));
+    }
+    let psi = chi * chi * alpha;
+    let (c2, c3) = stumpff(psi);
+
+    let r = chi * chi * c2 + sigma * chi * (1.0 - psi * c3) + r0n * (1.0 - psi * c2);
+    let f = 1.0 - chi * chi / r0n * c2;
+    let g = dt - chi * chi * chi / smu * c3;
+    let gdot = 1.0 - chi * chi / r * c2;
+    let fdot = smu / (r * r0n) * chi * (psi * c3 - 1.0);
+    Ok(core::array::from_fn(|i| {
+        if i < 3 { f * r0[i] + g * v0[i] } else { fdot * r0[i - 3] + gdot * v0[i - 3] }
+    }))
+}
+
+fn kepler_run(state_js: &Float64Array, dt: f64, steps: u32, mu: f64, keep_all: bool) -> Float64Array {
+    let sv = state_js.to_vec();
+    let s0: State6 = [sv[0], sv[1], sv[2], sv[3], sv[4], sv[5]];
+    let first = if keep_all { 0 } else { steps };
+    let mut buf = Vec::with_capacity(6 * (steps - first + 1) as usize);
+    for k in first..=steps {
+        // Each sample from the initial state, so nothing accumulates
+        let s = kepler_propagate_state(&s0, k as f64 * dt, mu).unwrap_or([f64::NAN; 6]);
+        buf.extend_from_slice(&s);
+    }
+    Float64Array::from(buf.as_slice())
+}
+
+/// Analytic counterpart of rk4_propagate: the two-body state at t₀ + steps·dt.
+/// Returns NaNs if the orbit is rectilinear or the iteration fails.
+#[wasm_bindgen]
+pub fn kepler_propagate(state_js: &Float64Array, dt: f64, steps: u32, mu: f64) -> Float64Array {
+    kepler_run(state_js, dt, steps, mu, false)
+}
+
+/// Analytic counterpart of rk4_trajectory: flat 6·(steps+1) states at k·dt.
+#[wasm_bindgen]
+pub fn kepler_trajectory(state_js: &Float64Array, dt: f64, steps: u32, mu: f64) -> Float64Array {
+    kepler_run(state_js, dt, steps, mu, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MU_EARTH;
+    use crate::dop853::dop853_integrate;
+    use crate::rk45::{two_body_rhs, AdaptiveOptions};
+
+    #[test]
+    fn matches_vallado_example() {
+        // Vallado Example 2-4: 40 minutes from (r₀, v₀)
+        let s0 = [1131.340e3, -2282.343e3, 6672.423e3, -5.64305e3, 4.30333e3, 2.42879e3];
+        let s = kepler_propagate_state(&s0, 2400.0, MU_EARTH).unwrap();
+        let want = [-4219.7527e3, 4363.0292e3, -3958.7666e3, 3.689866e3, -1.916735e3, -6.112511e3];
+        for i in 0..6 {
+            let tol = if i < 3 { 1.0 } else { 1e-3 };
+            assert!((s[i] - want[i]).abs() < tol, "[{}] {} vs {}", i, s[i], want[i]);
+        }
+    }
+
+    #[test]
+    fn all_conics_match_dop853_both_directions() {
+        let r = 7.0e6;
+        let vesc = (2.0 * MU_EARTH / r).sqrt();
+        let opts = AdaptiveOptions::new(1e-6, 1e-13);
+        for (speed, dt) in [(0.8, 20000.0), (1.0, 20000.0), (1.0 + 1e-12, 5000.0), (1.6, 50000.0), (0.75, -9000.0)] {
+            let s0 = [r, 0.0, 0.0, 0.0, speed * vesc * 0.8, speed * vesc * 0.6];
+            let s = kepler_propagate_state(&s0, dt, MU_EARTH).unwrap();
+            let (want, _) = dop853_integrate(two_body_rhs(MU_EARTH), 0.0, &s0, dt, &opts, false, |_, _, _| {}).unwrap();
+            let rn = (s[0] * s[0] + s[1] * s[1] + s[2] * s[2]).sqrt();
+            for i in 0..3 {
+                assert!((s[i] - want[i]).abs() < 1e-7 * rn, "v={} dt={} [{}]: {} vs {}", speed, dt, i, s[i], want[i]);
+            }
+            let back = kepler_propagate_state(&s, -dt, MU_EARTH).unwrap();
+            for i in 0..6 {
+                assert!((back[i] - s0[i]).abs() < 1e-8 * s0[i].abs().max(1e3), "v={} [{}]", speed, i);
+            }
+        }
+        assert!(kepler_propagate_state(&[r, 0.0, 0.0, 1e3, 0.0, 0.0], 100.0, MU_EARTH).is_err());
+    }
+}
@@ -0,0 +1,552 @@
+//! ID: WASM-LAMBERT-001
+//! Requirement: Solve Lambert's problem (r₁, r₂, Δt) for every zero- and
+//!   multi-revolution transfer, including both N-revolution branches, and report
+//!   structured diagnostics when no solution exists.
+//! Purpose: Fast native replacement for gnc-core's single-revolution
+//!   `lambertIzzo`, and the kernel for dense porkchop grids.
+//! Rationale: Izzo's formulation reduces every case to one non-dimensional
+//!   time-of-flight curve T(x; λ, N). Starting from his initial guesses, the
+//!   third-order Householder iteration converges in 2–4 steps on every branch.
+//!   T(x) is evaluated with Battin's hypergeometric series near x = 1, Lagrange's
+//!   form nearby, and Lancaster's expression elsewhere, so the parabolic
+//!   neighbourhood does not lose precision.
+//! Inputs: r₁, r₂ [m], Δt [s], μ [m³/s²], direction (prograde = counter-clockwise
+//!   about +z), maximum revolution count
+//! Outputs: departure/arrival velocities [m/s] per (N, branch) and diagnostics
+//!   (conic case, feasible TOF bounds, transfer angle, failure reason)
+//! Assumptions/Limits: the transfer plane is undefined for transfer angles of 0
+//!   or π; those cases are rejected as degenerate geometry.
+//! References: Izzo (2015) Celest. Mech. Dyn. Astr. 121, "Revisiting Lambert's
+//!   problem"; Battin §7.3–7.4; Lancaster & Blanchard (1969) NASA TN D-5368
+
+use std::f64::consts::{PI, TAU};
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::frames::cross;
+
+/// Which N-revolution solution: the single zero-revolution arc, or the
+/// left (x < x_min, longer semi-major axis) / right branch for N ≥ 1.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LambertBranch {
+    Single = 0,
+    Left = 1,
+    Right = 2,
+}
+
+/// Conic type of a transfer arc.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConicCase {
+    Elliptic,
+    Parabolic,
+    Hyperbolic,
+}
+
+impl ConicCase {
+    /// Name used by gnc-core's `LambertConicCase`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConicCase::Elliptic => "elliptic",
+            ConicCase::Parabolic => "parabolic",
+            ConicCase::Hyperbolic => "hyperbolic",
+        }
+    }
+}
+
+/// Why a Lambert solve produced no solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LambertFailure {
+    InvalidInput,
+    DegenerateGeometry,
+    InfeasibleTimeOfFlight,
+    NonConvergence,
+    NumericalFailure,
+}
+
+impl LambertFailure {
+    /// Name used by gnc-core's `LambertFailureReason`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LambertFailure::InvalidInput => "invalid_input",
+            LambertFailure::DegenerateGeometry => "degenerate_geometry",
+            LambertFailure::InfeasibleTimeOfFlight => "infeasible_time_of_flight",
+            LambertFailure::NonConvergence => "non_convergence",
+            LambertFailure::NumericalFailure => "numerical_failure",
+        }
+    }
+}
+
+/// One transfer arc.
+#[derive(Clone, Copy, Debug)]
+pub struct LambertSolution {
+    pub revolutions: u32,
+    pub branch: LambertBranch,
+    pub v1: [f64; 3],
+    pub v2: [f64; 3],
+    /// Izzo's free parameter (x < 1 elliptic, x > 1 hyperbolic)
+    pub x: f64,
+    pub iterations: u32,
+    pub conic: ConicCase,
+    /// Specific orbital energy v₁²/2 − μ/r₁ [m²/s²]
+    pub energy: f64,
+}
+
+/// Diagnostics mirroring gnc-core's `LambertDiagnostics`.
+/// TOF bounds are for the requested revolution count: a zero-revolution
+/// transfer exists for any Δt > 0, an N-revolution one for Δt ≥ Δt_min(N).
+/// There is no upper bound, so `max_feasible_tof` is always None here.
+#[derive(Clone, Copy, Debug)]
+pub struct LambertDiagnostics {
+    pub requested_tof: f64,
+    pub min_feasible_tof: Option<f64>,
+    pub max_feasible_tof: Option<f64>,
+    pub transfer_angle: Option<f64>,
+    pub prograde: bool,
+    pub failure: Option<LambertFailure>,
+}
+
+/// All solutions up to the requested revolution count, ordered by N then
+/// left before right.
+#[derive(Clone, Debug)]
+pub struct LambertSolutions {
+    pub solutions: Vec<LambertSolution>,
+    pub diagnostics: LambertDiagnostics,
+}
+
+impl LambertSolutions {
+    pub fn find(&self, revolutions: u32, branch: LambertBranch) -> Option<&LambertSolution> {
+        self.solutions.iter().find(|s| s.revolutions == revolutions && s.branch == branch)
+    }
+}
+
+fn norm(v: &[f64]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+fn unit(v: &[f64]) -> [f64; 3] {
+    let n = norm(v);
+    [v[0] / n, v[1] / n, v[2] / n]
+}
+
+/// Battin's hypergeometric series ₂F₁(3, 1; 5/2; z).
+fn hypergeometric_f(z: f64, tol: f64) -> f64 {
+    let (mut sj, mut cj) = (1.0, 1.0);
+    for j in 0..1000 {
+        let j = j as f64;
+        cj *= (3.0 + j) * (1.0 + j) / (2.5 + j) * z / (j + 1.0);
+        sj += cj;
+        if cj.abs() < tol {
+            break;
+        }
+    }
+    sj
+}
+
+/// Non-dimensional time of flight T(x) for N revolutions.
+fn x_to_tof(x: f64, n: u32, lambda: f64) -> f64 {
+    const BATTIN: f64 = 0.01;
+    const LAGRANGE: f64 = 0.2;
+    let nf = n as f64;
+    let dist = (x - 1.0).abs();
+    if dist < LAGRANGE && dist > BATTIN {
+        let a = 1.0 / (1.0 - x * x);
+        return if a > 0.0 {
+            let alfa = 2.0 * x.acos();
+            let beta = (2.0 * (lambda * lambda / a).sqrt().asin()).copysign(lambda);
+            a * a.sqrt() * ((alfa - alfa.sin()) - (beta - beta.sin()) + TAU * nf) / 2.0
+        } else {
+            let alfa = 2.0 * x.acosh();
+            let beta = (2.0 * (-lambda * lambda / a).sqrt().asinh()).copysign(lambda);
+            -a * (-a).sqrt() * ((beta - beta.sinh()) - (alfa - alfa.sinh())) / 2.0
+        };
+    }
+    let e = x * x - 1.0;
+    let rho = e.abs();
+    let z = (1.0 + lambda * lambda * e).sqrt();
+    if dist < BATTIN {
+        let eta = z - lambda * x;
+        let s1 = 0.5 * (1.0 - lambda - x * eta);
+        let q = 4.0 / 3.0 * hypergeometric_f(s1, 1e-11);
+        (eta * eta * eta * q + 4.0 * lambda * eta) / 2.0 + nf * PI / rho.powf(1.5)
+    } else {
+        let y = rho.sqrt();
+        let g = x * z - lambda * e;
+        let d = if e < 0.0 { nf * PI + g.acos() } else { (y * (z - lambda * x) + g).ln() };
+        (x - lambda * z - d / y) / e
+    }
+}
+
+/// First three derivatives of T(x).
+fn tof_derivatives(x: f64, t: f64, lambda: f64) -> (f64, f64, f64) {
+    let l2 = lambda * lambda;
+    let l3 = l2 * lambda;
+    let umx2 = 1.0 - x * x;
+    let y = (1.0 - l2 * umx2).sqrt();
+    let (y2, y3) = (y * y, y * y * y);
+    let d1 = (3.0 * t * x - 2.0 + 2.0 * l3 * x / y) / umx2;
+    let d2 = (3.0 * t + 5.0 * x * d1 + 2.0 * (1.0 - l2) * l3 / y3) / umx2;
+    let d3 = (7.0 * x * d2 + 8.0 * d1 - 6.0 * (1.0 - l2) * l2 * l3 * x / y3 / y2) / umx2;
+    (d1, d2, d3)
+}
+
+/// Householder iteration on T(x) = T. Returns (x, iterations) or None.
+fn householder(t: f64, mut x: f64, n: u32, lambda: f64) -> Option<(f64, u32)> {
+    for it in 1..=30 {
+        let tof = x_to_tof(x, n, lambda);
+        let (d1, d2, d3) = tof_derivatives(x, tof, lambda);
+        let delta = tof - t;
+        let d1s = d1 * d1;
+        let xn = x - delta * (d1s - delta * d2 / 2.0) / (d1 * (d1s - delta * d2) + d3 * delta * delta / 6.0);
+        if !xn.is_finite() {
+            return None;
+        }
+        let err = (x - xn).abs();
+        x = xn;
+        if err < 1e-11 {
+            return Some((x, it));
+        }
+    }
+    None
+}
+
+/// Minimum non-dimensional TOF of the N-revolution family (Halley on dT/dx = 0).
+fn tof_minimum(n: u32, lambda: f64) -> f64 {
+    let mut x = 0.0;
+    let mut t = x_to_tof(x, n, lambda);
+    for _ in 0..12 {
+        let (d1, d2, d3) = tof_derivatives(x, t, lambda);
+        if d1 == 0.0 {
+            break;
+        }
+        let xn = x - d1 * d2 / (d2 * d2 - d1 * d3 / 2.0);
+        let err = (x - xn).abs();
+        x = xn;
+        t = x_to_tof(x, n, lambda);
+        if err < 1e-13 {
+            break;
+        }
+    }
+    t
+}
+
+/// Solve Lambert's problem with Izzo's algorithm for 0..=max_revs revolutions.
+///
+/// Each N ≥ 1 contributes a left and a right branch when Δt ≥ Δt_min(N).
+/// When fewer revolutions are feasible than requested, the diagnostics carry
+/// the shortest Δt for which all `max_revs` families exist.
+pub fn lambert_izzo(r1: &[f64; 3], r2: &[f64; 3], tof: f64, mu: f64, prograde: bool, max_revs: u32) -> LambertSolutions {
+    let mut diagnostics = LambertDiagnostics {
+        requested_tof: tof,
+        min_feasible_tof: None,
+        max_feasible_tof: None,
+        transfer_angle: None,
+        prograde,
+        failure: None,
+    };
+    let fail = |mut diagnostics: LambertDiagnostics, reason| {
+        diagnostics.failure = Some(reason);
+        LambertSolutions { solutions: Vec::new(), diagnostics }
+    };
+
+    let finite = r1.iter().chain(r2).all(|c| c.is_finite()) && tof.is_finite() && mu.is_finite();
+    let (r1n, r2n) = (norm(r1), norm(r2));
+    if !finite || tof <= 0.0 || mu <= 0.0 || r1n == 0.0 || r2n == 0.0 {
+        return fail(diagnostics, LambertFailure::InvalidInput);
+    }
+
+    let c = [r2[0] - r1[0], r2[1] - r1[1], r2[2] - r1[2]];
+    let cn = norm(&c);
+    let s = (cn + r1n + r2n) / 2.0;
+    let (ir1, ir2) = (unit(r1), unit(r2));
+    let h = cross(&ir1, &ir2);
+    if norm(&h) < 1e-10 {
+        return fail(diagnostics, LambertFailure::DegenerateGeometry);
+    }
+    let ih = unit(&h);
+
+    // Short way when the geometric normal agrees with the requested sense
+    let short_way = (ih[2] >= 0.0) == prograde;
+    let mut lambda = (1.0 - cn / s).max(0.0).sqrt();
+    let (mut it1, mut it2) = (cross(&ih, &ir1), cross(&ih, &ir2));
+    if !short_way {
+        lambda = -lambda;
+        it1 = it1.map(|v| -v);
+        it2 = it2.map(|v| -v);
+    }
+    let theta = (ir1[0] * ir2[0] + ir1[1] * ir2[1] + ir1[2] * ir2[2]).clamp(-1.0, 1.0).acos();
+    diagnostics.transfer_angle = Some(if short_way { theta } else { TAU - theta });
+
+    let scale = (2.0 * mu / (s * s * s)).sqrt();
+    let t = tof * scale;
+
+    diagnostics.min_feasible_tof = Some(if max_revs == 0 { 0.0 } else { tof_minimum(max_revs, lambda) / scale });
+
+    // Highest N whose family reaches down to T
+    let t00 = lambda.acos() + lambda * (1.0 - lambda * lambda).sqrt();
+    let mut n_max = ((t / PI).floor() as u32).min(max_revs);
+    if n_max > 0 && t < t00 + n_max as f64 * PI && tof_minimum(n_max, lambda) > t {
+        n_max -= 1;
+    }
+
+    let mut candidates = Vec::with_capacity(2 * n_max as usize + 1);
+    let t1 = 2.0 / 3.0 * (1.0 - lambda * lambda * lambda);
+    let x0 = if t >= t00 {
+        -(t - t00) / (t - t00 + 4.0)
+    } else if t <= t1 {
+        t1 * (t1 - t) / (0.4 * (1.0 - lambda.powi(5)) * t) + 1.0
+    } else {
+        (t / t00).powf(std::f64::consts::LN_2 / (t1 / t00).ln()) - 1.0
+    };
+    candidates.push((0, LambertBranch::Single, householder(t, x0, 0, lambda)));
+    for n in 1..=n_max {
+        let nf = n as f64;
+        let tmp = ((nf * PI + PI) / (8.0 * t)).powf(2.0 / 3.0);
+        candidates.push((n, LambertBranch::Left, householder(t, (tmp - 1.0) / (tmp + 1.0), n, lambda)));
+        let tmp = (8.0 * t / (nf * PI)).powf(2.0 / 3.0);
+        candidates.push((n, LambertBranch::Right, householder(t, (tmp - 1.0) / (tmp + 1.0), n, lambda)));
+    }
+
+    // Velocities from x (Izzo eq. 16)
+    let gamma = (mu * s / 2.0).sqrt();
+    let rho = (r1n - r2n) / cn;
+    let sigma = (1.0 - rho * rho).sqrt();
+    let mut solutions = Vec::with_capacity(candidates.len());
+    let mut failure = None;
+    for (revolutions, branch, found) in candidates {
+        let Some((x, iterations)) = found else {
+            failure.get_or_insert(LambertFailure::NonConvergence);
+            continue;
+        };
+        let y = (1.0 - lambda * lambda + lambda * lambda * x * x).sqrt();
+        let vr1 = gamma * ((lambda * y - x) - rho * (lambda * y + x)) / r1n;
+        let vr2 = -gamma * ((lambda * y - x) + rho * (lambda * y + x)) / r2n;
+        let vt = gamma * sigma * (y + lambda * x);
+        let v1: [f64; 3] = core::array::from_fn(|i| vr1 * ir1[i] + vt / r1n * it1[i]);
+        let v2: [f64; 3] = core::array::from_fn(|i| vr2 * ir2[i] + vt / r2n * it2[i]);
+        if !v1.iter().chain(&v2).all(|v| v.is_finite()) {
+            failure.get_or_insert(LambertFailure::NumericalFailure);
+            continue;
+        }
+        let energy = 0.5 * (v1[0] * v1[0] + v1[1] * v1[1] + v1[2] * v1[2]) - mu / r1n;
+        let conic = if energy.abs() <= 1e-9 * mu / r1n {
+            ConicCase::Parabolic
+        } else if energy < 0.0 {
+            ConicCase::Elliptic
+        } else {
+            ConicCase::Hyperbolic
+        };
+        solutions.push(LambertSolution { revolutions, branch, v1, v2, x, iterations, conic, energy });
+    }
+    // A branch failing is only fatal when nothing at all was found
+    if solutions.is_empty() {
+        diagnostics.failure = failure;
+    }
+    LambertSolutions { solutions, diagnostics }
+}
+
+/// Single Lambert solution with diagnostics (mirrors gnc-core's `LambertResult`).
+#[wasm_bindgen]
+pub struct LambertResult {
+    solution: Option<LambertSolution>,
+    diagnostics: LambertDiagnostics,
+}
+
+#[wasm_bindgen]
+impl LambertResult {
+    #[wasm_bindgen(getter)]
+    pub fn success(&self) -> bool { self.solution.is_some() }
+
+    /// Departure velocity [m/s] (NaN on failure)
+    #[wasm_bindgen(getter)]
+    pub fn v1(&self) -> Float64Array {
+        Float64Array::from(&self.solution.map_or([f64::NAN; 3], |s| s.v1)[..])
+    }
+
+    /// Arrival velocity [m/s] (NaN on failure)
+    #[wasm_bindgen(getter)]
+    pub fn v2(&self) -> Float64Array {
+        Float64Array::from(&self.solution.map_or([f64::NAN; 3], |s| s.v2)[..])
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn iterations(&self) -> u32 { self.solution.map_or(0, |s| s.iterations) }
+
+    /// "elliptic" | "parabolic" | "hyperbolic" | "unknown"
+    #[wasm_bindgen(getter)]
+    pub fn conic_case(&self) -> String {
+        self.solution.map_or("unknown", |s| s.conic.as_str()).to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn requested_tof(&self) -> f64 { self.diagnostics.requested_tof }
+
+    #[wasm_bindgen(getter)]
+    pub fn min_feasible_tof(&self) -> Option<f64> { self.diagnostics.min_feasible_tof }
+
+    #[wasm_bindgen(getter)]
+    pub fn max_feasible_tof(&self) -> Option<f64> { self.diagnostics.max_feasible_tof }
+
+    #[wasm_bindgen(getter)]
+    pub fn transfer_angle_rad(&self) -> Option<f64> { self.diagnostics.transfer_angle }
+
+    /// "prograde" | "retrograde"
+    #[wasm_bindgen(getter)]
+    pub fn transfer_direction(&self) -> String {
+        if self.diagnostics.prograde { "prograde" } else { "retrograde" }.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn energy_at_departure(&self) -> Option<f64> { self.solution.map(|s| s.energy) }
+
+    /// gnc-core `LambertFailureReason` string, or undefined on success
+    #[wasm_bindgen(getter)]
+    pub fn failure_reason(&self) -> Option<String> {
+        self.diagnostics.failure.map(|f| f.as_str().to_string())
+    }
+}
+
+fn vec3_from_js(v: &Float64Array) -> Option<[f64; 3]> {
+    (v.length() == 3).then(|| {
+        let v = v.to_vec();
+        [v[0], v[1], v[2]]
+    })
+}
+
+/// Solve for one (revolutions, branch) transfer. `branch` is ignored when
+/// revolutions = 0. Asking for more revolutions than Δt allows reports
+/// `infeasible_time_of_flight` with the minimum Δt in the diagnostics.
+#[wasm_bindgen]
+pub fn lambert_solve(
+    r1_js: &Float64Array,
+    r2_js: &Float64Array,
+    tof: f64,
+    mu: f64,
+    prograde: bool,
+    revolutions: u32,
+    branch: LambertBranch,
+) -> LambertResult {
+    let (Some(r1), Some(r2)) = (vec3_from_js(r1_js), vec3_from_js(r2_js)) else {
+        // Zero radii are rejected as invalid input
+        let diagnostics = lambert_izzo(&[0.0; 3], &[0.0; 3], tof, mu, prograde, 0).diagnostics;
+        return LambertResult { solution: None, diagnostics };
+    };
+    let mut result = lambert_izzo(&r1, &r2, tof, mu, prograde, revolutions);
+    let branch = if revolutions == 0 { LambertBranch::Single } else { branch };
+    let solution = result.find(revolutions, branch).copied();
+    if solution.is_none() && result.diagnostics.failure.is_none() {
+        let in_family = result.solutions.iter().all(|s| s.revolutions < revolutions);
+        result.diagnostics.failure = Some(if in_family {
+            LambertFailure::InfeasibleTimeOfFlight
+        } else {
+            LambertFailure::NonConvergence
+        });
+    }
+    LambertResult { solution, diagnostics: result.diagnostics }
+}
+
+/// Every transfer up to `max_revs` revolutions as flat rows
+/// [N, branch, v1x, v1y, v1z, v2x, v2y, v2z]. Empty when unsolvable.
+#[wasm_bindgen]
+pub fn lambert_solutions(
+    r1_js: &Float64Array,
+    r2_js: &Float64Array,
+    tof: f64,
+    mu: f64,
+    prograde: bool,
+    max_revs: u32,
+) -> Result<Float64Array, JsValue> {
+    let (Some(r1), Some(r2)) = (vec3_from_js(r1_js), vec3_from_js(r2_js)) else {
+        return Err(JsValue::from_str("r1 and r2 must have length 3"));
+    };
+    let result = lambert_izzo(&r1, &r2, tof, mu, prograde, max_revs);
+    let mut buf = Vec::with_capacity(8 * result.solutions.len());
+    for s in &result.solutions {
+        buf.extend_from_slice(&[s.revolutions as f64, s.branch as u32 as f64]);
+        buf.extend_from_slice(&s.v1);
+        buf.extend_from_slice(&s.v2);
+    }
+    Ok(Float64Array::from(buf.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{MU_EARTH, MU_SUN, AU};
+    use crate::kepler::kepler_propagate_state;
+
+    fn arrives(r1: &[f64; 3], r2: &[f64; 3], sol: &LambertSolution, tof: f64, mu: f64) -> f64 {
+        let s0 = [r1[0], r1[1], r1[2], sol.v1[0], sol.v1[1], sol.v1[2]];
+        let s = kepler_propagate_state(&s0, tof, mu).unwrap();
+        (0..3).map(|i| (s[i] - r2[i]).abs().max((s[i + 3] - sol.v2[i]).abs() * 1e3)).fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn matches_curtis_example() {
+        // Curtis Example 5.2 (μ = 398600 km³/s²)
+        let mu = 398600e9;
+        let r1 = [5000e3, 10000e3, 2100e3];
+        let r2 = [-14600e3, 2500e3, 7000e3];
+        let res = lambert_izzo(&r1, &r2, 3600.0, mu, true, 0);
+        let sol = res.find(0, LambertBranch::Single).unwrap();
+        let want1 = [-5.9925e3, 1.9254e3, 3.2456e3];
+        let want2 = [-3.3125e3, -4.1966e3, -0.38529e3];
+        for i in 0..3 {
+            assert!((sol.v1[i] - want1[i]).abs() < 0.1, "v1[{}] {}", i, sol.v1[i]);
+            assert!((sol.v2[i] - want2[i]).abs() < 0.1, "v2[{}] {}", i, sol.v2[i]);
+        }
+        assert_eq!(sol.conic, ConicCase::Elliptic);
+        assert!(sol.iterations <= 5);
+        assert!(arrives(&r1, &r2, sol, 3600.0, mu) < 1e-3);
+
+        // Retrograde goes the long way round and still arrives
+        let retro = lambert_izzo(&r1, &r2, 3600.0, mu, false, 0);
+        let theta = res.diagnostics.transfer_angle.unwrap();
+        assert!((retro.diagnostics.transfer_angle.unwrap() - (TAU - theta)).abs() < 1e-12);
+        assert!(arrives(&r1, &r2, &retro.solutions[0], 3600.0, mu) < 1e-3);
+    }
+
+    #[test]
+    fn multi_revolution_branches_all_arrive() {
+        let r1 = [AU, 0.0, 0.0];
+        let r2 = [-0.3 * AU, 1.2 * AU, 0.05 * AU];
+        let tof = 3.5 * 365.25 * 86400.0;
+        let res = lambert_izzo(&r1, &r2, tof, MU_SUN, true, 5);
+        assert!(res.diagnostics.failure.is_none());
+        let n_max = res.solutions.iter().map(|s| s.revolutions).max().unwrap();
+        assert!((1..5).contains(&n_max), "n_max = {}", n_max);
+        assert_eq!(res.solutions.len(), 2 * n_max as usize + 1);
+        for sol in &res.solutions {
+            assert!(arrives(&r1, &r2, sol, tof, MU_SUN) < 10.0, "N={} {:?}", sol.revolutions, sol.branch);
+        }
+        let (l, r) = (res.find(1, LambertBranch::Left).unwrap(), res.find(1, LambertBranch::Right).unwrap());
+        assert!((l.x - r.x).abs() > 1e-3);
+
+        // The reported minimum splits feasible from infeasible N = n_max + 1
+        let tmin = lambert_izzo(&r1, &r2, tof, MU_SUN, true, n_max + 1).diagnostics.min_feasible_tof.unwrap();
+        assert!(tmin > tof);
+        let below = lambert_izzo(&r1, &r2, 0.999 * tmin, MU_SUN, true, n_max + 1);
+        let above = lambert_izzo(&r1, &r2, 1.001 * tmin, MU_SUN, true, n_max + 1);
+        assert!(below.find(n_max + 1, LambertBranch::Left).is_none());
+        for b in [LambertBranch::Left, LambertBranch::Right] {
+            assert!(arrives(&r1, &r2, above.find(n_max + 1, b).unwrap(), 1.001 * tmin, MU_SUN) < 10.0);
+        }
+    }
+
+    #[test]
+    fn reports_failures_and_hyperbolic_arcs() {
+        let r1 = [7.0e6, 0.0, 0.0];
+        let r2 = [0.0, 8.0e6, 1.0e6];
+        assert_eq!(lambert_izzo(&r1, &r2, -10.0, MU_EARTH, true, 0).diagnostics.failure, Some(LambertFailure::InvalidInput));
+        let opposite = lambert_izzo(&r1, &[-8.0e6, 0.0, 0.0], 3000.0, MU_EARTH, true, 0);
+        assert_eq!(opposite.diagnostics.failure, Some(LambertFailure::DegenerateGeometry));
+        assert!(opposite.solutions.is_empty());
+
+        let fast = lambert_izzo(&r1, &r2, 300.0, MU_EARTH, true, 2);
+        assert_eq!(fast.solutions.len(), 1);
+        assert_eq!(fast.solutions[0].conic, ConicCase::Hyperbolic);
+        assert!(arrives(&r1, &r2, &fast.solutions[0], 300.0, MU_EARTH) < 1e-3);
+        assert!(fast.diagnostics.min_feasible_tof.unwrap() > 300.0);
+    }
+}
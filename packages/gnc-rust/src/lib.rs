@@ -1,7 +1,52 @@
-use wasm_bindgen::prelude::*;
-
 mod sssp;
+mod bmssp;
+mod partition;
+mod point_to_point;
+mod contraction;
 mod rk4;
+mod rk45;
+mod dop853;
+mod symplectic;
+mod events;
+mod stm;
+mod forces;
+mod constants;
+mod zonal;
+mod harmonics;
+mod drag;
+mod srp;
+mod ephemeris;
+mod spk;
+mod time;
+mod frames;
+mod local_frames;
+mod geodetic;
+mod elements;
+mod kepler;
+mod lambert;
+mod porkchop;
 
 pub use sssp::*;
+pub use partition::*;
 pub use rk4::*;
+pub use rk45::*;
+pub use dop853::*;
+pub use symplectic::*;
+pub use events::*;
+pub use stm::*;
+pub use forces::*;
+pub use constants::*;
+pub use zonal::*;
+pub use harmonics::*;
+pub use drag::*;
+pub use srp::*;
+pub use ephemeris::*;
+pub use spk::*;
+pub use time::*;
+pub use frames::*;
+pub use local_frames::*;
+pub use geodetic::*;
+pub use elements::*;
+pub use kepler::*;
+pub use lambert::*;
+pub use porkchop::*;
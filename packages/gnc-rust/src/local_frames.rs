@@ -0,0 +1,250 @@
+//! ID: WASM-LOF-001
+//! Requirement: Build the RIC (RSW), LVLH, VNB and NTW local orbital frames of a
+//!   chief spacecraft and convert deputy states between inertial and relative form.
+//! Purpose: Rendezvous, proximity operations and maneuver planning express
+//!   offsets, Δv and covariance along the chief's radial / along-track / normal axes.
+//! Rationale: Each frame is a rotation Q whose rows are unit vectors built from
+//!   r, v and h = r × v; differentiating those with ṙ = v, v̇ = a, ḣ = r × a gives
+//!   Q̇ = −[ω×]Q exactly, so ρ̇ = Q δv + Q̇ δr carries the ω × ρ transport term for
+//!   any chief orbit, not only circular ones.
+//! Inputs: chief inertial State6 [m, m/s] and acceleration [m/s²] (or μ for a
+//!   Keplerian chief); deputy inertial or relative state
+//! Outputs: Q and Q̇ (row-major, inertial → local); relative or inertial states
+//! References: Vallado §3.3 (RSW, NTW); Fehse "Automated Rendezvous and Docking"
+//!   §2.1 (LVLH); CCSDS 500.2-G (local orbital frames)
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::frames::{cross, mat_vec, transpose};
+use crate::rk4::State6;
+use crate::rk45::state6_from_js;
+use crate::stm::Mat3;
+
+/// Local orbital frame conventions (axis order x, y, z).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalFrame {
+    /// Radial, in-track, cross-track (Vallado's RSW)
+    Ric = 0,
+    /// x ≈ velocity, y = −orbit normal, z = nadir
+    Lvlh = 1,
+    /// Velocity, orbit normal, binormal
+    Vnb = 2,
+    /// In-plane normal to velocity, tangential, orbit normal
+    Ntw = 3,
+}
+
+/// Unit vector of u and its time derivative given u̇.
+fn unit_rate(u: &[f64; 3], du: &[f64; 3]) -> ([f64; 3], [f64; 3]) {
+    let n = (u[0] * u[0] + u[1] * u[1] + u[2] * u[2]).sqrt();
+    let e: [f64; 3] = core::array::from_fn(|i| u[i] / n);
+    let along = e[0] * du[0] + e[1] * du[1] + e[2] * du[2];
+    (e, core::array::from_fn(|i| (du[i] - e[i] * along) / n))
+}
+
+/// a × b and its derivative.
+fn cross_rate(a: &([f64; 3], [f64; 3]), b: &([f64; 3], [f64; 3])) -> ([f64; 3], [f64; 3]) {
+    let (x, y) = (cross(&a.1, &b.0), cross(&a.0, &b.1));
+    (cross(&a.0, &b.0), core::array::from_fn(|i| x[i] + y[i]))
+}
+
+fn neg(u: &([f64; 3], [f64; 3])) -> ([f64; 3], [f64; 3]) {
+    (u.0.map(|x| -x), u.1.map(|x| -x))
+}
+
+/// A chief's local frame at one instant: ρ = Q δr, ρ̇ = Q δv + Q̇ δr.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalOrbitalFrame {
+    /// Rows are the local axes in inertial coordinates
+    pub q: Mat3,
+    /// Time derivative of q
+    pub q_dot: Mat3,
+    /// Chief state the frame was built from
+    pub chief: State6,
+}
+
+impl LocalOrbitalFrame {
+    /// Frame of a chief with inertial state `chief` and acceleration `accel`.
+    pub fn new(kind: LocalFrame, chief: &State6, accel: &[f64; 3]) -> Self {
+        let r = [chief[0], chief[1], chief[2]];
+        let v = [chief[3], chief[4], chief[5]];
+        let r_hat = unit_rate(&r, &v);
+        let v_hat = unit_rate(&v, accel);
+        let h_hat = unit_rate(&cross(&r, &v), &cross(&r, accel));
+
+        let rows = match kind {
+            LocalFrame::Ric => [r_hat, cross_rate(&h_hat, &r_hat), h_hat],
+            LocalFrame::Lvlh => {
+                let (y, z) = (neg(&h_hat), neg(&r_hat));
+                [cross_rate(&y, &z), y, z]
+            }
+            LocalFrame::Vnb => [v_hat, h_hat, cross_rate(&v_hat, &h_hat)],
+            LocalFrame::Ntw => [cross_rate(&v_hat, &h_hat), v_hat, h_hat],
+        };
+        LocalOrbitalFrame {
+            q: [rows[0].0, rows[1].0, rows[2].0],
+            q_dot: [rows[0].1, rows[1].1, rows[2].1],
+            chief: *chief,
+        }
+    }
+
+    /// Frame of a chief in Keplerian motion about a body with parameter `mu`.
+    pub fn keplerian(kind: LocalFrame, chief: &State6, mu: f64) -> Self {
+        let r = (chief[0] * chief[0] + chief[1] * chief[1] + chief[2] * chief[2]).sqrt();
+        let k = -mu / (r * r * r);
+        Self::new(kind, chief, &[k * chief[0], k * chief[1], k * chief[2]])
+    }
+
+    /// Angular velocity of the frame relative to inertial space, in local axes.
+    pub fn angular_velocity(&self) -> [f64; 3] {
+        // Q̇ Qᵀ = −[ω×]
+        let qt = transpose(&self.q);
+        let s = |i: usize, j: usize| -> f64 { (0..3).map(|k| self.q_dot[i][k] * qt[k][j]).sum() };
+        [s(1, 2), s(2, 0), s(0, 1)]
+    }
+
+    /// Deputy inertial state → relative state in this frame.
+    pub fn to_relative(&self, deputy: &State6) -> State6 {
+        let dr: [f64; 3] = core::array::from_fn(|i| deputy[i] - self.chief[i]);
+        let dv: [f64; 3] = core::array::from_fn(|i| deputy[i + 3] - self.chief[i + 3]);
+        let rho = mat_vec(&self.q, &dr);
+        let (a, b) = (mat_vec(&self.q, &dv), mat_vec(&self.q_dot, &dr));
+        [rho[0], rho[1], rho[2], a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+    }
+
+    /// Relative state in this frame → deputy inertial state.
+    pub fn to_inertial(&self, relative: &State6) -> State6 {
+        let qt = transpose(&self.q);
+        let dr = mat_vec(&qt, &relative[..3]);
+        let b = mat_vec(&self.q_dot, &dr);
+        let dv = mat_vec(&qt, &[relative[3] - b[0], relative[4] - b[1], relative[5] - b[2]]);
+        core::array::from_fn(|i| if i < 3 { self.chief[i] + dr[i] } else { self.chief[i] + dv[i - 3] })
+    }
+}
+
+fn pairs_from_js(a_js: &Float64Array, b_js: &Float64Array) -> Result<(Vec<f64>, Vec<f64>), JsValue> {
+    let (a, b) = (a_js.to_vec(), b_js.to_vec());
+    if a.is_empty() || !a.len().is_multiple_of(6) || a.len() != b.len() {
+        return Err(JsValue::from_str("chief and deputy buffers must be equal-length multiples of 6"));
+    }
+    Ok((a, b))
+}
+
+fn map_pairs(
+    chief_js: &Float64Array,
+    other_js: &Float64Array,
+    kind: LocalFrame,
+    mu: f64,
+    f: impl Fn(&LocalOrbitalFrame, &State6) -> State6,
+) -> Result<Float64Array, JsValue> {
+    let (chief, other) = pairs_from_js(chief_js, other_js)?;
+    let mut out = Vec::with_capacity(chief.len());
+    for (c, o) in chief.chunks_exact(6).zip(other.chunks_exact(6)) {
+        let frame = LocalOrbitalFrame::keplerian(kind, &[c[0], c[1], c[2], c[3], c[4], c[5]], mu);
+        out.extend_from_slice(&f(&frame, &[o[0], o[1], o[2], o[3], o[4], o[5]]));
+    }
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+/// Relative states of a deputy in the chief's local frame. Both buffers are flat
+/// [rx,ry,rz,vx,vy,vz, …] with one row per instant (e.g. two rk4_trajectory
+/// outputs); the chief is assumed Keplerian about `mu` for the frame rate.
+#[wasm_bindgen]
+pub fn to_relative_states(
+    chief_js: &Float64Array,
+    deputy_js: &Float64Array,
+    kind: LocalFrame,
+    mu: f64,
+) -> Result<Float64Array, JsValue> {
+    map_pairs(chief_js, deputy_js, kind, mu, |f, d| f.to_relative(d))
+}
+
+/// Inverse of to_relative_states: deputy inertial states from relative ones.
+#[wasm_bindgen]
+pub fn from_relative_states(
+    chief_js: &Float64Array,
+    relative_js: &Float64Array,
+    kind: LocalFrame,
+    mu: f64,
+) -> Result<Float64Array, JsValue> {
+    map_pairs(chief_js, relative_js, kind, mu, |f, r| f.to_inertial(r))
+}
+
+/// Q then Q̇ (inertial → local), row-major, 18 values.
+#[wasm_bindgen]
+pub fn local_frame_matrices(chief_js: &Float64Array, kind: LocalFrame, mu: f64) -> Result<Float64Array, JsValue> {
+    let c = state6_from_js(chief_js)?;
+    let frame = LocalOrbitalFrame::keplerian(kind, &[c[0], c[1], c[2], c[3], c[4], c[5]], mu);
+    let mut out = frame.q.concat();
+    out.extend_from_slice(&frame.q_dot.concat());
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MU_EARTH;
+    use crate::forces::{PointMass, SpacecraftParams};
+    use crate::rk4::rk4_step_model;
+
+    const ALL: [LocalFrame; 4] = [LocalFrame::Ric, LocalFrame::Lvlh, LocalFrame::Vnb, LocalFrame::Ntw];
+
+    fn circular(radius: f64, angle: f64) -> State6 {
+        let v = (MU_EARTH / radius).sqrt();
+        let (s, c) = angle.sin_cos();
+        [radius * c, radius * s, 0.0, -v * s, v * c, 0.0]
+    }
+
+    #[test]
+    fn co_orbiting_deputy_is_stationary() {
+        let (radius, lead) = (7.0e6, 1e-3);
+        let chief = circular(radius, 0.3);
+        let deputy = circular(radius, 0.3 + lead);
+        let rel = LocalOrbitalFrame::keplerian(LocalFrame::Ric, &chief, MU_EARTH).to_relative(&deputy);
+        assert!((rel[0] - radius * (lead.cos() - 1.0)).abs() < 1e-6);
+        assert!((rel[1] - radius * lead.sin()).abs() < 1e-6);
+        // Without the ω × ρ term this would be ~7.5 m/s
+        for &x in &rel[2..] {
+            assert!(x.abs() < 1e-9, "{:?}", rel);
+        }
+        // LVLH is RIC with x = in-track, y = −cross, z = −radial for circular orbits
+        let lvlh = LocalOrbitalFrame::keplerian(LocalFrame::Lvlh, &chief, MU_EARTH).to_relative(&deputy);
+        assert!((lvlh[0] - rel[1]).abs() < 1e-6 && (lvlh[2] + rel[0]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn frame_rates_match_finite_difference_and_invert() {
+        // Eccentric, inclined chief with a deputy a few km away
+        let chief = [6.8e6, 1.2e6, -0.4e6, -1.1e3, 7.9e3, 2.3e3];
+        let deputy = [6.803e6, 1.199e6, -0.398e6, -1.102e3, 7.896e3, 2.305e3];
+        let pm = PointMass { mu: MU_EARTH };
+        let params = SpacecraftParams::default();
+        let h = 0.5;
+        let (ahead, behind) = (rk4_step_model(&pm, &params, 0.0, &chief, h), rk4_step_model(&pm, &params, 0.0, &chief, -h));
+        for kind in ALL {
+            let f = LocalOrbitalFrame::keplerian(kind, &chief, MU_EARTH);
+            let (fa, fb) = (LocalOrbitalFrame::keplerian(kind, &ahead, MU_EARTH), LocalOrbitalFrame::keplerian(kind, &behind, MU_EARTH));
+            for i in 0..3 {
+                for j in 0..3 {
+                    let fd = (fa.q[i][j] - fb.q[i][j]) / (2.0 * h);
+                    assert!((fd - f.q_dot[i][j]).abs() < 1e-10, "{:?} [{}][{}]", kind, i, j);
+                }
+            }
+            let back = f.to_inertial(&f.to_relative(&deputy));
+            for i in 0..6 {
+                assert!((back[i] - deputy[i]).abs() < 1e-8 * deputy[i].abs().max(1.0));
+            }
+            // In-plane frames rotate about the orbit normal only (Keplerian chief)
+            let w = f.angular_velocity();
+            let n = (w[0] * w[0] + w[1] * w[1] + w[2] * w[2]).sqrt();
+            let normal = match kind {
+                LocalFrame::Ric => w[2],
+                LocalFrame::Lvlh => -w[1],
+                LocalFrame::Vnb => w[1],
+                LocalFrame::Ntw => w[2],
+            };
+            assert!((normal - n).abs() < 1e-12 * n.max(1e-3));
+        }
+    }
+}
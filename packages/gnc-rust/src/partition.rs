@@ -0,0 +1,421 @@
+//! ID: WASM-PART-001
+//! Requirement: Partition a SparseGraph into clusters of about √n nodes that
+//!   follow its topology and minimise the number of cut edges, for the
+//!   hierarchical SSSP decomposition.
+//! Purpose: Index chunking ignores topology, so on trajectory graphs almost
+//!   every node becomes a boundary node and the overlay is no smaller than the
+//!   graph itself.
+//! Rationale: BFS region growing is cheap and keeps clusters contiguous. The
+//!   multilevel scheme (as in METIS) contracts heavy-edge matchings until about
+//!   20 vertices per part remain. It partitions that small graph by region
+//!   growing, then projects the result back level by level with Fiduccia–
+//!   Mattheyses refinement. FM tries the highest-gain boundary moves first,
+//!   may take uphill moves, and rolls back to the best cut it saw.
+//! Inputs: SparseGraph (edge directions and weights ignored: the cut counts
+//!   edges), number of parts
+//! Outputs: part index per node (0..parts, no empty parts)
+//! Assumptions/Limits: parts are balanced to within 5 % (plus one coarse
+//!   vertex) by node count; the result is deterministic.
+//! References: Karypis & Kumar (1998) SIAM J. Sci. Comput. 20(1), "A Fast and
+//!   High Quality Multilevel Scheme for Partitioning Irregular Graphs";
+//!   Fiduccia & Mattheyses (1982) DAC; Kernighan & Lin (1970) Bell Syst. Tech. J. 49
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use wasm_bindgen::prelude::*;
+
+use crate::sssp::SparseGraph;
+
+/// How preprocess() clusters the graph.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Partitioner {
+    /// Consecutive node indices (topology-blind)
+    Index = 0,
+    /// BFS region growing
+    Bfs = 1,
+    /// Multilevel coarsening, region-growing initial partition, FM refinement
+    Multilevel = 2,
+}
+
+/// Allowed part-weight excess over the average.
+const IMBALANCE: f64 = 0.05;
+/// Coarsening stops at about this many vertices per part.
+const COARSEST_PER_PART: usize = 20;
+
+/// Undirected graph with integer vertex and edge weights.
+struct WeightedGraph {
+    offsets: Vec<usize>,
+    adjacency: Vec<usize>,
+    edge_weights: Vec<u64>,
+    vertex_weights: Vec<u64>,
+}
+
+impl WeightedGraph {
+    fn len(&self) -> usize {
+        self.vertex_weights.len()
+    }
+
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        let range = self.offsets[v]..self.offsets[v + 1];
+        self.adjacency[range.clone()].iter().copied().zip(self.edge_weights[range].iter().copied())
+    }
+
+    /// Build from (u, v, w) pairs, symmetrising, merging duplicates and dropping loops.
+    fn from_pairs(vertex_weights: Vec<u64>, pairs: impl Iterator<Item = (usize, usize, u64)>) -> Self {
+        let mut entries: Vec<(usize, usize, u64)> = pairs
+            .filter(|&(u, v, _)| u != v)
+            .flat_map(|(u, v, w)| [(u, v, w), (v, u, w)])
+            .collect();
+        entries.sort_unstable_by_key(|&(u, v, _)| (u, v));
+        let n = vertex_weights.len();
+        let mut offsets = vec![0; n + 1];
+        let mut adjacency = Vec::with_capacity(entries.len());
+        let mut edge_weights: Vec<u64> = Vec::with_capacity(entries.len());
+        for (k, &(u, v, w)) in entries.iter().enumerate() {
+            if k > 0 && entries[k - 1].0 == u && entries[k - 1].1 == v {
+                *edge_weights.last_mut().unwrap() += w;
+            } else {
+                adjacency.push(v);
+                edge_weights.push(w);
+                offsets[u + 1] += 1;
+            }
+        }
+        for v in 0..n {
+            offsets[v + 1] += offsets[v];
+        }
+        WeightedGraph { offsets, adjacency, edge_weights, vertex_weights }
+    }
+
+    fn from_sparse(graph: &SparseGraph) -> Self {
+        let n = graph.node_count();
+        let pairs = (0..n).flat_map(|u| graph.edges(u).map(move |(v, _)| (u, v, 1)));
+        WeightedGraph::from_pairs(vec![1; n], pairs)
+    }
+}
+
+/// Deterministic xorshift generator for the matching order; the crate's
+/// tests also use it for reproducible random graphs
+pub(crate) struct XorShift(pub(crate) u64);
+
+impl XorShift {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[cfg(test)]
+impl XorShift {
+    /// Uniform integer in [0, n)
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// Uniform in [0, 1)
+    pub(crate) fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Heavy-edge matching contraction. Returns the coarse graph and the
+/// fine → coarse vertex map.
+fn coarsen(g: &WeightedGraph, max_vertex_weight: u64, rng: &mut XorShift) -> (WeightedGraph, Vec<usize>) {
+    let n = g.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    for i in (1..n).rev() {
+        order.swap(i, (rng.next() % (i as u64 + 1)) as usize);
+    }
+    let mut map = vec![usize::MAX; n];
+    let mut coarse_weights = Vec::new();
+    for &v in &order {
+        if map[v] != usize::MAX {
+            continue;
+        }
+        let mate = g
+            .neighbors(v)
+            .filter(|&(u, _)| map[u] == usize::MAX && g.vertex_weights[u] + g.vertex_weights[v] <= max_vertex_weight)
+            .max_by_key(|&(u, w)| (w, Reverse(u)))
+            .map(|(u, _)| u);
+        let id = coarse_weights.len();
+        map[v] = id;
+        let mut weight = g.vertex_weights[v];
+        if let Some(u) = mate {
+            map[u] = id;
+            weight += g.vertex_weights[u];
+        }
+        coarse_weights.push(weight);
+    }
+    let map_ref = &map;
+    let pairs = (0..n).flat_map(|v| {
+        g.neighbors(v).filter(move |&(u, _)| v < u).map(move |(u, w)| (map_ref[v], map_ref[u], w))
+    });
+    let coarse = WeightedGraph::from_pairs(coarse_weights, pairs);
+    (coarse, map)
+}
+
+/// Region growing: each part grows breadth-first from a seed on the previous
+/// part's frontier until it holds its share of the remaining vertex weight.
+fn grow_regions(g: &WeightedGraph, parts: usize) -> Vec<usize> {
+    let n = g.len();
+    let mut remaining: u64 = g.vertex_weights.iter().sum();
+    let mut part = vec![usize::MAX; n];
+    let mut carry: VecDeque<usize> = VecDeque::new();
+    let (mut assigned, mut cursor) = (0, 0);
+    for p in 0..parts {
+        let last = p + 1 == parts;
+        let target = remaining / (parts - p) as u64;
+        let mut weight = 0;
+        let mut queue = VecDeque::new();
+        while (last || weight < target) && assigned < n {
+            let v = match queue.pop_front() {
+                Some(v) => v,
+                None => match carry.pop_front() {
+                    Some(v) => v,
+                    None => {
+                        while part[cursor] != usize::MAX {
+                            cursor += 1;
+                        }
+                        cursor
+                    }
+                },
+            };
+            if part[v] != usize::MAX {
+                continue;
+            }
+            part[v] = p;
+            weight += g.vertex_weights[v];
+            assigned += 1;
+            queue.extend(g.neighbors(v).map(|(u, _)| u).filter(|&u| part[u] == usize::MAX));
+        }
+        carry.extend(queue);
+        remaining -= weight;
+    }
+    part
+}
+
+/// Best single move for v: (gain, target part), or None if v is interior.
+fn best_move(g: &WeightedGraph, part: &[usize], v: usize) -> Option<(i64, usize)> {
+    let mut connectivity: Vec<(usize, u64)> = Vec::new();
+    for (u, w) in g.neighbors(v) {
+        match connectivity.iter_mut().find(|(p, _)| *p == part[u]) {
+            Some(entry) => entry.1 += w,
+            None => connectivity.push((part[u], w)),
+        }
+    }
+    let internal = connectivity.iter().find(|(p, _)| *p == part[v]).map_or(0, |e| e.1) as i64;
+    connectivity
+        .iter()
+        .filter(|(p, _)| *p != part[v])
+        .max_by_key(|&&(p, w)| (w, Reverse(p)))
+        .map(|&(p, w)| (w as i64 - internal, p))
+}
+
+/// k-way Fiduccia–Mattheyses refinement under a part-weight limit.
+fn refine(g: &WeightedGraph, part: &mut [usize], parts: usize, max_part_weight: u64) {
+    const MAX_PASSES: usize = 8;
+    // Uphill moves allowed past the best prefix before a pass gives up
+    const PATIENCE: usize = 64;
+    let n = g.len();
+    let mut part_weight = vec![0u64; parts];
+    for v in 0..n {
+        part_weight[part[v]] += g.vertex_weights[v];
+    }
+    for _ in 0..MAX_PASSES {
+        let mut locked = vec![false; n];
+        let mut candidate: Vec<Option<(i64, usize)>> = (0..n).map(|v| best_move(g, part, v)).collect();
+        let mut heap: BinaryHeap<(i64, Reverse<usize>)> =
+            candidate.iter().enumerate().filter_map(|(v, m)| m.map(|(gain, _)| (gain, Reverse(v)))).collect();
+        let mut moves: Vec<(usize, usize)> = Vec::new();
+        let (mut gain_sum, mut best_sum, mut best_len) = (0i64, 0i64, 0usize);
+        while let Some((gain, Reverse(v))) = heap.pop() {
+            let Some((current, to)) = candidate[v] else { continue };
+            if locked[v] || current != gain {
+                continue;
+            }
+            let (from, w) = (part[v], g.vertex_weights[v]);
+            if part_weight[to] + w > max_part_weight || part_weight[from] == w {
+                continue;
+            }
+            part[v] = to;
+            part_weight[from] -= w;
+            part_weight[to] += w;
+            locked[v] = true;
+            moves.push((v, from));
+            gain_sum += gain;
+            if gain_sum > best_sum {
+                best_sum = gain_sum;
+                best_len = moves.len();
+            } else if moves.len() - best_len > PATIENCE {
+                break;
+            }
+            for (u, _) in g.neighbors(v) {
+                if !locked[u] {
+                    candidate[u] = best_move(g, part, u);
+                    if let Some((gain, _)) = candidate[u] {
+                        heap.push((gain, Reverse(u)));
+                    }
+                }
+            }
+        }
+        // Roll back to the best prefix
+        for &(v, from) in moves[best_len..].iter().rev() {
+            let w = g.vertex_weights[v];
+            part_weight[part[v]] -= w;
+            part_weight[from] += w;
+            part[v] = from;
+        }
+        if best_sum <= 0 {
+            break;
+        }
+    }
+}
+
+fn multilevel(graph: &SparseGraph, parts: usize) -> Vec<usize> {
+    let mut rng = XorShift(0x853c49e6748fea9b);
+    let n = graph.node_count();
+    let coarsest = (COARSEST_PER_PART * parts).max(2 * parts);
+    let max_vertex_weight = ((1.5 * n as f64 / coarsest as f64).ceil() as u64).max(1);
+
+    let mut levels = vec![WeightedGraph::from_sparse(graph)];
+    let mut maps = Vec::new();
+    while levels.last().unwrap().len() > coarsest {
+        let g = levels.last().unwrap();
+        let (coarse, map) = coarsen(g, max_vertex_weight, &mut rng);
+        // Stop once matching no longer shrinks the graph appreciably
+        if coarse.len() as f64 > 0.95 * g.len() as f64 {
+            break;
+        }
+        levels.push(coarse);
+        maps.push(map);
+    }
+
+    let limit = |g: &WeightedGraph| {
+        let total: u64 = g.vertex_weights.iter().sum();
+        let heaviest = g.vertex_weights.iter().copied().max().unwrap_or(1);
+        ((1.0 + IMBALANCE) * total as f64 / parts as f64).ceil() as u64 + heaviest - 1
+    };
+    let coarse = levels.last().unwrap();
+    let mut part = grow_regions(coarse, parts);
+    refine(coarse, &mut part, parts, limit(coarse));
+    for (g, map) in levels.iter().rev().skip(1).zip(maps.iter().rev()) {
+        part = map.iter().map(|&c| part[c]).collect();
+        refine(g, &mut part, parts, limit(g));
+    }
+    part
+}
+
+/// Renumber parts densely in order of first appearance.
+fn compact(part: &mut [usize]) -> usize {
+    let mut renumber: Vec<usize> = vec![usize::MAX; part.iter().max().map_or(0, |&p| p + 1)];
+    let mut count = 0;
+    for p in part.iter_mut() {
+        if renumber[*p] == usize::MAX {
+            renumber[*p] = count;
+            count += 1;
+        }
+        *p = renumber[*p];
+    }
+    count
+}
+
+/// Split `graph` into at most `parts` clusters. Returns the cluster index
+/// of every node and the number of clusters.
+pub(crate) fn partition(graph: &SparseGraph, parts: usize, method: Partitioner) -> (Vec<usize>, usize) {
+    let n = graph.node_count();
+    let parts = parts.clamp(1, n.max(1));
+    let mut part = match method {
+        Partitioner::Index => {
+            let size = n.div_ceil(parts).max(1);
+            (0..n).map(|v| v / size).collect()
+        }
+        Partitioner::Bfs => grow_regions(&WeightedGraph::from_sparse(graph), parts),
+        Partitioner::Multilevel => multilevel(graph, parts),
+    };
+    let count = compact(&mut part);
+    (part, count)
+}
+
+/// Number of directed edges whose endpoints lie in different clusters.
+pub(crate) fn cut_edges(graph: &SparseGraph, part: &[usize]) -> usize {
+    (0..graph.node_count()).map(|u| graph.edges(u).filter(|&(v, _)| part[u] != part[v]).count()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// side × side 4-neighbour grid (both directions) with node ids shuffled
+    fn scrambled_grid(side: usize) -> SparseGraph {
+        let n = side * side;
+        let mut rng = XorShift(0x243f6a8885a308d3);
+        let mut label: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            label.swap(i, (rng.next() % (i as u64 + 1)) as usize);
+        }
+        let mut adjacency = vec![Vec::new(); n];
+        for r in 0..side {
+            for c in 0..side {
+                let v = label[r * side + c];
+                for (dr, dc) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                    let (rr, cc) = (r as i64 + dr, c as i64 + dc);
+                    if (0..side as i64).contains(&rr) && (0..side as i64).contains(&cc) {
+                        adjacency[v].push(label[rr as usize * side + cc as usize] as u32);
+                    }
+                }
+            }
+        }
+        let mut offsets = vec![0u32];
+        let mut destinations = Vec::new();
+        for targets in adjacency {
+            destinations.extend(targets);
+            offsets.push(destinations.len() as u32);
+        }
+        let weights = vec![1.0; destinations.len()];
+        SparseGraph::from_csr(n, offsets, destinations, weights)
+    }
+
+    fn sizes(part: &[usize], count: usize) -> Vec<usize> {
+        let mut sizes = vec![0; count];
+        for &p in part {
+            sizes[p] += 1;
+        }
+        sizes
+    }
+
+    #[test]
+    fn topology_aware_partitions_cut_far_fewer_edges() {
+        let graph = scrambled_grid(60);
+        let parts = 60;
+        let cut = |method| {
+            let (part, count) = partition(&graph, parts, method);
+            assert_eq!(count, parts, "{:?}", method);
+            assert!(sizes(&part, count).iter().all(|&s| s > 0));
+            cut_edges(&graph, &part)
+        };
+        let (index, bfs, multilevel) = (cut(Partitioner::Index), cut(Partitioner::Bfs), cut(Partitioner::Multilevel));
+        // Shuffled ids: index chunks cut nearly every edge
+        assert!(index > graph.edge_count() * 9 / 10, "index {} edges {}", index, graph.edge_count());
+        assert!(bfs * 4 < index, "bfs {} index {}", bfs, index);
+        assert!(multilevel < bfs, "multilevel {} bfs {}", multilevel, bfs);
+        // Square tiles of n / parts nodes: 4·sqrt(n / parts) cut edges each
+        let tiles = parts as f64 * 4.0 * (graph.node_count() as f64 / parts as f64).sqrt();
+        assert!((multilevel as f64) < 1.25 * tiles, "multilevel {} tiles {:.0}", multilevel, tiles);
+    }
+
+    #[test]
+    fn multilevel_respects_balance_limit() {
+        let graph = scrambled_grid(45);
+        for parts in [2, 7, 45] {
+            let (part, count) = partition(&graph, parts, Partitioner::Multilevel);
+            let largest = *sizes(&part, count).iter().max().unwrap();
+            let average = graph.node_count() as f64 / parts as f64;
+            // 5 % imbalance plus at most one coarse vertex
+            assert!(largest as f64 <= (1.0 + IMBALANCE) * average + 3.0 * average / COARSEST_PER_PART as f64,
+                "{} parts: largest {} average {:.1}", parts, largest, average);
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! ID: WASM-P2P-001
+//! Requirement: Single-pair shortest paths on a SparseGraph that stop as soon
+//!   as the target's distance is final.
+//! Purpose: The trajectory planner needs one path from the current state to a
+//!   target orbit node, not distances to every node.
+//! Rationale: Bidirectional Dijkstra grows two balls of about half the radius.
+//!   It stops once the two heap minima sum to at least the best meeting
+//!   distance. A* orders the search by g + h with a caller heuristic. ALT
+//!   derives that heuristic from precomputed landmark distances through the
+//!   triangle inequality, which gives a consistent lower bound on any graph.
+//! Inputs: SparseGraph and its reverse (CSR, weights ≥ 0), source, target,
+//!   heuristic h(v) ≤ d(v, target) for A*
+//! Outputs: distance (+∞ if unreachable), node path source..target,
+//!   nodes settled and edges relaxed
+//! Assumptions/Limits: A* reopens nodes, so an admissible but inconsistent
+//!   heuristic still gives exact distances (at the cost of extra expansions).
+//!   Searches allocate O(n) arrays per query. Landmarks use 16·k bytes per node.
+//! References: Goldberg & Harrelson (2005) "Computing the Shortest Path: A*
+//!   Search Meets Graph Theory", SODA; Hart, Nilsson & Raphael (1968) IEEE
+//!   Trans. SSC 4(2); Pohl (1971) "Bi-directional Search", Machine Intelligence 6
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::sssp::{SearchStats, SparseGraph};
+
+/// Min-heap entry ordered by key, then node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Entry {
+    pub(crate) key: f64,
+    pub(crate) node: usize,
+}
+
+impl Eq for Entry {}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.total_cmp(&self.key).then(other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Result of a single-pair search
+#[derive(Debug, Clone)]
+pub(crate) struct PathSearch {
+    pub(crate) distance: f64,
+    pub(crate) path: Vec<u32>,
+    pub(crate) stats: SearchStats,
+}
+
+impl PathSearch {
+    fn unreachable(stats: SearchStats) -> Self {
+        PathSearch { distance: f64::INFINITY, path: Vec::new(), stats }
+    }
+}
+
+/// Follow predecessors from `node` back to the search root (root first).
+fn trace(predecessors: &[i32], node: usize) -> Vec<u32> {
+    let mut path = vec![node as u32];
+    let mut v = node;
+    while predecessors[v] >= 0 {
+        v = predecessors[v] as usize;
+        path.push(v as u32);
+    }
+    path.reverse();
+    path
+}
+
+/// One direction of a bidirectional search
+struct Frontier<'a> {
+    graph: &'a SparseGraph,
+    distances: Vec<f64>,
+    predecessors: Vec<i32>,
+    settled: Vec<bool>,
+    heap: BinaryHeap<Entry>,
+}
+
+impl<'a> Frontier<'a> {
+    fn new(graph: &'a SparseGraph, root: usize) -> Self {
+        let n = graph.node_count();
+        let mut distances = vec![f64::INFINITY; n];
+        distances[root] = 0.0;
+        Frontier {
+            graph,
+            distances,
+            predecessors: vec![-1; n],
+            settled: vec![false; n],
+            heap: BinaryHeap::from([Entry { key: 0.0, node: root }]),
+        }
+    }
+
+    fn min_key(&self) -> f64 {
+        self.heap.peek().map_or(f64::INFINITY, |e| e.key)
+    }
+
+    /// Settle the next node and relax its edges, recording the best meeting
+    /// point with the opposite search.
+    fn step(&mut self, other: &[f64], best: &mut (f64, usize), stats: &mut SearchStats) {
+        let Some(Entry { key, node: u }) = self.heap.pop() else { return };
+        if self.settled[u] || key > self.distances[u] {
+            return;
+        }
+        self.settled[u] = true;
+        stats.nodes_visited += 1;
+        for (v, w) in self.graph.edges(u) {
+            stats.edges_relaxed += 1;
+            let d = key + w;
+            if d < self.distances[v] {
+                self.distances[v] = d;
+                self.predecessors[v] = u as i32;
+                self.heap.push(Entry { key: d, node: v });
+            }
+            if self.distances[v] + other[v] < best.0 {
+                *best = (self.distances[v] + other[v], v);
+            }
+        }
+    }
+}
+
+/// Bidirectional Dijkstra: forward from `source` on `graph`, backward from
+/// `target` on `reverse`.
+pub(crate) fn bidirectional_dijkstra(graph: &SparseGraph, reverse: &SparseGraph, source: usize, target: usize) -> PathSearch {
+    let mut stats = SearchStats::default();
+    let mut forward = Frontier::new(graph, source);
+    let mut backward = Frontier::new(reverse, target);
+    let mut best = if source == target { (0.0, source) } else { (f64::INFINITY, usize::MAX) };
+
+    loop {
+        let (f, b) = (forward.min_key(), backward.min_key());
+        // Any path not yet seen is at least as long as the two radii together
+        if f + b >= best.0 {
+            break;
+        }
+        if f <= b {
+            forward.step(&backward.distances, &mut best, &mut stats);
+        } else {
+            backward.step(&forward.distances, &mut best, &mut stats);
+        }
+    }
+
+    let (distance, meet) = best;
+    if distance.is_infinite() {
+        return PathSearch::unreachable(stats);
+    }
+    let mut path = trace(&forward.predecessors, meet);
+    let mut v = meet;
+    while backward.predecessors[v] >= 0 {
+        v = backward.predecessors[v] as usize;
+        path.push(v as u32);
+    }
+    PathSearch { distance, path, stats }
+}
+
+/// A* from `source` to `target`. `heuristic(v)` must not exceed d(v, target);
+/// +∞ marks nodes known not to reach the target.
+pub(crate) fn astar(graph: &SparseGraph, source: usize, target: usize, heuristic: impl Fn(usize) -> f64) -> PathSearch {
+    let n = graph.node_count();
+    let mut stats = SearchStats::default();
+    let mut g = vec![f64::INFINITY; n];
+    let mut h = vec![f64::NAN; n];
+    let mut predecessors = vec![-1i32; n];
+    let mut heap = BinaryHeap::new();
+
+    h[source] = heuristic(source);
+    g[source] = 0.0;
+    if h[source].is_finite() {
+        heap.push(Entry { key: h[source], node: source });
+    }
+
+    while let Some(Entry { key, node: u }) = heap.pop() {
+        // Skip entries superseded by a shorter g
+        if key > g[u] + h[u] {
+            continue;
+        }
+        stats.nodes_visited += 1;
+        if u == target {
+            return PathSearch { distance: g[u], path: trace(&predecessors, u), stats };
+        }
+        for (v, w) in graph.edges(u) {
+            stats.edges_relaxed += 1;
+            let d = g[u] + w;
+            if d < g[v] {
+                if h[v].is_nan() {
+                    h[v] = heuristic(v);
+                }
+                g[v] = d;
+                predecessors[v] = u as i32;
+                if h[v].is_finite() {
+                    heap.push(Entry { key: d + h[v], node: v });
+                }
+            }
+        }
+    }
+    PathSearch::unreachable(stats)
+}
+
+/// Full single-source distances (landmark tables)
+fn distances_from(graph: &SparseGraph, source: usize) -> Vec<f64> {
+    let mut distances = vec![f64::INFINITY; graph.node_count()];
+    let mut heap = BinaryHeap::from([Entry { key: 0.0, node: source }]);
+    distances[source] = 0.0;
+    while let Some(Entry { key, node: u }) = heap.pop() {
+        if key > distances[u] {
+            continue;
+        }
+        for (v, w) in graph.edges(u) {
+            if key + w < distances[v] {
+                distances[v] = key + w;
+                heap.push(Entry { key: key + w, node: v });
+            }
+        }
+    }
+    distances
+}
+
+/// d(x, t) ≥ a − b from the triangle inequality, where a and b are distances
+/// to or from one landmark. a = +∞ with b finite proves t unreachable from x.
+fn landmark_bound(a: f64, b: f64) -> f64 {
+    if b.is_infinite() {
+        0.0
+    } else {
+        (a - b).max(0.0)
+    }
+}
+
+/// ALT landmark tables, stored node-major: `from[v·k + i]` = d(L_i, v) and
+/// `to[v·k + i]` = d(v, L_i)
+#[derive(Debug)]
+pub(crate) struct Landmarks {
+    pub(crate) nodes: Vec<usize>,
+    from: Vec<f64>,
+    to: Vec<f64>,
+}
+
+impl Landmarks {
+    /// Pick `count` landmarks by farthest-point selection and tabulate their
+    /// distances. Nodes no landmark reaches or is reached from are picked first.
+    pub(crate) fn build(graph: &SparseGraph, reverse: &SparseGraph, count: usize) -> Self {
+        let n = graph.node_count();
+        let count = count.min(n);
+        let mut nodes = Vec::with_capacity(count);
+        let mut from_rows = Vec::with_capacity(count);
+        let mut to_rows = Vec::with_capacity(count);
+        // Distance (either direction) to the nearest landmark; the first
+        // landmark is the node farthest from node 0
+        let mut score = if n > 0 { distances_from(graph, 0) } else { Vec::new() };
+        for _ in 0..count {
+            let next = (0..n)
+                .filter(|v| !nodes.contains(v))
+                .max_by(|&a, &b| score[a].total_cmp(&score[b]).then(b.cmp(&a)))
+                .unwrap();
+            let (from, to) = (distances_from(graph, next), distances_from(reverse, next));
+            if nodes.is_empty() {
+                score.fill(f64::INFINITY);
+            }
+            for v in 0..n {
+                score[v] = score[v].min(from[v].min(to[v]));
+            }
+            nodes.push(next);
+            from_rows.push(from);
+            to_rows.push(to);
+        }
+
+        let interleave = |rows: &[Vec<f64>]| -> Vec<f64> {
+            (0..n).flat_map(|v| rows.iter().map(move |row| row[v])).collect()
+        };
+        Landmarks { from: interleave(&from_rows), to: interleave(&to_rows), nodes }
+    }
+
+    /// Lower bound on d(v, target) over all landmarks
+    pub(crate) fn lower_bound(&self, v: usize, target: usize) -> f64 {
+        let k = self.nodes.len();
+        let (from_v, from_t) = (&self.from[v * k..(v + 1) * k], &self.from[target * k..(target + 1) * k]);
+        let (to_v, to_t) = (&self.to[v * k..(v + 1) * k], &self.to[target * k..(target + 1) * k]);
+        (0..k)
+            .map(|i| landmark_bound(from_t[i], from_v[i]).max(landmark_bound(to_v[i], to_t[i])))
+            .fold(0.0, f64::max)
+    }
+}
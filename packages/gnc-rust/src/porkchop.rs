@@ -0,0 +1,315 @@
+//! ID: WASM-PORKCHOP-001
+//! Requirement: Evaluate departure C3, arrival v∞, total Δv and time of flight
+//!   over a departure × arrival date grid between two heliocentric bodies, and
+//!   report the optimal cell for each metric.
+//! Purpose: Porkchop plots for the Earth–asteroid–Mars mission-design scenario.
+//! Rationale: The cells are independent, so the grid is solved in parallel with
+//!   rayon. On wasm32 without threads, rayon falls back to the calling thread.
+//!   Body states are computed once per date rather than once per cell. When
+//!   multi-revolution arcs are allowed, each cell keeps the branch with the
+//!   smallest total Δv.
+//! Inputs: departure/arrival bodies (analytic planet ephemeris or a two-body
+//!   heliocentric orbit), date windows as Julian dates (TDB), revolution limit
+//! Outputs: flat row-major grids (row = departure date, column = arrival date):
+//!   C3 [m²/s²], v∞ at departure and arrival [m/s], total Δv [m/s], TOF [days]
+//! Assumptions: prograde heliocentric transfers about the Sun only. Total Δv is
+//!   the sum of the two hyperbolic excess speeds (no parking-orbit or capture
+//!   burns). Cells with arrival ≤ departure or no Lambert solution are NaN.
+//! References: Izzo (2015) Lambert solver (WASM-LAMBERT-001);
+//!   Vallado §7.6 (porkchop plots); George & Kos (1998) "Interplanetary
+//!   Mission Design Handbook" NASA/TM-1998-208533
+
+use rayon::prelude::*;
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::constants::MU_SUN;
+use crate::elements::{mean_to_true_anomaly, KeplerianElements};
+use crate::ephemeris::{body_state, ecliptic_to_equatorial, EphemerisBody};
+use crate::kepler::kepler_propagate_state;
+use crate::lambert::lambert_izzo;
+use crate::rk4::State6;
+use crate::rk45::state6_from_js;
+
+/// Departure or arrival body of a transfer.
+#[derive(Debug, Clone, Copy)]
+pub enum TransferBody {
+    /// Analytic ephemeris (EphemerisBody::Earth is the Earth itself, not the EMB)
+    Planet(EphemerisBody),
+    /// Heliocentric EME2000 state [m, m/s] at `epoch_jd` (TDB), propagated two-body
+    Orbit { state: State6, epoch_jd: f64 },
+}
+
+impl TransferBody {
+    /// Heliocentric EME2000 state at Julian date `jd_tdb`.
+    pub fn heliocentric_state(&self, jd_tdb: f64) -> Result<State6, String> {
+        match self {
+            TransferBody::Planet(body) => Ok(body_state(*body, EphemerisBody::Sun, jd_tdb)),
+            TransferBody::Orbit { state, epoch_jd } => kepler_propagate_state(state, (jd_tdb - epoch_jd) * 86400.0, MU_SUN),
+        }
+    }
+}
+
+/// Porkchop grid; every metric is flat row-major [departure][arrival].
+#[derive(Debug, Clone)]
+pub struct Porkchop {
+    pub departure_jd: Vec<f64>,
+    pub arrival_jd: Vec<f64>,
+    pub c3: Vec<f64>,
+    pub vinf_departure: Vec<f64>,
+    pub vinf_arrival: Vec<f64>,
+    pub total_dv: Vec<f64>,
+    pub tof_days: Vec<f64>,
+}
+
+/// Index of the smallest finite value.
+fn argmin(values: &[f64]) -> Option<usize> {
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| v.is_finite())
+        .min_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(k, _)| k)
+}
+
+impl Porkchop {
+    pub fn best_c3(&self) -> Option<usize> { argmin(&self.c3) }
+    pub fn best_vinf_arrival(&self) -> Option<usize> { argmin(&self.vinf_arrival) }
+    pub fn best_total_dv(&self) -> Option<usize> { argmin(&self.total_dv) }
+}
+
+/// `count` evenly spaced dates from `start` to `end` inclusive.
+pub fn date_window(start_jd: f64, end_jd: f64, count: usize) -> Vec<f64> {
+    match count {
+        0 => Vec::new(),
+        1 => vec![start_jd],
+        _ => (0..count).map(|k| start_jd + (end_jd - start_jd) * k as f64 / (count - 1) as f64).collect(),
+    }
+}
+
+fn speed(v: &[f64], body: &State6) -> f64 {
+    ((v[0] - body[3]).powi(2) + (v[1] - body[4]).powi(2) + (v[2] - body[5]).powi(2)).sqrt()
+}
+
+/// Solve every (departure, arrival) cell in parallel.
+pub fn porkchop(
+    departure: &TransferBody,
+    arrival: &TransferBody,
+    departure_jd: &[f64],
+    arrival_jd: &[f64],
+    max_revs: u32,
+) -> Porkchop {
+    let dep_states: Vec<_> = departure_jd.iter().map(|&jd| departure.heliocentric_state(jd).ok()).collect();
+    let arr_states: Vec<_> = arrival_jd.iter().map(|&jd| arrival.heliocentric_state(jd).ok()).collect();
+    let cols = arrival_jd.len();
+
+    // [C3, v∞ dep, v∞ arr, total Δv, TOF] per cell
+    let cells: Vec<[f64; 5]> = (0..departure_jd.len() * cols)
+        .into_par_iter()
+        .map(|cell| {
+            let (i, j) = (cell / cols, cell % cols);
+            let tof_days = arrival_jd[j] - departure_jd[i];
+            let (Some(sd), Some(sa)) = (dep_states[i], arr_states[j]) else {
+                return [f64::NAN; 5];
+            };
+            if tof_days <= 0.0 {
+                return [f64::NAN; 5];
+            }
+            let r1 = [sd[0], sd[1], sd[2]];
+            let r2 = [sa[0], sa[1], sa[2]];
+            lambert_izzo(&r1, &r2, tof_days * 86400.0, MU_SUN, true, max_revs)
+                .solutions
+                .iter()
+                .map(|s| (speed(&s.v1, &sd), speed(&s.v2, &sa)))
+                .min_by(|a, b| (a.0 + a.1).total_cmp(&(b.0 + b.1)))
+                .map_or([f64::NAN; 5], |(vd, va)| [vd * vd, vd, va, vd + va, tof_days])
+        })
+        .collect();
+
+    let column = |k: usize| cells.iter().map(|c| c[k]).collect::<Vec<f64>>();
+    Porkchop {
+        departure_jd: departure_jd.to_vec(),
+        arrival_jd: arrival_jd.to_vec(),
+        c3: column(0),
+        vinf_departure: column(1),
+        vinf_arrival: column(2),
+        total_dv: column(3),
+        tof_days: column(4),
+    }
+}
+
+/// Endpoint of a porkchop grid.
+#[wasm_bindgen]
+pub struct PorkchopBody {
+    inner: TransferBody,
+}
+
+#[wasm_bindgen]
+impl PorkchopBody {
+    /// A body from the built-in analytic ephemeris
+    #[wasm_bindgen]
+    pub fn planet(body: EphemerisBody) -> PorkchopBody {
+        PorkchopBody { inner: TransferBody::Planet(body) }
+    }
+
+    /// A heliocentric EME2000 state [m, m/s] at Julian date `epoch_jd` (TDB)
+    #[wasm_bindgen]
+    pub fn from_state(state_js: &Float64Array, epoch_jd: f64) -> Result<PorkchopBody, JsValue> {
+        let sv = state6_from_js(state_js)?;
+        let state = [sv[0], sv[1], sv[2], sv[3], sv[4], sv[5]];
+        Ok(PorkchopBody { inner: TransferBody::Orbit { state, epoch_jd } })
+    }
+
+    /// Heliocentric osculating elements referred to the ecliptic of J2000, as
+    /// published for asteroids: a [m], e, i, Ω, ω, M [rad] at `epoch_jd` (TDB)
+    #[wasm_bindgen]
+    pub fn from_elements(
+        a: f64,
+        e: f64,
+        i: f64,
+        raan: f64,
+        argp: f64,
+        mean_anomaly: f64,
+        epoch_jd: f64,
+    ) -> Result<PorkchopBody, JsValue> {
+        let nu = mean_to_true_anomaly(mean_anomaly, e);
+        let el = KeplerianElements::from_semi_major_axis(a, e, i, raan, argp, nu).map_err(|e| JsValue::from_str(&e))?;
+        let s = el.to_state(MU_SUN);
+        let r = ecliptic_to_equatorial([s[0], s[1], s[2]]);
+        let v = ecliptic_to_equatorial([s[3], s[4], s[5]]);
+        let state = [r[0], r[1], r[2], v[0], v[1], v[2]];
+        Ok(PorkchopBody { inner: TransferBody::Orbit { state, epoch_jd } })
+    }
+
+    /// Heliocentric EME2000 state at Julian date `jd_tdb` (NaN on failure)
+    #[wasm_bindgen]
+    pub fn state(&self, jd_tdb: f64) -> Float64Array {
+        Float64Array::from(&self.inner.heliocentric_state(jd_tdb).unwrap_or([f64::NAN; 6])[..])
+    }
+}
+
+/// Porkchop grid exposed to JS. Grids are flat row-major: cell k is departure
+/// k / columns and arrival k % columns.
+#[wasm_bindgen]
+pub struct PorkchopPlot {
+    inner: Porkchop,
+}
+
+#[wasm_bindgen]
+impl PorkchopPlot {
+    #[wasm_bindgen(getter)]
+    pub fn rows(&self) -> usize { self.inner.departure_jd.len() }
+
+    #[wasm_bindgen(getter)]
+    pub fn columns(&self) -> usize { self.inner.arrival_jd.len() }
+
+    /// Departure Julian dates (TDB), one per row
+    #[wasm_bindgen(getter)]
+    pub fn departure_dates(&self) -> Float64Array { Float64Array::from(self.inner.departure_jd.as_slice()) }
+
+    /// Arrival Julian dates (TDB), one per column
+    #[wasm_bindgen(getter)]
+    pub fn arrival_dates(&self) -> Float64Array { Float64Array::from(self.inner.arrival_jd.as_slice()) }
+
+    /// Departure C3 [m²/s²]
+    #[wasm_bindgen(getter)]
+    pub fn c3(&self) -> Float64Array { Float64Array::from(self.inner.c3.as_slice()) }
+
+    /// Departure hyperbolic excess speed [m/s]
+    #[wasm_bindgen(getter)]
+    pub fn vinf_departure(&self) -> Float64Array { Float64Array::from(self.inner.vinf_departure.as_slice()) }
+
+    /// Arrival hyperbolic excess speed [m/s]
+    #[wasm_bindgen(getter)]
+    pub fn vinf_arrival(&self) -> Float64Array { Float64Array::from(self.inner.vinf_arrival.as_slice()) }
+
+    /// Departure plus arrival v∞ [m/s]
+    #[wasm_bindgen(getter)]
+    pub fn total_dv(&self) -> Float64Array { Float64Array::from(self.inner.total_dv.as_slice()) }
+
+    /// Time of flight [days]
+    #[wasm_bindgen(getter)]
+    pub fn tof(&self) -> Float64Array { Float64Array::from(self.inner.tof_days.as_slice()) }
+
+    /// Cell index of the minimum C3 (undefined if no cell solved)
+    #[wasm_bindgen(getter)]
+    pub fn best_c3(&self) -> Option<u32> { self.inner.best_c3().map(|k| k as u32) }
+
+    /// Cell index of the minimum arrival v∞
+    #[wasm_bindgen(getter)]
+    pub fn best_vinf_arrival(&self) -> Option<u32> { self.inner.best_vinf_arrival().map(|k| k as u32) }
+
+    /// Cell index of the minimum total Δv
+    #[wasm_bindgen(getter)]
+    pub fn best_total_dv(&self) -> Option<u32> { self.inner.best_total_dv().map(|k| k as u32) }
+}
+
+/// Porkchop grid from `departure` to `arrival` over inclusive date windows of
+/// `departure_count` × `arrival_count` Julian dates (TDB).
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen]
+pub fn porkchop_plot(
+    departure: &PorkchopBody,
+    arrival: &PorkchopBody,
+    departure_start_jd: f64,
+    departure_end_jd: f64,
+    departure_count: u32,
+    arrival_start_jd: f64,
+    arrival_end_jd: f64,
+    arrival_count: u32,
+    max_revs: u32,
+) -> Result<PorkchopPlot, JsValue> {
+    let window = [departure_start_jd, departure_end_jd, arrival_start_jd, arrival_end_jd];
+    if departure_count == 0 || arrival_count == 0 || !window.iter().all(|jd| jd.is_finite()) {
+        return Err(JsValue::from_str("Date windows must be finite with at least one date each"));
+    }
+    let dep = date_window(departure_start_jd, departure_end_jd, departure_count as usize);
+    let arr = date_window(arrival_start_jd, arrival_end_jd, arrival_count as usize);
+    Ok(PorkchopPlot { inner: porkchop(&departure.inner, &arrival.inner, &dep, &arr, max_revs) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::AU;
+
+    #[test]
+    fn earth_mars_2005_minimum_c3() {
+        // 2005 opportunity: minimum type-I C3 ≈ 16 km²/s² (MRO launched at 16.4)
+        let dep = date_window(2453550.5, 2453700.5, 31);
+        let arr = date_window(2453750.5, 2454000.5, 26);
+        let earth = TransferBody::Planet(EphemerisBody::Earth);
+        let mars = TransferBody::Planet(EphemerisBody::Mars);
+        let grid = porkchop(&earth, &mars, &dep, &arr, 0);
+        let k = grid.best_c3().unwrap();
+        let c3_km = grid.c3[k] / 1e6;
+        assert!((14.0..18.5).contains(&c3_km), "C3 {} km²/s² at dep {} arr {}", c3_km, dep[k / 26], arr[k % 26]);
+        assert!((grid.tof_days[k] - (arr[k % 26] - dep[k / 26])).abs() < 1e-9);
+        assert_eq!(grid.c3[k], grid.vinf_departure[k].powi(2));
+
+        // The parallel grid agrees with a single-cell evaluation
+        let one = porkchop(&earth, &mars, &dep[k / 26..k / 26 + 1], &arr[k % 26..k % 26 + 1], 0);
+        assert_eq!(one.total_dv[0], grid.total_dv[k]);
+        assert!(grid.total_dv[grid.best_total_dv().unwrap()] <= grid.total_dv[k]);
+    }
+
+    #[test]
+    fn orbit_bodies_and_invalid_cells() {
+        // An orbit body seeded from Earth's state follows Earth for weeks
+        let jd0 = 2460000.5;
+        let earth = TransferBody::Planet(EphemerisBody::Earth);
+        let s0 = earth.heliocentric_state(jd0).unwrap();
+        let speed = (s0[3] * s0[3] + s0[4] * s0[4] + s0[5] * s0[5]).sqrt();
+        assert!((speed - 29.8e3).abs() < 700.0, "{}", speed);
+        let twin = TransferBody::Orbit { state: s0, epoch_jd: jd0 };
+        let (a, b) = (twin.heliocentric_state(jd0 + 20.0).unwrap(), earth.heliocentric_state(jd0 + 20.0).unwrap());
+        let d = ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt();
+        assert!(d < 1e-4 * AU, "{} km", d / 1e3);
+
+        // Arrival before departure is NaN, and argmin skips NaN cells
+        let grid = porkchop(&earth, &TransferBody::Planet(EphemerisBody::Mars), &[jd0, jd0 + 300.0], &[jd0 + 200.0], 1);
+        assert!(grid.c3[0].is_finite() && grid.c3[1].is_nan());
+        assert_eq!(grid.best_c3(), Some(0));
+        assert_eq!(argmin(&[f64::NAN, f64::NAN]), None);
+    }
+}
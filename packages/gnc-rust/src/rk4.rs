@@ -12,13 +12,16 @@
 use wasm_bindgen::prelude::*;
 use js_sys::Float64Array;
 
-type State6 = [f64; 6];
+use crate::forces::{state_derivative, ForceModel, ForceModelBuilder, PointMass, SpacecraftParams};
+use crate::time::Epoch;
+
+pub type State6 = [f64; 6];
 
 /// Compute the two-body gravitational derivative of the state vector.
 /// state = [x, y, z, vx, vy, vz], mu = GM [m³/s²]
 /// returns  [vx, vy, vz, ax, ay, az]
 #[inline]
-fn two_body_deriv(s: &State6, mu: f64) -> State6 {
+pub(crate) fn two_body_deriv(s: &State6, mu: f64) -> State6 {
     let r2 = s[0] * s[0] + s[1] * s[1] + s[2] * s[2];
     let r  = r2.sqrt();
     let r3 = r * r2;
@@ -26,36 +29,64 @@ fn two_body_deriv(s: &State6, mu: f64) -> State6 {
     [s[3], s[4], s[5], f * s[0], f * s[1], f * s[2]]
 }
 
-/// Single RK4 step: propagates state by dt using two-body gravity.
+/// Single RK4 step of the state under a force model, starting at time t.
 #[inline]
-fn rk4_step(s: &State6, dt: f64, mu: f64) -> State6 {
-    let k1 = two_body_deriv(s, mu);
+pub fn rk4_step_model<M: ForceModel + ?Sized>(
+    model: &M,
+    params: &SpacecraftParams,
+    t: f64,
+    s: &State6,
+    dt: f64,
+) -> State6 {
+    let k1 = state_derivative(model, t, s, params);
 
     let s2: State6 = core::array::from_fn(|i| s[i] + 0.5 * dt * k1[i]);
-    let k2 = two_body_deriv(&s2, mu);
+    let k2 = state_derivative(model, t + 0.5 * dt, &s2, params);
 
     let s3: State6 = core::array::from_fn(|i| s[i] + 0.5 * dt * k2[i]);
-    let k3 = two_body_deriv(&s3, mu);
+    let k3 = state_derivative(model, t + 0.5 * dt, &s3, params);
 
     let s4: State6 = core::array::from_fn(|i| s[i] + dt * k3[i]);
-    let k4 = two_body_deriv(&s4, mu);
+    let k4 = state_derivative(model, t + dt, &s4, params);
 
     core::array::from_fn(|i| {
         s[i] + (dt / 6.0) * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i])
     })
 }
 
+pub(crate) fn rk4_run<M: ForceModel + ?Sized>(
+    model: &M,
+    params: &SpacecraftParams,
+    state_js: &Float64Array,
+    t0: f64,
+    dt: f64,
+    steps: u32,
+    keep_all: bool,
+) -> Float64Array {
+    let sv = state_js.to_vec();
+    let mut state: State6 = [sv[0], sv[1], sv[2], sv[3], sv[4], sv[5]];
+    let mut buf: Vec<f64> = Vec::with_capacity(6 * if keep_all { steps as usize + 1 } else { 1 });
+    if keep_all {
+        buf.extend_from_slice(&state);
+    }
+    for k in 0..steps {
+        state = rk4_step_model(model, params, t0 + k as f64 * dt, &state, dt);
+        if keep_all {
+            buf.extend_from_slice(&state);
+        }
+    }
+    if !keep_all {
+        buf.extend_from_slice(&state);
+    }
+    Float64Array::from(buf.as_slice())
+}
+
 /// Propagate a two-body state forward by `steps` steps of size `dt`.
 /// Returns a 6-element Float64Array [rx,ry,rz,vx,vy,vz] at t₀ + steps*dt.
 /// state_js must have exactly 6 elements.
 #[wasm_bindgen]
 pub fn rk4_propagate(state_js: &Float64Array, dt: f64, steps: u32, mu: f64) -> Float64Array {
-    let sv = state_js.to_vec();
-    let mut state: State6 = [sv[0], sv[1], sv[2], sv[3], sv[4], sv[5]];
-    for _ in 0..steps {
-        state = rk4_step(&state, dt, mu);
-    }
-    Float64Array::from(state.as_slice())
+    rk4_run(&PointMass { mu }, &SpacecraftParams::default(), state_js, 0.0, dt, steps, false)
 }
 
 /// Propagate and return every intermediate state.
@@ -63,52 +94,89 @@ pub fn rk4_propagate(state_js: &Float64Array, dt: f64, steps: u32, mu: f64) -> F
 /// Index layout: [rx₀,ry₀,..., rx₁,ry₁,..., ...]
 #[wasm_bindgen]
 pub fn rk4_trajectory(state_js: &Float64Array, dt: f64, steps: u32, mu: f64) -> Float64Array {
-    let sv = state_js.to_vec();
-    let mut state: State6 = [sv[0], sv[1], sv[2], sv[3], sv[4], sv[5]];
-    let mut buf: Vec<f64> = Vec::with_capacity(6 * (steps as usize + 1));
-    buf.extend_from_slice(&state);
-    for _ in 0..steps {
-        state = rk4_step(&state, dt, mu);
-        buf.extend_from_slice(&state);
+    rk4_run(&PointMass { mu }, &SpacecraftParams::default(), state_js, 0.0, dt, steps, true)
+}
+
+/// rk4_propagate under a ForceModelBuilder's dynamics (t₀ = 0).
+#[wasm_bindgen]
+pub fn rk4_propagate_model(state_js: &Float64Array, dt: f64, steps: u32, model: &ForceModelBuilder) -> Float64Array {
+    rk4_run(model.model(), model.params(), state_js, 0.0, dt, steps, false)
+}
+
+/// rk4_trajectory under a ForceModelBuilder's dynamics (t₀ = 0).
+#[wasm_bindgen]
+pub fn rk4_trajectory_model(state_js: &Float64Array, dt: f64, steps: u32, model: &ForceModelBuilder) -> Float64Array {
+    rk4_run(model.model(), model.params(), state_js, 0.0, dt, steps, true)
+}
+
+/// Fixed step count and size covering start → end with steps of about `dt`.
+fn epoch_steps(model: &ForceModelBuilder, start: &Epoch, end: &Epoch, dt: f64) -> Result<(f64, f64, u32), String> {
+    if !(dt.is_finite() && dt > 0.0) {
+        return Err(format!("Step size must be finite and positive, got {}", dt));
     }
-    Float64Array::from(buf.as_slice())
+    let span = end.seconds_since(start);
+    let steps = (span / dt).abs().round().max(1.0);
+    if steps > u32::MAX as f64 {
+        return Err(format!("Step size {} s needs {:.3e} steps to cover {} s", dt, steps, span));
+    }
+    let steps = steps as u32;
+    Ok((model.model_time(start), span / steps as f64, steps))
 }
 
-/// N-body RK4 step.
-/// bodies_js: flat [x0,y0,z0,vx0,vy0,vz0, x1,...] for n bodies
-/// masses_js: [m0, m1, ...] [kg]; G = 6.674e-11 m³/kg/s²
-/// Returns updated flat body states.
+/// rk4_propagate_model between two epochs. The step is adjusted so a whole
+/// number of steps of about `dt` lands exactly on `end`; model time is measured
+/// from the builder's epoch. `dt` must be finite and positive.
 #[wasm_bindgen]
-pub fn nbody_rk4_step(bodies_js: &Float64Array, masses_js: &Float64Array, dt: f64) -> Float64Array {
-    const G: f64 = 6.674e-11;
-    let flat = bodies_js.to_vec();
-    let masses = masses_js.to_vec();
+pub fn rk4_propagate_epoch(state_js: &Float64Array, start: &Epoch, end: &Epoch, dt: f64, model: &ForceModelBuilder) -> Result<Float64Array, JsValue> {
+    let (t0, h, steps) = epoch_steps(model, start, end, dt).map_err(|e| JsValue::from_str(&e))?;
+    Ok(rk4_run(model.model(), model.params(), state_js, t0, h, steps, false))
+}
+
+/// rk4_trajectory_model between two epochs (see rk4_propagate_epoch).
+#[wasm_bindgen]
+pub fn rk4_trajectory_epoch(state_js: &Float64Array, start: &Epoch, end: &Epoch, dt: f64, model: &ForceModelBuilder) -> Result<Float64Array, JsValue> {
+    let (t0, h, steps) = epoch_steps(model, start, end, dt).map_err(|e| JsValue::from_str(&e))?;
+    Ok(rk4_run(model.model(), model.params(), state_js, t0, h, steps, true))
+}
+
+/// Universal gravitational constant [m³/kg/s²]
+pub(crate) const G: f64 = 6.674e-11;
+
+/// N-body derivative of the flat state vector.
+/// states: flat [x0,y0,z0,vx0,vy0,vz0, x1,...] for n bodies; masses: [kg]
+/// Writes [vx,vy,vz,ax,ay,az] per body into `out` (same layout as `states`).
+pub(crate) fn nbody_deriv(states: &[f64], masses: &[f64], out: &mut [f64]) {
     let n = masses.len();
+    out.fill(0.0);
+    for i in 0..n {
+        let xi = states[6*i]; let yi = states[6*i+1]; let zi = states[6*i+2];
+        // copy velocity into first 3 slots
+        out[6*i]   = states[6*i+3];
+        out[6*i+1] = states[6*i+4];
+        out[6*i+2] = states[6*i+5];
+        for j in 0..n {
+            if i == j { continue; }
+            let xj = states[6*j]; let yj = states[6*j+1]; let zj = states[6*j+2];
+            let dx = xj - xi; let dy = yj - yi; let dz = zj - zi;
+            let r2 = dx*dx + dy*dy + dz*dz;
+            let r  = r2.sqrt();
+            let f  = G * masses[j] / (r2 * r);
+            out[6*i+3] += f * dx;
+            out[6*i+4] += f * dy;
+            out[6*i+5] += f * dz;
+        }
+    }
+}
 
+/// Single N-body RK4 step on the flat state layout.
+pub(crate) fn rk4_nbody_step(flat: &[f64], masses: &[f64], dt: f64) -> Vec<f64> {
     let accel = |states: &[f64]| -> Vec<f64> {
-        let mut acc = vec![0.0f64; 6 * n];
-        for i in 0..n {
-            let xi = states[6*i]; let yi = states[6*i+1]; let zi = states[6*i+2];
-            // copy velocity into first 3 slots
-            acc[6*i]   = states[6*i+3];
-            acc[6*i+1] = states[6*i+4];
-            acc[6*i+2] = states[6*i+5];
-            for j in 0..n {
-                if i == j { continue; }
-                let xj = states[6*j]; let yj = states[6*j+1]; let zj = states[6*j+2];
-                let dx = xj - xi; let dy = yj - yi; let dz = zj - zi;
-                let r2 = dx*dx + dy*dy + dz*dz;
-                let r  = r2.sqrt();
-                let f  = G * masses[j] / (r2 * r);
-                acc[6*i+3] += f * dx;
-                acc[6*i+4] += f * dy;
-                acc[6*i+5] += f * dz;
-            }
-        }
+        let mut acc = vec![0.0f64; states.len()];
+        nbody_deriv(states, masses, &mut acc);
         acc
     };
 
-    let k1 = accel(&flat);
+    let k1 = accel(flat);
     let s2: Vec<f64> = flat.iter().zip(k1.iter()).map(|(s,k)| s + 0.5*dt*k).collect();
     let k2 = accel(&s2);
     let s3: Vec<f64> = flat.iter().zip(k2.iter()).map(|(s,k)| s + 0.5*dt*k).collect();
@@ -116,9 +184,71 @@ pub fn nbody_rk4_step(bodies_js: &Float64Array, masses_js: &Float64Array, dt: f6
     let s4: Vec<f64> = flat.iter().zip(k3.iter()).map(|(s,k)| s + dt*k).collect();
     let k4 = accel(&s4);
 
-    let result: Vec<f64> = (0..flat.len())
+    (0..flat.len())
         .map(|i| flat[i] + (dt/6.0)*(k1[i]+2.0*k2[i]+2.0*k3[i]+k4[i]))
-        .collect();
+        .collect()
+}
 
+/// N-body RK4 step.
+/// bodies_js: flat [x0,y0,z0,vx0,vy0,vz0, x1,...] for n bodies
+/// masses_js: [m0, m1, ...] [kg]; G = 6.674e-11 m³/kg/s²
+/// Returns updated flat body states.
+#[wasm_bindgen]
+pub fn nbody_rk4_step(bodies_js: &Float64Array, masses_js: &Float64Array, dt: f64) -> Float64Array {
+    let flat = bodies_js.to_vec();
+    let masses = masses_js.to_vec();
+    let result = rk4_nbody_step(&flat, &masses, dt);
     Float64Array::from(result.as_slice())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::MU_EARTH;
+    use crate::kepler::kepler_propagate_state;
+
+    fn position_error(dt: f64, duration: f64, s0: &State6) -> f64 {
+        let pm = PointMass { mu: MU_EARTH };
+        let params = SpacecraftParams::default();
+        let steps = (duration / dt).round() as u32;
+        let mut s = *s0;
+        for k in 0..steps {
+            s = rk4_step_model(&pm, &params, k as f64 * dt, &s, dt);
+        }
+        let truth = kepler_propagate_state(s0, steps as f64 * dt, MU_EARTH).unwrap();
+        (0..3).map(|i| (s[i] - truth[i]).powi(2)).sum::<f64>().sqrt()
+    }
+
+    #[test]
+    fn rk4_converges_at_fourth_order_against_kepler() {
+        // e ≈ 0.1 LEO over three orbits
+        let s0 = [7.0e6, 0.0, 0.0, 0.0, 8.2e3, 1.2e3];
+        let (coarse, fine) = (position_error(20.0, 18000.0, &s0), position_error(10.0, 18000.0, &s0));
+        assert!(coarse < 5.0, "dt = 20 s error {} m", coarse);
+        let order = (coarse / fine).log2();
+        assert!((order - 4.0).abs() < 0.3, "observed order {}", order);
+    }
+
+    #[test]
+    fn rk4_tracks_hyperbolic_escape() {
+        let s0 = [7.0e6, 0.0, 0.0, 0.0, 1.3e4, 0.0];
+        assert!(position_error(5.0, 20000.0, &s0) < 1.0);
+    }
+
+    #[test]
+    fn epoch_steps_rejects_degenerate_step_sizes() {
+        let model = ForceModelBuilder::new();
+        let start = Epoch::from_seconds_since_j2000(0.0, crate::time::TimeScale::Tt);
+        let end = Epoch::from_seconds_since_j2000(3600.0, crate::time::TimeScale::Tt);
+        for dt in [0.0, -60.0, f64::NAN, f64::INFINITY, 1e-9] {
+            assert!(epoch_steps(&model, &start, &end, dt).is_err(), "dt = {}", dt);
+        }
+        let (_, h, steps) = epoch_steps(&model, &start, &end, 70.0).unwrap();
+        assert_eq!(steps, 51);
+        assert!((h * steps as f64 - 3600.0).abs() < 1e-9);
+        // Backwards in time: negative step, same count
+        let (_, h, steps) = epoch_steps(&model, &end, &start, 70.0).unwrap();
+        assert_eq!(steps, 51);
+        assert!(h < 0.0);
+    }
+}
@@ -0,0 +1,558 @@
+//! ID: WASM-RK45-001
+//! Requirement: Provide an adaptive Dormand–Prince RK5(4) propagator that picks
+//!   its own step size from an embedded error estimate.
+//! Purpose: Remove the hand-tuned (dt, steps) pair of rk4_propagate so that
+//!   eccentric and circular arcs can be propagated with the same tolerance.
+//! Rationale: The embedded 4th-order solution is free (FSAL), so the step shrinks
+//!   near periapsis and grows near apoapsis without extra force evaluations.
+//! Inputs: state [rx,ry,rz,vx,vy,vz] [m, m/s], tf [s], mu [m³/s²], atol, rtol
+//! Outputs: final state (or every accepted state) plus accepted/rejected counts
+//! References: Dormand & Prince (1980) J. Comp. Appl. Math. 6(1):19-26;
+//!   Hairer, Nørsett & Wanner "Solving ODEs I" §II.4 (step control, initial h)
+
+use std::fmt;
+use std::ops::ControlFlow;
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::forces::{force_model_rhs, ForceModelBuilder};
+use crate::rk4::{two_body_deriv, State6};
+use crate::time::Epoch;
+
+/// Step-size control settings shared by the adaptive integrators.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveOptions {
+    /// Absolute tolerance per component [state units]
+    pub atol: f64,
+    /// Relative tolerance per component [-]
+    pub rtol: f64,
+    /// First trial step [s]; 0 selects one automatically
+    pub h_init: f64,
+    /// Largest allowed |h| [s]; 0 means unbounded
+    pub h_max: f64,
+    /// Limit on attempted (accepted + rejected) steps
+    pub max_steps: u32,
+}
+
+impl AdaptiveOptions {
+    pub fn new(atol: f64, rtol: f64) -> Self {
+        AdaptiveOptions { atol, rtol, h_init: 0.0, h_max: 0.0, max_steps: 1_000_000 }
+    }
+}
+
+/// Step bookkeeping reported by the adaptive integrators.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AdaptiveStats {
+    pub accepted: u32,
+    pub rejected: u32,
+    pub evaluations: u32,
+}
+
+/// Return type of integrator observers: `()` always continues, while
+/// `ControlFlow::Break(())` stops the integration after the current step.
+pub trait StepControl {
+    fn keep_going(self) -> bool;
+}
+
+impl StepControl for () {
+    fn keep_going(self) -> bool { true }
+}
+
+impl StepControl for ControlFlow<()> {
+    fn keep_going(self) -> bool { self.is_continue() }
+}
+
+/// Reasons an adaptive integration can stop before reaching tf.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrationError {
+    InvalidInput(String),
+    StepSizeUnderflow { t: f64 },
+    MaxStepsExceeded { t: f64 },
+}
+
+impl fmt::Display for IntegrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntegrationError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            IntegrationError::StepSizeUnderflow { t } => write!(f, "Step size underflow at t = {} s", t),
+            IntegrationError::MaxStepsExceeded { t } => write!(f, "Maximum step count exceeded at t = {} s", t),
+        }
+    }
+}
+
+impl From<IntegrationError> for JsValue {
+    fn from(e: IntegrationError) -> JsValue {
+        JsValue::from_str(&e.to_string())
+    }
+}
+
+// Dormand–Prince 5(4) tableau. Row 6 of A equals the 5th-order weights, which
+// makes the last stage the first stage of the next step (FSAL).
+const C: [f64; 7] = [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+const A: [[f64; 6]; 7] = [
+    [0.0; 6],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [19372.0 / 6561.0, -25360.0 / 2187.0, 64448.0 / 6561.0, -212.0 / 729.0, 0.0, 0.0],
+    [9017.0 / 3168.0, -355.0 / 33.0, 46732.0 / 5247.0, 49.0 / 176.0, -5103.0 / 18656.0, 0.0],
+    [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0, 11.0 / 84.0],
+];
+/// Difference between the 5th- and 4th-order weights (error estimator).
+const E: [f64; 7] = [
+    71.0 / 57600.0, 0.0, -71.0 / 16695.0, 71.0 / 1920.0,
+    -17253.0 / 339200.0, 22.0 / 525.0, -1.0 / 40.0,
+];
+
+/// Weighted RMS norm of a local error estimate (Hairer's `err`).
+pub(crate) fn error_norm(err: &[f64], y0: &[f64], y1: &[f64], opts: &AdaptiveOptions) -> f64 {
+    let sum: f64 = err.iter().zip(y0).zip(y1)
+        .map(|((e, a), b)| {
+            let sc = opts.atol + opts.rtol * a.abs().max(b.abs());
+            (e / sc) * (e / sc)
+        })
+        .sum();
+    (sum / err.len() as f64).sqrt()
+}
+
+/// Starting step estimate for a method of the given order (Hairer's algorithm).
+/// `f0` must already hold f(t0, y0). Costs one derivative evaluation.
+pub(crate) fn initial_step<F>(
+    f: &mut F,
+    t0: f64,
+    y0: &[f64],
+    f0: &[f64],
+    dir: f64,
+    order: i32,
+    opts: &AdaptiveOptions,
+) -> f64
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+{
+    let n = y0.len() as f64;
+    let rms = |v: &mut dyn Iterator<Item = (f64, f64)>| -> f64 {
+        let s: f64 = v.map(|(x, y)| { let sc = opts.atol + opts.rtol * y.abs(); (x / sc) * (x / sc) }).sum();
+        (s / n).sqrt()
+    };
+    let d0 = rms(&mut y0.iter().map(|&y| (y, y)));
+    let d1 = rms(&mut f0.iter().zip(y0).map(|(&d, &y)| (d, y)));
+    let h0 = if d0 < 1e-5 || d1 < 1e-5 { 1e-6 } else { 0.01 * d0 / d1 };
+
+    let y1: Vec<f64> = y0.iter().zip(f0).map(|(y, d)| y + dir * h0 * d).collect();
+    let mut f1 = vec![0.0; y0.len()];
+    f(t0 + dir * h0, &y1, &mut f1);
+    let d2 = rms(&mut f1.iter().zip(f0).zip(y0).map(|((a, b), &y)| (a - b, y))) / h0;
+
+    let dmax = d1.max(d2);
+    let h1 = if dmax <= 1e-15 {
+        (h0 * 1e-3).max(1e-6)
+    } else {
+        (0.01 / dmax).powf(1.0 / (order as f64 + 1.0))
+    };
+    let h = (100.0 * h0).min(h1);
+    if opts.h_max > 0.0 { h.min(opts.h_max) } else { h }
+}
+
+/// One Dormand–Prince trial step of signed size h from (t, y).
+/// `k[0]` must hold f(t, y); on return `k[6]` holds f(t+h, y_new).
+/// Writes the 5th-order solution to `y_new` and the error estimate to `err`.
+fn dopri5_step<F>(
+    f: &mut F,
+    t: f64,
+    y: &[f64],
+    h: f64,
+    k: &mut [Vec<f64>; 7],
+    y_new: &mut [f64],
+    err: &mut [f64],
+)
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+{
+    for s in 1..7 {
+        for (i, yi) in y_new.iter_mut().enumerate() {
+            let mut acc = 0.0;
+            for (j, a) in A[s][..s].iter().enumerate() {
+                acc += a * k[j][i];
+            }
+            *yi = y[i] + h * acc;
+        }
+        f(t + C[s] * h, y_new, &mut k[s]);
+    }
+    for (i, e) in err.iter_mut().enumerate() {
+        *e = h * (0..7).map(|j| E[j] * k[j][i]).sum::<f64>();
+    }
+}
+
+/// Take one Dormand–Prince step of signed size h without error control.
+/// Used to re-evaluate the solution inside an accepted step (event location).
+pub(crate) fn dopri5_single_step<F>(f: &mut F, t: f64, y: &[f64], h: f64) -> Vec<f64>
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+{
+    let n = y.len();
+    let mut k: [Vec<f64>; 7] = std::array::from_fn(|_| vec![0.0; n]);
+    let mut y_new = vec![0.0; n];
+    let mut err = vec![0.0; n];
+    f(t, y, &mut k[0]);
+    dopri5_step(f, t, y, h, &mut k, &mut y_new, &mut err);
+    y_new
+}
+
+/// Reject settings and endpoints that would make an adaptive integration
+/// return garbage or run to the step limit: NaN t0/tf/y0 stop the loop before
+/// its first step, ±∞ tf never comes within reach.
+pub(crate) fn validate_problem(t0: f64, y0: &[f64], tf: f64, opts: &AdaptiveOptions) -> Result<(), IntegrationError> {
+    if !(opts.atol > 0.0 && opts.rtol >= 0.0) {
+        return Err(IntegrationError::InvalidInput("atol must be > 0 and rtol >= 0".to_string()));
+    }
+    if !t0.is_finite() || !tf.is_finite() {
+        return Err(IntegrationError::InvalidInput(format!("t0 = {} and tf = {} must be finite", t0, tf)));
+    }
+    if let Some(i) = y0.iter().position(|v| !v.is_finite()) {
+        return Err(IntegrationError::InvalidInput(format!("initial state component {} is {}", i, y0[i])));
+    }
+    Ok(())
+}
+
+/// Integrate dy/dt = f(t, y) from t0 to tf with Dormand–Prince 5(4) step control.
+///
+/// `f(t, y, dy)` writes the derivative into `dy`; any state dimension works.
+/// `observer(t, y)` is called at t0 and after every accepted step; returning
+/// `ControlFlow::Break(())` stops early with y at that step.
+/// tf < t0 integrates backward in time.
+pub fn dopri5_integrate<F, O, R>(
+    mut f: F,
+    t0: f64,
+    y0: &[f64],
+    tf: f64,
+    opts: &AdaptiveOptions,
+    mut observer: O,
+) -> Result<(Vec<f64>, AdaptiveStats), IntegrationError>
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+    O: FnMut(f64, &[f64]) -> R,
+    R: StepControl,
+{
+    validate_problem(t0, y0, tf, opts)?;
+
+    let n = y0.len();
+    let mut stats = AdaptiveStats::default();
+    let mut t = t0;
+    let mut y = y0.to_vec();
+    if !observer(t, &y).keep_going() || tf == t0 {
+        return Ok((y, stats));
+    }
+
+    let dir = (tf - t0).signum();
+    let mut k: [Vec<f64>; 7] = std::array::from_fn(|_| vec![0.0; n]);
+    f(t, &y, &mut k[0]);
+    stats.evaluations += 1;
+
+    let mut h = if opts.h_init > 0.0 {
+        opts.h_init
+    } else {
+        stats.evaluations += 1;
+        initial_step(&mut f, t, &y, &k[0], dir, 5, opts)
+    };
+
+    let mut y_new = vec![0.0; n];
+    let mut err = vec![0.0; n];
+    let mut last_rejected = false;
+
+    while (tf - t) * dir > 0.0 {
+        if stats.accepted + stats.rejected >= opts.max_steps {
+            return Err(IntegrationError::MaxStepsExceeded { t });
+        }
+        if opts.h_max > 0.0 {
+            h = h.min(opts.h_max);
+        }
+        if h <= 16.0 * f64::EPSILON * t.abs().max(1.0) {
+            return Err(IntegrationError::StepSizeUnderflow { t });
+        }
+
+        let remaining = (tf - t).abs();
+        let last = h >= remaining;
+        let h_step = dir * if last { remaining } else { h };
+
+        dopri5_step(&mut f, t, &y, h_step, &mut k, &mut y_new, &mut err);
+        stats.evaluations += 6;
+
+        let en = error_norm(&err, &y, &y_new, opts);
+        if !en.is_finite() {
+            stats.rejected += 1;
+            h = h_step.abs() * 0.2;
+            last_rejected = true;
+            continue;
+        }
+
+        let fac = 0.9 * en.powf(-0.2);
+        if en <= 1.0 {
+            t = if last { tf } else { t + h_step };
+            y.copy_from_slice(&y_new);
+            k.swap(0, 6);
+            stats.accepted += 1;
+            if !observer(t, &y).keep_going() {
+                break;
+            }
+
+            let fac = fac.clamp(0.2, 10.0);
+            h = h_step.abs() * if last_rejected { fac.min(1.0) } else { fac };
+            last_rejected = false;
+        } else {
+            stats.rejected += 1;
+            h = h_step.abs() * fac.max(0.2);
+            last_rejected = true;
+        }
+    }
+
+    Ok((y, stats))
+}
+
+/// Two-body right-hand side in the slice form used by the adaptive integrators.
+pub(crate) fn two_body_rhs(mu: f64) -> impl Fn(f64, &[f64], &mut [f64]) {
+    move |_t, y, dy| {
+        let s: State6 = [y[0], y[1], y[2], y[3], y[4], y[5]];
+        dy.copy_from_slice(&two_body_deriv(&s, mu));
+    }
+}
+
+pub(crate) fn state6_from_js(state_js: &Float64Array) -> Result<Vec<f64>, JsValue> {
+    let sv = state_js.to_vec();
+    if sv.len() != 6 {
+        return Err(JsValue::from_str(&format!("State must have 6 elements, got {}", sv.len())));
+    }
+    Ok(sv)
+}
+
+/// Result of an adaptive propagation.
+/// `states` is flat [rx₀,ry₀,...,vz₀, rx₁,...] with one row per entry of `times`.
+#[wasm_bindgen]
+pub struct AdaptivePropagation {
+    times: Vec<f64>,
+    states: Vec<f64>,
+    dim: usize,
+    stats: AdaptiveStats,
+}
+
+impl AdaptivePropagation {
+    pub(crate) fn new(times: Vec<f64>, states: Vec<f64>, dim: usize, stats: AdaptiveStats) -> Self {
+        AdaptivePropagation { times, states, dim, stats }
+    }
+}
+
+#[wasm_bindgen]
+impl AdaptivePropagation {
+    /// Epochs of the stored states [s]
+    #[wasm_bindgen(getter)]
+    pub fn times(&self) -> Float64Array {
+        Float64Array::from(self.times.as_slice())
+    }
+
+    /// Stored states, flat row-major
+    #[wasm_bindgen(getter)]
+    pub fn states(&self) -> Float64Array {
+        Float64Array::from(self.states.as_slice())
+    }
+
+    /// State at the final time
+    #[wasm_bindgen(getter)]
+    pub fn final_state(&self) -> Float64Array {
+        Float64Array::from(&self.states[self.states.len() - self.dim..])
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn accepted_steps(&self) -> u32 { self.stats.accepted }
+
+    #[wasm_bindgen(getter)]
+    pub fn rejected_steps(&self) -> u32 { self.stats.rejected }
+
+    #[wasm_bindgen(getter)]
+    pub fn function_evaluations(&self) -> u32 { self.stats.evaluations }
+}
+
+fn rk45_run<F>(
+    rhs: F,
+    state_js: &Float64Array,
+    t0: f64,
+    tf: f64,
+    atol: f64,
+    rtol: f64,
+    keep_all: bool,
+) -> Result<AdaptivePropagation, JsValue>
+where
+    F: FnMut(f64, &[f64], &mut [f64]),
+{
+    let y0 = state6_from_js(state_js)?;
+    let mut times = vec![0.0];
+    let mut states = y0.clone();
+    let opts = AdaptiveOptions::new(atol, rtol);
+
+    // Reported times are relative to t0
+    let (y, stats) = dopri5_integrate(rhs, t0, &y0, tf, &opts, |t, y| {
+        if keep_all && t != t0 {
+            times.push(t - t0);
+            states.extend_from_slice(y);
+        }
+    })?;
+
+    if !keep_all {
+        times.push(tf - t0);
+        states.extend_from_slice(&y);
+    }
+    Ok(AdaptivePropagation::new(times, states, 6, stats))
+}
+
+/// Propagate a two-body state from t = 0 to `tf` with Dormand–Prince 5(4).
+/// Returns the initial and final states; tf < 0 propagates backward.
+#[wasm_bindgen]
+pub fn rk45_propagate(
+    state_js: &Float64Array,
+    tf: f64,
+    mu: f64,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    rk45_run(two_body_rhs(mu), state_js, 0.0, tf, atol, rtol, false)
+}
+
+/// Propagate and return the state after every accepted step.
+#[wasm_bindgen]
+pub fn rk45_trajectory(
+    state_js: &Float64Array,
+    tf: f64,
+    mu: f64,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    rk45_run(two_body_rhs(mu), state_js, 0.0, tf, atol, rtol, true)
+}
+
+/// rk45_propagate under a ForceModelBuilder's dynamics.
+#[wasm_bindgen]
+pub fn rk45_propagate_model(
+    state_js: &Float64Array,
+    tf: f64,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    rk45_run(force_model_rhs(model.model(), model.params()), state_js, 0.0, tf, atol, rtol, false)
+}
+
+/// rk45_trajectory under a ForceModelBuilder's dynamics.
+#[wasm_bindgen]
+pub fn rk45_trajectory_model(
+    state_js: &Float64Array,
+    tf: f64,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    rk45_run(force_model_rhs(model.model(), model.params()), state_js, 0.0, tf, atol, rtol, true)
+}
+
+/// rk45_propagate_model from `start` to `end`; model time is measured from the
+/// builder's epoch and reported times are seconds since `start`.
+#[wasm_bindgen]
+pub fn rk45_propagate_epoch(
+    state_js: &Float64Array,
+    start: &Epoch,
+    end: &Epoch,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    let (t0, tf) = (model.model_time(start), model.model_time(end));
+    rk45_run(force_model_rhs(model.model(), model.params()), state_js, t0, tf, atol, rtol, false)
+}
+
+/// rk45_trajectory_model from `start` to `end` (see rk45_propagate_epoch).
+#[wasm_bindgen]
+pub fn rk45_trajectory_epoch(
+    state_js: &Float64Array,
+    start: &Epoch,
+    end: &Epoch,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+) -> Result<AdaptivePropagation, JsValue> {
+    let (t0, tf) = (model.model_time(start), model.model_time(end));
+    rk45_run(force_model_rhs(model.model(), model.params()), state_js, t0, tf, atol, rtol, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MU: f64 = 3.986004418e14;
+
+    fn period(a: f64) -> f64 {
+        2.0 * std::f64::consts::PI * (a * a * a / MU).sqrt()
+    }
+
+    #[test]
+    fn circular_orbit_closes_after_one_period() {
+        let r = 7.0e6;
+        let v = (MU / r).sqrt();
+        let y0 = [r, 0.0, 0.0, 0.0, v, 0.0];
+        let opts = AdaptiveOptions::new(1e-6, 1e-12);
+        let (y, stats) = dopri5_integrate(two_body_rhs(MU), 0.0, &y0, period(r), &opts, |_, _| {}).unwrap();
+        let dr = ((y[0] - y0[0]).powi(2) + (y[1] - y0[1]).powi(2) + (y[2] - y0[2]).powi(2)).sqrt();
+        assert!(dr < 1.0, "position error {} m", dr);
+        assert!(stats.accepted > 0);
+    }
+
+    #[test]
+    fn eccentric_orbit_uses_smaller_steps_at_periapsis() {
+        let rp = 6.778e6;
+        let ra = 4.0e7;
+        let a = 0.5 * (rp + ra);
+        let vp = (MU * (2.0 / rp - 1.0 / a)).sqrt();
+        let y0 = [rp, 0.0, 0.0, 0.0, vp, 0.0];
+        let opts = AdaptiveOptions::new(1e-6, 1e-10);
+
+        let mut samples: Vec<(f64, f64)> = Vec::new();
+        let (y, _) = dopri5_integrate(two_body_rhs(MU), 0.0, &y0, period(a), &opts, |t, y| {
+            samples.push((t, (y[0] * y[0] + y[1] * y[1]).sqrt()));
+        }).unwrap();
+
+        let steps: Vec<(f64, f64)> = samples.windows(2).map(|w| (w[1].0 - w[0].0, w[0].1)).collect();
+        let near_peri = steps.iter().find(|(_, r)| *r < 1.1 * rp).unwrap().0;
+        let near_apo = steps.iter().find(|(_, r)| *r > 0.9 * ra).unwrap().0;
+        assert!(near_apo > 5.0 * near_peri);
+
+        let energy = |s: &[f64]| {
+            0.5 * (s[3] * s[3] + s[4] * s[4] + s[5] * s[5])
+                - MU / (s[0] * s[0] + s[1] * s[1] + s[2] * s[2]).sqrt()
+        };
+        assert!(((energy(&y) - energy(&y0)) / energy(&y0)).abs() < 1e-8);
+    }
+
+    #[test]
+    fn backward_propagation_returns_to_start() {
+        let y0 = [7.0e6, 1.0e5, -2.0e5, 100.0, 7400.0, 1200.0];
+        let opts = AdaptiveOptions::new(1e-8, 1e-13);
+        let (y1, _) = dopri5_integrate(two_body_rhs(MU), 0.0, &y0, 3000.0, &opts, |_, _| {}).unwrap();
+        let (y2, _) = dopri5_integrate(two_body_rhs(MU), 3000.0, &y1, 0.0, &opts, |_, _| {}).unwrap();
+        for i in 0..3 {
+            assert!((y2[i] - y0[i]).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn non_finite_inputs_are_rejected() {
+        let y0 = [7.0e6, 0.0, 0.0, 0.0, 7500.0, 0.0];
+        let opts = AdaptiveOptions::new(1e-6, 1e-12);
+        let run = |t0: f64, y0: &[f64], tf: f64| dopri5_integrate(two_body_rhs(MU), t0, y0, tf, &opts, |_, _| {});
+        for (t0, tf) in [(0.0, f64::NAN), (0.0, f64::INFINITY), (0.0, f64::NEG_INFINITY), (f64::NAN, 100.0)] {
+            assert!(matches!(run(t0, &y0, tf), Err(IntegrationError::InvalidInput(_))), "t0 {} tf {}", t0, tf);
+        }
+        for bad in [f64::NAN, f64::INFINITY] {
+            let mut y = y0;
+            y[4] = bad;
+            assert!(matches!(run(0.0, &y, 100.0), Err(IntegrationError::InvalidInput(_))), "{}", bad);
+        }
+        assert!(run(0.0, &y0, 100.0).is_ok());
+    }
+}
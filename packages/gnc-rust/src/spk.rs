@@ -0,0 +1,349 @@
+//! ID: WASM-SPK-001
+//! Requirement: Read JPL DAF/SPK binary kernels (.bsp) and evaluate Type 2
+//!   (Chebyshev position) segments for any target/centre pair.
+//! Purpose: DE440-grade Sun and Moon positions for lunar trajectory work, fed
+//!   into the third-body force model in place of the analytic series.
+//! Rationale: Type 2 is the segment type of the planetary DE kernels; each record
+//!   holds Chebyshev coefficients of x, y, z over a fixed interval, so position
+//!   and velocity are one Clenshaw-style recurrence. Arbitrary pairs are resolved
+//!   by walking each body's centre chain up to a shared ancestor (usually the SSB).
+//! Inputs: kernel bytes (little- or big-endian IEEE), NAIF ids, ephemeris time
+//!   ET = TDB seconds past J2000
+//! Outputs: state [m, m/s] in the kernel's J2000/ICRF frame
+//! Limits: only Type 2 segments in frame 1 (J2000) are loaded; others are skipped.
+//! References: NAIF "DAF Required Reading" and "SPK Required Reading" (§Type 2)
+
+use std::path::Path;
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+const RECORD_BYTES: usize = 1024;
+
+/// One Type 2 segment.
+#[derive(Debug, Clone)]
+struct Segment {
+    target: i32,
+    center: i32,
+    start: f64,
+    end: f64,
+    init: f64,
+    intlen: f64,
+    rsize: usize,
+    records: usize,
+    /// records × rsize doubles: [MID, RADIUS, X…, Y…, Z…] per record
+    data: Vec<f64>,
+}
+
+impl Segment {
+    /// State [km, km/s] of target relative to centre.
+    fn state(&self, et: f64) -> [f64; 6] {
+        let idx = (((et - self.init) / self.intlen).floor().max(0.0) as usize).min(self.records - 1);
+        let rec = &self.data[idx * self.rsize..(idx + 1) * self.rsize];
+        let (mid, radius) = (rec[0], rec[1]);
+        let n = (self.rsize - 2) / 3;
+        let s = (et - mid) / radius;
+
+        // Tₖ(s) and Tₖ′(s) by the three-term recurrence
+        let mut t = vec![0.0; n];
+        let mut dt = vec![0.0; n];
+        t[0] = 1.0;
+        if n > 1 {
+            t[1] = s;
+            dt[1] = 1.0;
+        }
+        for k in 2..n {
+            t[k] = 2.0 * s * t[k - 1] - t[k - 2];
+            dt[k] = 2.0 * t[k - 1] + 2.0 * s * dt[k - 1] - dt[k - 2];
+        }
+        let mut out = [0.0; 6];
+        for axis in 0..3 {
+            let c = &rec[2 + axis * n..2 + (axis + 1) * n];
+            out[axis] = c.iter().zip(&t).map(|(c, t)| c * t).sum();
+            out[axis + 3] = c.iter().zip(&dt).map(|(c, t)| c * t).sum::<f64>() / radius;
+        }
+        out
+    }
+}
+
+/// A loaded SPK kernel.
+#[derive(Debug, Clone, Default)]
+pub struct SpkKernel {
+    segments: Vec<Segment>,
+}
+
+impl SpkKernel {
+    /// Parse kernel bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < RECORD_BYTES || !(bytes.starts_with(b"DAF/SPK") || bytes.starts_with(b"NAIF/DAF")) {
+            return Err("not a DAF/SPK file".into());
+        }
+        let little = match &bytes[88..96] {
+            b"LTL-IEEE" => true,
+            b"BIG-IEEE" => false,
+            // Pre-N0050 files have no format string: infer from ND = 2
+            _ => i32::from_le_bytes(bytes[8..12].try_into().unwrap()) == 2,
+        };
+        let int_at = |off: usize| -> Result<i32, String> {
+            let b: [u8; 4] = bytes
+                .get(off..off + 4)
+                .ok_or_else(|| format!("byte offset {} beyond end of file", off))?
+                .try_into()
+                .unwrap();
+            Ok(if little { i32::from_le_bytes(b) } else { i32::from_be_bytes(b) })
+        };
+        let word = |addr: usize| -> Result<f64, String> {
+            // DAF addresses are 1-based double-precision word indices
+            let off = addr
+                .checked_sub(1)
+                .and_then(|a| a.checked_mul(8))
+                .ok_or_else(|| format!("invalid DAF address {}", addr))?;
+            let b: [u8; 8] = bytes
+                .get(off..off.saturating_add(8))
+                .ok_or_else(|| format!("address {} beyond end of file", addr))?
+                .try_into()
+                .unwrap();
+            Ok(if little { f64::from_le_bytes(b) } else { f64::from_be_bytes(b) })
+        };
+        // Summary-record control words are doubles holding small integers
+        let count_word = |addr: usize| -> Result<usize, String> {
+            let w = word(addr)?;
+            if !(w >= 0.0 && w.fract() == 0.0 && w <= u32::MAX as f64) {
+                return Err(format!("invalid control word {} at address {}", w, addr));
+            }
+            Ok(w as usize)
+        };
+
+        let (nd, ni) = (int_at(8)?, int_at(12)?);
+        if nd != 2 || ni != 6 {
+            return Err(format!("unexpected SPK summary format ND={} NI={}", nd, ni));
+        }
+        let summary_words = 5; // ND + (NI + 1) / 2
+        let max_summaries = (128 - 3) / summary_words;
+
+        let mut segments = Vec::new();
+        let first_record = int_at(76)?;
+        if first_record < 1 {
+            return Err(format!("invalid first summary record {}", first_record));
+        }
+        let mut record = first_record as usize;
+        let mut visited = std::collections::HashSet::new();
+        while record != 0 {
+            if !visited.insert(record) {
+                return Err(format!("summary record {} links back into the record chain", record));
+            }
+            let base = (record - 1)
+                .checked_mul(128)
+                .map(|w| w + 1)
+                .ok_or_else(|| format!("invalid summary record {}", record))?;
+            let next = count_word(base)?;
+            let count = count_word(base + 2)?;
+            if count > max_summaries {
+                return Err(format!("summary record {} claims {} summaries", record, count));
+            }
+            for i in 0..count {
+                let addr = base + 3 + i * summary_words;
+                let (start, end) = (word(addr)?, word(addr + 1)?);
+                let ints = (addr + 1) * 8; // byte offset of the integer part
+                let mut fields = [0i32; 6];
+                for (k, field) in fields.iter_mut().enumerate() {
+                    *field = int_at(ints + 4 * k)?;
+                }
+                let [target, center, frame, kind, first, last] = fields;
+                if kind != 2 || frame != 1 {
+                    continue;
+                }
+                if first < 1 || last < 4 || last < first {
+                    return Err(format!("segment {} wrt {} has invalid addresses {}..{}", target, center, first, last));
+                }
+                let (first, last) = (first as usize, last as usize);
+                let init = word(last - 3)?;
+                let intlen = word(last - 2)?;
+                let rsize = count_word(last - 1)?;
+                let records = count_word(last)?;
+                // Records fill first..last − 4; the last four words are the trailer
+                let fits = records.checked_mul(rsize).is_some_and(|w| Some(w) == (last - 3).checked_sub(first));
+                if rsize < 5 || records == 0 || !(rsize - 2).is_multiple_of(3) || !fits {
+                    return Err(format!("malformed Type 2 segment {} wrt {}", target, center));
+                }
+                let data = (0..records * rsize).map(|k| word(first + k)).collect::<Result<Vec<_>, _>>()?;
+                segments.push(Segment { target, center, start, end, init, intlen, rsize, records, data });
+            }
+            record = next;
+        }
+        if segments.is_empty() {
+            return Err("kernel has no Type 2 J2000 segments".into());
+        }
+        Ok(SpkKernel { segments })
+    }
+
+    /// Read and parse a local kernel file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let bytes = std::fs::read(path.as_ref()).map_err(|e| format!("{}: {}", path.as_ref().display(), e))?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Chain from `body` towards the root: (ancestor, state of body wrt ancestor [km, km/s]).
+    /// Later segments take precedence, as in SPICE.
+    fn chain(&self, body: i32, et: f64) -> Vec<(i32, [f64; 6])> {
+        let mut out = vec![(body, [0.0; 6])];
+        let mut current = body;
+        let mut acc = [0.0; 6];
+        while let Some(seg) = self
+            .segments
+            .iter()
+            .rev()
+            .find(|s| s.target == current && et >= s.start && et <= s.end)
+        {
+            let s = seg.state(et);
+            for k in 0..6 {
+                acc[k] += s[k];
+            }
+            current = seg.center;
+            out.push((current, acc));
+            if out.len() > 32 {
+                break;
+            }
+        }
+        out
+    }
+
+    /// State of `target` relative to `center` [m, m/s] at ET (TDB s past J2000).
+    pub fn state(&self, target: i32, center: i32, et: f64) -> Result<[f64; 6], String> {
+        let t = self.chain(target, et);
+        let c = self.chain(center, et);
+        for (node, st) in &t {
+            if let Some((_, sc)) = c.iter().find(|(n, _)| n == node) {
+                return Ok(core::array::from_fn(|k| (st[k] - sc[k]) * 1e3));
+            }
+        }
+        Err(format!("no SPK coverage linking {} and {} at ET {}", target, center, et))
+    }
+}
+
+/// SPK kernel handle for JS; load once, then share with force models.
+#[wasm_bindgen]
+pub struct SpkEphemeris {
+    kernel: Arc<SpkKernel>,
+}
+
+impl SpkEphemeris {
+    pub fn kernel(&self) -> Arc<SpkKernel> {
+        Arc::clone(&self.kernel)
+    }
+}
+
+#[wasm_bindgen]
+impl SpkEphemeris {
+    /// Parse kernel bytes (e.g. a fetched .bsp as Uint8Array)
+    #[wasm_bindgen(constructor)]
+    pub fn new(bytes: &[u8]) -> Result<SpkEphemeris, JsValue> {
+        let kernel = SpkKernel::from_bytes(bytes).map_err(|e| JsValue::from_str(&e))?;
+        Ok(SpkEphemeris { kernel: Arc::new(kernel) })
+    }
+
+    /// Number of Type 2 segments loaded
+    #[wasm_bindgen(getter)]
+    pub fn segment_count(&self) -> usize {
+        self.kernel.segment_count()
+    }
+
+    /// State [m, m/s] of NAIF `target` relative to `center` at ET (TDB s past J2000)
+    #[wasm_bindgen]
+    pub fn state(&self, target: i32, center: i32, et: f64) -> Result<Float64Array, JsValue> {
+        let s = self.kernel.state(target, center, et).map_err(|e| JsValue::from_str(&e))?;
+        Ok(Float64Array::from(&s[..]))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const FIXTURE: &[u8] = include_bytes!("../data/test_type2.bsp");
+    const ET0: f64 = 7.5e8;
+    const DAY: f64 = 86400.0;
+
+    /// Closed forms the fixture was fitted to (see data/make_spk_fixture.py), km.
+    pub(crate) fn circle(radius: f64, period_days: f64, phase: f64, inc_deg: f64, et: f64) -> [f64; 6] {
+        let w = 2.0 * std::f64::consts::PI / (period_days * DAY);
+        let (si, ci) = inc_deg.to_radians().sin_cos();
+        let a = w * (et - ET0) + phase;
+        let (x, y) = (radius * a.cos(), radius * a.sin());
+        let (vx, vy) = (-radius * w * a.sin(), radius * w * a.cos());
+        [x, y * ci, y * si, vx, vy * ci, vy * si]
+    }
+
+    pub(crate) fn moon_wrt_emb(et: f64) -> [f64; 6] {
+        circle(379729.0, 27.321661, 0.5, 5.1, et)
+    }
+
+    pub(crate) fn earth_wrt_emb(et: f64) -> [f64; 6] {
+        circle(4671.0, 27.321661, std::f64::consts::PI + 0.5, 5.1, et)
+    }
+
+    #[test]
+    fn type2_chain_matches_fitted_orbits() {
+        let k = SpkKernel::from_bytes(FIXTURE).unwrap();
+        assert_eq!(k.segment_count(), 5);
+        for frac in [0.0, 0.13, 0.5, 0.77, 1.0] {
+            let et = ET0 + frac * 8.0 * DAY;
+            let got = k.state(301, 399, et).unwrap();
+            let (m, e) = (moon_wrt_emb(et), earth_wrt_emb(et));
+            for i in 0..6 {
+                let want = (m[i] - e[i]) * 1e3;
+                let tol = if i < 3 { 1e-3 } else { 1e-6 };
+                assert!((got[i] - want).abs() < tol, "ET {} [{}]: {} vs {}", et, i, got[i], want);
+            }
+            // Via the SSB: Moon wrt Sun = (Moon wrt EMB + EMB wrt SSB) − Sun wrt SSB
+            let got = k.state(301, 10, et).unwrap();
+            let emb = circle(1.495978707e8, 365.25, 1.0, 0.0, et);
+            let sun = circle(7.0e5, 4332.6, 0.3, 1.3, et);
+            let want = (m[0] + emb[0] - sun[0]) * 1e3;
+            assert!((got[0] - want).abs() < 1e-2);
+        }
+        assert!(k.state(301, 399, ET0 + 9.0 * DAY).is_err());
+        assert!(k.state(499, 399, ET0).is_err());
+        assert!(SpkKernel::from_bytes(&FIXTURE[..512]).is_err());
+    }
+
+    /// Fixture copy with the first summary record patched by `edit(bytes, base)`,
+    /// where base is the record's byte offset
+    fn patched(edit: impl Fn(&mut Vec<u8>, usize)) -> Vec<u8> {
+        let mut bytes = FIXTURE.to_vec();
+        assert_eq!(&bytes[88..96], b"LTL-IEEE");
+        let record = i32::from_le_bytes(bytes[76..80].try_into().unwrap()) as usize;
+        edit(&mut bytes, (record - 1) * RECORD_BYTES);
+        bytes
+    }
+
+    #[test]
+    fn malformed_kernels_are_rejected() {
+        // Truncated inside the summary record, the data, or the last segment's
+        // trailer (which ends at word 888)
+        for len in [RECORD_BYTES + 8, 2 * RECORD_BYTES, 4 * RECORD_BYTES, 888 * 8 - 8] {
+            assert!(SpkKernel::from_bytes(&FIXTURE[..len]).is_err(), "truncated to {}", len);
+        }
+        // Negative first summary record
+        let mut bytes = FIXTURE.to_vec();
+        bytes[76..80].copy_from_slice(&(-3i32).to_le_bytes());
+        assert!(SpkKernel::from_bytes(&bytes).is_err());
+        // First summary's end address (6th integer after the two doubles) set to 0
+        let bytes = patched(|b, base| {
+            let last = base + 24 + 16 + 20;
+            b[last..last + 4].copy_from_slice(&0i32.to_le_bytes());
+        });
+        assert!(SpkKernel::from_bytes(&bytes).unwrap_err().contains("invalid addresses"));
+        // Summary record whose NEXT word points back to itself
+        let bytes = patched(|b, base| {
+            let record = (base / RECORD_BYTES + 1) as f64;
+            b[base..base + 8].copy_from_slice(&record.to_le_bytes());
+        });
+        assert!(SpkKernel::from_bytes(&bytes).unwrap_err().contains("links back"));
+    }
+}
@@ -0,0 +1,239 @@
+//! ID: WASM-SRP-001
+//! Requirement: Model solar radiation pressure on a cannonball spacecraft with
+//!   cylindrical or dual-cone (umbra + penumbra) shadowing by occulting bodies.
+//! Purpose: SRP dominates the non-gravitational budget for GEO and cislunar
+//!   spacecraft with large arrays; the shadow fraction also drives the UI's
+//!   eclipse intervals.
+//! Rationale: The cannonball model a = ν P☉ C_r (A/m) (AU/d)² d̂ (d from Sun to
+//!   spacecraft) needs only area, mass and C_r. The conical model treats Sun and
+//!   occulter as discs of apparent radii a, b at separation c and takes the
+//!   occulted fraction from their overlap area; the cylindrical model is its
+//!   a → 0, infinitely distant Sun limit (no penumbra).
+//! Inputs: spacecraft position [m]; Sun and occulter positions [m] in the same
+//!   frame; occulter radii [m]; SpacecraftParams srp_area, mass, reflectivity
+//! Outputs: SRP acceleration [m/s²]; shadow fraction (0 sunlit … 1 umbra)
+//! References: Montenbruck & Gill §3.4.1–3.4.2; Vallado §8.6.4
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::constants::{AU, P_SUN, R_SUN};
+use crate::forces::{ForceModel, ForceModelBuilder, SpacecraftParams};
+use crate::rk4::State6;
+
+/// Shadow geometry used for occultation of the Sun.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowModel {
+    /// Always illuminated
+    None = 0,
+    /// Umbra only, parallel sunlight
+    Cylindrical = 1,
+    /// Umbra and penumbra from the apparent solar and body discs
+    Conical = 2,
+}
+
+/// A spherical body that can block sunlight.
+#[derive(Debug, Clone, Copy)]
+pub struct Occulter {
+    /// Position relative to the central body [m]
+    pub position: [f64; 3],
+    /// Radius [m]
+    pub radius: f64,
+}
+
+#[inline]
+fn sub(a: &[f64], b: &[f64]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+#[inline]
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Fraction of the solar disc hidden by one occulter as seen from `r`.
+pub fn occulted_fraction(model: ShadowModel, r: &[f64], sun: &[f64; 3], body: &Occulter) -> f64 {
+    let s = sub(r, &body.position); // body → spacecraft
+    let d = sub(sun, r); // spacecraft → Sun
+    match model {
+        ShadowModel::None => 0.0,
+        ShadowModel::Cylindrical => {
+            let u = sub(sun, &body.position);
+            let un = dot(&u, &u).sqrt();
+            let along = dot(&s, &u) / un;
+            if along >= 0.0 {
+                return 0.0;
+            }
+            let perp2 = dot(&s, &s) - along * along;
+            if perp2 < body.radius * body.radius { 1.0 } else { 0.0 }
+        }
+        ShadowModel::Conical => {
+            let (sn, dn) = (dot(&s, &s).sqrt(), dot(&d, &d).sqrt());
+            if sn <= body.radius {
+                return 1.0;
+            }
+            let a = (R_SUN / dn).min(1.0).asin();
+            let b = (body.radius / sn).asin();
+            let c = (-dot(&s, &d) / (sn * dn)).clamp(-1.0, 1.0).acos();
+            if c >= a + b {
+                0.0
+            } else if c <= b - a {
+                1.0
+            } else if c <= a - b {
+                // Annular: body disc entirely inside the solar disc
+                (b * b) / (a * a)
+            } else {
+                let x = (c * c + a * a - b * b) / (2.0 * c);
+                let y = (a * a - x * x).max(0.0).sqrt();
+                let area = a * a * (x / a).clamp(-1.0, 1.0).acos()
+                    + b * b * ((c - x) / b).clamp(-1.0, 1.0).acos()
+                    - c * y;
+                area / (std::f64::consts::PI * a * a)
+            }
+        }
+    }
+}
+
+/// Shadow fraction from all occulters (0 sunlit … 1 umbra). Illumination from
+/// separate occulters multiplies, which is exact unless their shadows overlap.
+pub fn shadow_fraction_of(model: ShadowModel, r: &[f64], sun: &[f64; 3], occulters: &[Occulter]) -> f64 {
+    let lit: f64 = occulters.iter().map(|o| 1.0 - occulted_fraction(model, r, sun, o)).product();
+    1.0 - lit
+}
+
+/// Cannonball solar radiation pressure with a fixed Sun position.
+#[derive(Debug, Clone)]
+pub struct SolarRadiationPressure {
+    /// Sun position relative to the central body [m]
+    pub sun: [f64; 3],
+    pub shadow: ShadowModel,
+    pub occulters: Vec<Occulter>,
+}
+
+impl SolarRadiationPressure {
+    /// Sun at `sun`, shadowed by a central body of `body_radius` at the origin.
+    pub fn with_central_body(sun: [f64; 3], shadow: ShadowModel, body_radius: f64) -> Self {
+        SolarRadiationPressure { sun, shadow, occulters: vec![Occulter { position: [0.0; 3], radius: body_radius }] }
+    }
+
+    /// Acceleration for a given Sun position and illumination fraction ν.
+    pub(crate) fn cannonball(r: &[f64], sun: &[f64; 3], illumination: f64, params: &SpacecraftParams) -> [f64; 3] {
+        if illumination == 0.0 {
+            return [0.0; 3];
+        }
+        let d = sub(r, sun); // Sun → spacecraft
+        let dn = dot(&d, &d).sqrt();
+        let k = illumination * P_SUN * params.reflectivity * params.srp_area / params.mass * (AU * AU) / (dn * dn * dn);
+        [k * d[0], k * d[1], k * d[2]]
+    }
+}
+
+impl ForceModel for SolarRadiationPressure {
+    fn acceleration(&self, _t: f64, state: &State6, params: &SpacecraftParams) -> [f64; 3] {
+        let nu = 1.0 - shadow_fraction_of(self.shadow, &state[..3], &self.sun, &self.occulters);
+        Self::cannonball(&state[..3], &self.sun, nu, params)
+    }
+}
+
+fn occulters_from_js(occulters_js: &Float64Array) -> Result<Vec<Occulter>, JsValue> {
+    let v = occulters_js.to_vec();
+    if !v.len().is_multiple_of(4) {
+        return Err(JsValue::from_str("occulters must be [x, y, z, radius] per body"));
+    }
+    Ok(v.chunks_exact(4).map(|o| Occulter { position: [o[0], o[1], o[2]], radius: o[3] }).collect())
+}
+
+fn sun_from_js(sun_js: &Float64Array) -> Result<[f64; 3], JsValue> {
+    let v = sun_js.to_vec();
+    if v.len() != 3 {
+        return Err(JsValue::from_str("sun position must have 3 elements"));
+    }
+    Ok([v[0], v[1], v[2]])
+}
+
+#[wasm_bindgen]
+impl ForceModelBuilder {
+    /// Cannonball SRP (srp_area, mass, C_r from set_spacecraft) with the Sun fixed
+    /// at `sun` [m] and occulting bodies given as [x, y, z, radius] per body [m].
+    #[wasm_bindgen]
+    pub fn add_solar_radiation_pressure(
+        &mut self,
+        sun_js: &Float64Array,
+        shadow: ShadowModel,
+        occulters_js: &Float64Array,
+    ) -> Result<(), JsValue> {
+        let sun = sun_from_js(sun_js)?;
+        let occulters = occulters_from_js(occulters_js)?;
+        self.push(SolarRadiationPressure { sun, shadow, occulters });
+        Ok(())
+    }
+}
+
+/// Shadow fraction (0 sunlit … 1 umbra) at every state of a flat trajectory
+/// [rx,ry,rz,vx,vy,vz, …] as returned by rk4_trajectory; one value per state.
+#[wasm_bindgen]
+pub fn shadow_fraction(
+    states_js: &Float64Array,
+    sun_js: &Float64Array,
+    occulters_js: &Float64Array,
+    model: ShadowModel,
+) -> Result<Float64Array, JsValue> {
+    let states = states_js.to_vec();
+    if !states.len().is_multiple_of(6) {
+        return Err(JsValue::from_str("states length must be a multiple of 6"));
+    }
+    let sun = sun_from_js(sun_js)?;
+    let occulters = occulters_from_js(occulters_js)?;
+    let out: Vec<f64> = states.chunks_exact(6).map(|s| shadow_fraction_of(model, &s[..3], &sun, &occulters)).collect();
+    Ok(Float64Array::from(out.as_slice()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::R_EARTH;
+
+    #[test]
+    fn conical_shadow_has_penumbra_around_cylinder() {
+        let sun = [AU, 0.0, 0.0];
+        let earth = [Occulter { position: [0.0; 3], radius: R_EARTH }];
+        let x = -4.2164e7; // GEO radius, anti-sun side
+        let at = |model, y: f64| shadow_fraction_of(model, &[x, y, 0.0], &sun, &earth);
+
+        assert_eq!(at(ShadowModel::Conical, 0.0), 1.0);
+        assert_eq!(at(ShadowModel::Conical, 2.0 * R_EARTH), 0.0);
+        assert_eq!(at(ShadowModel::None, 0.0), 0.0);
+        // Umbra narrows and penumbra widens behind the body: ~±0.0093·x around R_E
+        let mut prev = 1.0;
+        let mut penumbra = 0;
+        for k in 0..=400 {
+            let y = R_EARTH - 5e5 + 2.5e3 * k as f64;
+            let f = at(ShadowModel::Conical, y);
+            assert!(f <= prev + 1e-12);
+            if f > 0.0 && f < 1.0 {
+                penumbra += 1;
+            }
+            prev = f;
+        }
+        assert!(penumbra > 100, "{} penumbra samples", penumbra);
+        // Half-shadow close to the cylinder edge
+        assert!((at(ShadowModel::Conical, R_EARTH) - 0.5).abs() < 0.05);
+        assert_eq!(at(ShadowModel::Cylindrical, R_EARTH - 1.0), 1.0);
+        assert_eq!(at(ShadowModel::Cylindrical, R_EARTH + 1.0), 0.0);
+    }
+
+    #[test]
+    fn sunlit_acceleration_points_away_from_sun() {
+        let srp = SolarRadiationPressure::with_central_body([AU, 0.0, 0.0], ShadowModel::Conical, R_EARTH);
+        let params = SpacecraftParams { mass: 100.0, srp_area: 20.0, reflectivity: 1.5, ..Default::default() };
+        let geo = 4.2164e7;
+        let a = srp.acceleration(0.0, &[0.0, geo, 0.0, 0.0, 0.0, 0.0], &params);
+        let expected = P_SUN * 1.5 * 20.0 / 100.0;
+        let an = dot(&a, &a).sqrt();
+        assert!((an - expected).abs() < 1e-3 * expected);
+        assert!(a[0] < 0.0);
+        let eclipsed = srp.acceleration(0.0, &[-geo, 0.0, 0.0, 0.0, 0.0, 0.0], &params);
+        assert_eq!(eclipsed, [0.0; 3]);
+    }
+}
@@ -1,29 +1,61 @@
 //! Enhanced Single-Source Shortest Path (SSSP) Algorithm
 //!
 //! High-performance Rust implementation of the breakthrough SSSP algorithm
-//! that achieves better than O(m + n log n) performance on sparse directed graphs.
+//! that achieves better than O(m + n log n) performance on sparse directed graphs
+//! (SsspAlgorithm::Bmssp, see bmssp.rs), alongside binary-heap Dijkstra and a
+//! hierarchical boundary-overlay search.
 //!
 //! Compiled to WebAssembly for use in spacecraft trajectory planning.
 
 use wasm_bindgen::prelude::*;
-use js_sys::{Array, Uint32Array, Float64Array};
-use web_sys::console;
+use js_sys::{Uint32Array, Float64Array};
 use std::collections::BinaryHeap;
 use std::cmp::Ordering;
-use rustc_hash::FxHashMap;
+use std::cell::OnceCell;
+
+use crate::bmssp::bmssp;
+use crate::partition::{cut_edges, partition, Partitioner};
+use crate::point_to_point::{astar, bidirectional_dijkstra, Landmarks, PathSearch};
+use crate::contraction::ContractionHierarchy;
 
 // Import the console.log function from the console module
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
     fn log(s: &str);
 }
 
-// Define a macro for console logging
+// Define a macro for console logging (a no-op off the browser, where the
+// console import does not exist)
+#[cfg(target_arch = "wasm32")]
 macro_rules! console_log {
     ($($t:tt)*) => (log(&format_args!($($t)*).to_string()))
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+macro_rules! console_log {
+    ($($t:tt)*) => {{
+        let _ = format_args!($($t)*);
+    }};
+}
+
+/// Monotonic clock in milliseconds: performance.now() in the browser,
+/// std::time::Instant natively
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|w| w.performance())
+        .map(|p| p.now())
+        .unwrap_or(0.0)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> f64 {
+    static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    START.get_or_init(std::time::Instant::now).elapsed().as_secs_f64() * 1e3
+}
+
 /// Compressed Sparse Row (CSR) graph representation
 #[wasm_bindgen]
 #[derive(Debug, Clone)]
@@ -47,15 +79,7 @@ impl SparseGraph {
         destinations: &Uint32Array,
         weights: &Float64Array
     ) -> SparseGraph {
-        console_log!("Creating sparse graph with {} nodes", node_count);
-
-        SparseGraph {
-            node_count,
-            edge_count: destinations.length() as usize,
-            outgoing_edges: outgoing_edges.to_vec(),
-            destinations: destinations.to_vec(),
-            weights: weights.to_vec(),
-        }
+        SparseGraph::from_csr(node_count, outgoing_edges.to_vec(), destinations.to_vec(), weights.to_vec())
     }
 
     /// Get the number of nodes in the graph
@@ -113,6 +137,51 @@ impl SparseGraph {
     }
 }
 
+impl SparseGraph {
+    /// Create a sparse graph from CSR vectors
+    pub fn from_csr(node_count: usize, outgoing_edges: Vec<u32>, destinations: Vec<u32>, weights: Vec<f64>) -> SparseGraph {
+        console_log!("Creating sparse graph with {} nodes", node_count);
+
+        SparseGraph {
+            node_count,
+            edge_count: destinations.len(),
+            outgoing_edges,
+            destinations,
+            weights,
+        }
+    }
+
+    /// Outgoing (destination, weight) pairs of a node
+    pub(crate) fn edges(&self, node: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.outgoing_edges[node] as usize..self.outgoing_edges[node + 1] as usize;
+        self.destinations[range.clone()].iter().map(|&d| d as usize).zip(self.weights[range].iter().copied())
+    }
+
+    /// The same graph with every edge reversed (incoming edges in CSR form)
+    pub(crate) fn reversed(&self) -> SparseGraph {
+        let n = self.node_count;
+        let mut offsets = vec![0u32; n + 1];
+        for &d in &self.destinations {
+            offsets[d as usize + 1] += 1;
+        }
+        for v in 0..n {
+            offsets[v + 1] += offsets[v];
+        }
+        let mut next = offsets.clone();
+        let mut sources = vec![0u32; self.edge_count];
+        let mut weights = vec![0.0; self.edge_count];
+        for u in 0..n {
+            for (v, w) in self.edges(u) {
+                let slot = next[v] as usize;
+                sources[slot] = u as u32;
+                weights[slot] = w;
+                next[v] += 1;
+            }
+        }
+        SparseGraph::from_csr(n, offsets, sources, weights)
+    }
+}
+
 /// SSSP algorithm result
 #[wasm_bindgen]
 pub struct SSSpResult {
@@ -156,12 +225,116 @@ impl SSSpResult {
     pub fn algorithm_used(&self) -> String { self.algorithm_used.clone() }
 }
 
+/// Single-pair shortest path result
+#[wasm_bindgen]
+pub struct PathResult {
+    distance: f64,
+    path: Vec<u32>,
+    nodes_visited: u32,
+    edges_relaxed: u32,
+    wall_time_ms: f64,
+    algorithm_used: String,
+}
+
+#[wasm_bindgen]
+impl PathResult {
+    /// Whether the target is reachable from the source
+    #[wasm_bindgen(getter)]
+    pub fn found(&self) -> bool { self.distance.is_finite() }
+
+    /// Shortest distance (Infinity if unreachable)
+    #[wasm_bindgen(getter)]
+    pub fn distance(&self) -> f64 { self.distance }
+
+    /// Nodes from source to target (empty if unreachable)
+    #[wasm_bindgen(getter)]
+    pub fn path(&self) -> Uint32Array {
+        let array = Uint32Array::new_with_length(self.path.len() as u32);
+        array.copy_from(&self.path);
+        array
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn nodes_visited(&self) -> u32 { self.nodes_visited }
+
+    #[wasm_bindgen(getter)]
+    pub fn edges_relaxed(&self) -> u32 { self.edges_relaxed }
+
+    #[wasm_bindgen(getter)]
+    pub fn wall_time_ms(&self) -> f64 { self.wall_time_ms }
+
+    #[wasm_bindgen(getter)]
+    pub fn algorithm_used(&self) -> String { self.algorithm_used.clone() }
+}
+
+/// Quality of the clustering built by preprocess()
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct PartitionStats {
+    partitioner: Partitioner,
+    clusters: usize,
+    cut_edges: usize,
+    cut_ratio: f64,
+    balance: f64,
+    boundary_nodes: usize,
+    boundary_ratio: f64,
+}
+
+#[wasm_bindgen]
+impl PartitionStats {
+    #[wasm_bindgen(getter)]
+    pub fn partitioner(&self) -> Partitioner { self.partitioner }
+
+    #[wasm_bindgen(getter)]
+    pub fn clusters(&self) -> usize { self.clusters }
+
+    /// Directed edges between different clusters
+    #[wasm_bindgen(getter)]
+    pub fn cut_edges(&self) -> usize { self.cut_edges }
+
+    /// Cut edges / all edges
+    #[wasm_bindgen(getter)]
+    pub fn cut_ratio(&self) -> f64 { self.cut_ratio }
+
+    /// Largest cluster size / mean cluster size (1.0 = perfectly balanced)
+    #[wasm_bindgen(getter)]
+    pub fn balance(&self) -> f64 { self.balance }
+
+    #[wasm_bindgen(getter)]
+    pub fn boundary_nodes(&self) -> usize { self.boundary_nodes }
+
+    /// Boundary nodes / all nodes
+    #[wasm_bindgen(getter)]
+    pub fn boundary_ratio(&self) -> f64 { self.boundary_ratio }
+}
+
+/// Query algorithm used by EnhancedSSSpSolver::solve
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SsspAlgorithm {
+    /// Binary-heap Dijkstra
+    Dijkstra = 0,
+    /// Boundary-overlay search after preprocess() (Dijkstra until then)
+    Hierarchical = 1,
+    /// Duan et al. bounded multi-source recursion (no preprocessing)
+    Bmssp = 2,
+}
+
+/// ALT landmarks built by preprocess() unless set_landmark_count() says otherwise
+const DEFAULT_LANDMARKS: usize = 8;
+
 /// Enhanced SSSP solver with hierarchical decomposition
 #[wasm_bindgen]
 pub struct EnhancedSSSpSolver {
     graph: SparseGraph,
     hop_sets_built: bool,
     hierarchical_decomposition: Option<HierarchicalDecomposition>,
+    algorithm: SsspAlgorithm,
+    reverse: OnceCell<SparseGraph>,
+    landmark_count: usize,
+    landmarks: Option<Landmarks>,
+    contraction_enabled: bool,
+    contraction: Option<ContractionHierarchy>,
 }
 
 #[wasm_bindgen]
@@ -175,16 +348,56 @@ impl EnhancedSSSpSolver {
             graph,
             hop_sets_built: false,
             hierarchical_decomposition: None,
+            algorithm: SsspAlgorithm::Hierarchical,
+            reverse: OnceCell::new(),
+            landmark_count: DEFAULT_LANDMARKS,
+            landmarks: None,
+            contraction_enabled: false,
+            contraction: None,
         }
     }
 
-    /// Preprocess the graph for accelerated queries
+    /// Select the algorithm used by solve()
+    #[wasm_bindgen]
+    pub fn set_algorithm(&mut self, algorithm: SsspAlgorithm) {
+        self.algorithm = algorithm;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn algorithm(&self) -> SsspAlgorithm {
+        self.algorithm
+    }
+
+    /// Number of ALT landmarks the next preprocess() builds (0 disables ALT)
+    #[wasm_bindgen]
+    pub fn set_landmark_count(&mut self, count: usize) {
+        self.landmark_count = count;
+    }
+
+    /// Also build a contraction hierarchy in the next preprocess() (off by
+    /// default: it pays off only over many solve_to_ch() queries)
     #[wasm_bindgen]
-    pub fn preprocess(&mut self) -> bool {
-        let start_time = web_sys::window()
-            .and_then(|w| w.performance())
-            .map(|p| p.now())
-            .unwrap_or(0.0);
+    pub fn set_contraction_hierarchy(&mut self, enabled: bool) {
+        self.contraction_enabled = enabled;
+    }
+
+    /// Shortcut edges added by the contraction hierarchy (0 if not built)
+    #[wasm_bindgen(getter)]
+    pub fn shortcut_count(&self) -> usize {
+        self.contraction.as_ref().map_or(0, |ch| ch.shortcuts)
+    }
+
+    /// Clustering quality of the last preprocess(), if any
+    #[wasm_bindgen(getter)]
+    pub fn partition_stats(&self) -> Option<PartitionStats> {
+        self.hierarchical_decomposition.as_ref().map(|h| h.stats)
+    }
+
+    /// Preprocess the graph for accelerated queries. The graph is clustered
+    /// with `partitioner` (multilevel by default).
+    #[wasm_bindgen]
+    pub fn preprocess(&mut self, partitioner: Option<Partitioner>) -> bool {
+        let start_time = now_ms();
 
         console_log!("Starting graph preprocessing...");
 
@@ -195,15 +408,25 @@ impl EnhancedSSSpSolver {
         }
 
         // Build hierarchical decomposition
-        self.hierarchical_decomposition = Some(self.build_hierarchical_decomposition());
+        self.hierarchical_decomposition = Some(self.build_hierarchical_decomposition(
+            partitioner.unwrap_or(Partitioner::Multilevel),
+        ));
 
-        // Construct hop sets (simplified for now)
+        // Boundary-to-boundary distance tables are built with the decomposition
         self.hop_sets_built = true;
 
-        let end_time = web_sys::window()
-            .and_then(|w| w.performance())
-            .map(|p| p.now())
-            .unwrap_or(0.0);
+        // ALT landmark distance tables for solve_to_alt
+        self.landmarks = (self.landmark_count > 0)
+            .then(|| Landmarks::build(&self.graph, self.reverse_graph(), self.landmark_count));
+
+        // Contraction hierarchy for solve_to_ch
+        if self.contraction_enabled {
+            self.build_contraction_hierarchy();
+        } else {
+            self.contraction = None;
+        }
+
+        let end_time = now_ms();
 
         console_log!("Preprocessing completed in {:.2}ms", end_time - start_time);
         true
@@ -216,21 +439,16 @@ impl EnhancedSSSpSolver {
             return Err(JsValue::from_str(&format!("Invalid source node: {}", source)));
         }
 
-        let start_time = web_sys::window()
-            .and_then(|w| w.performance())
-            .map(|p| p.now())
-            .unwrap_or(0.0);
+        let start_time = now_ms();
 
-        let result = if self.hop_sets_built && self.hierarchical_decomposition.is_some() {
-            self.solve_enhanced(source)
-        } else {
-            self.solve_dijkstra_optimized(source)
+        let preprocessed = self.hop_sets_built && self.hierarchical_decomposition.is_some();
+        let result = match self.algorithm {
+            SsspAlgorithm::Hierarchical if preprocessed => self.solve_enhanced(source),
+            SsspAlgorithm::Dijkstra | SsspAlgorithm::Hierarchical => self.solve_dijkstra_optimized(source),
+            SsspAlgorithm::Bmssp => Ok(self.solve_bmssp(source)),
         };
 
-        let end_time = web_sys::window()
-            .and_then(|w| w.performance())
-            .map(|p| p.now())
-            .unwrap_or(0.0);
+        let end_time = now_ms();
 
         let mut result = result?;
         result.wall_time_ms = end_time - start_time;
@@ -246,37 +464,186 @@ impl EnhancedSSSpSolver {
         Ok(result)
     }
 
-    /// Solve using enhanced hierarchical algorithm
+    /// Shortest path from source to target by bidirectional Dijkstra
+    #[wasm_bindgen]
+    pub fn solve_to(&self, source: usize, target: usize) -> Result<PathResult, JsValue> {
+        self.path_query(source, target, "bidirectional-dijkstra", |graph| {
+            bidirectional_dijkstra(graph, self.reverse_graph(), source, target)
+        })
+    }
+
+    /// Shortest path from source to target by A*. `heuristic[v]` must never
+    /// exceed the distance from v to target (Infinity: v cannot reach it).
+    #[wasm_bindgen]
+    pub fn solve_to_astar(&self, source: usize, target: usize, heuristic: &Float64Array) -> Result<PathResult, JsValue> {
+        self.solve_to_astar_slice(source, target, &heuristic.to_vec())
+    }
+
+    /// Shortest path from source to target by A* with the landmark (ALT)
+    /// lower bounds built in preprocess()
+    #[wasm_bindgen]
+    pub fn solve_to_alt(&self, source: usize, target: usize) -> Result<PathResult, JsValue> {
+        let landmarks = self.landmarks.as_ref()
+            .ok_or_else(|| JsValue::from_str("Landmarks have not been built: call preprocess() with a landmark count > 0"))?;
+        self.path_query(source, target, "alt", |graph| {
+            astar(graph, source, target, |v| landmarks.lower_bound(v, target))
+        })
+    }
+
+    /// Shortest path from source to target by an upward search in the
+    /// contraction hierarchy built in preprocess()
+    #[wasm_bindgen]
+    pub fn solve_to_ch(&self, source: usize, target: usize) -> Result<PathResult, JsValue> {
+        let hierarchy = self.contraction.as_ref()
+            .ok_or_else(|| JsValue::from_str("Contraction hierarchy has not been built: call set_contraction_hierarchy(true) and preprocess()"))?;
+        self.path_query(source, target, "contraction-hierarchy", |_| hierarchy.query(source, target))
+    }
+
+    /// Solve using enhanced hierarchical algorithm: a local search in the
+    /// source cluster, Dijkstra over the boundary-node overlay, then a local
+    /// expansion of every cluster from its exact entry distances
     fn solve_enhanced(&self, source: usize) -> Result<SSSpResult, JsValue> {
         console_log!("Using enhanced SSSP algorithm");
 
+        let decomposition = self.hierarchical_decomposition.as_ref()
+            .ok_or_else(|| JsValue::from_str("Graph has not been preprocessed"))?;
+        let assignment = &decomposition.cluster_assignment;
+        let overlay = &decomposition.overlay;
         let n = self.graph.node_count;
-        let mut distances = vec![f64::INFINITY; n];
-        let mut predecessors = vec![-1i32; n];
+        let mut stats = SearchStats::default();
 
-        distances[source] = 0.0;
+        // 1. Source cluster only: distances to its boundary nodes
+        let source_cluster = &decomposition.clusters[assignment[source]];
+        let mut local = ClusterSearch::new(n);
+        local.run(&self.graph, assignment, source_cluster.id, &[(source, 0.0, -1)], &mut stats);
 
-        let mut nodes_visited = 0u32;
-        let mut edges_relaxed = 0u32;
-
-        // Phase 1: Solve within clusters using Dijkstra
-        let decomp = self.hierarchical_decomposition.as_ref().unwrap();
-        let source_cluster = decomp.cluster_assignment[source];
+        // 2. Overlay Dijkstra gives exact distances to every boundary node
+        let mut overlay_dist = vec![f64::INFINITY; overlay.nodes.len()];
+        let mut heap = BinaryHeap::new();
+        for &b in &source_cluster.boundary_nodes {
+            let k = overlay.index[b] as usize;
+            if local.distances[b] < overlay_dist[k] {
+                overlay_dist[k] = local.distances[b];
+                heap.push(HeapNode { node: k, distance: local.distances[b] });
+            }
+        }
+        let mut settled = vec![false; overlay.nodes.len()];
+        while let Some(HeapNode { node: k, distance }) = heap.pop() {
+            if settled[k] {
+                continue;
+            }
+            settled[k] = true;
+            stats.nodes_visited += 1;
+            for e in overlay.offsets[k] as usize..overlay.offsets[k + 1] as usize {
+                let (j, w) = (overlay.targets[e] as usize, overlay.weights[e]);
+                stats.edges_relaxed += 1;
+                if distance + w < overlay_dist[j] {
+                    overlay_dist[j] = distance + w;
+                    heap.push(HeapNode { node: j, distance: distance + w });
+                }
+            }
+        }
 
-        // For now, use optimized Dijkstra as the enhanced algorithm core
-        // In a full implementation, this would use the hierarchical decomposition
-        let dijkstra_result = self.solve_dijkstra_optimized(source)?;
+        // 3. Every shortest path enters its final cluster through a cross edge
+        // from a boundary node (or starts at the source), so seeding each
+        // cluster with those entries and searching locally is exact
+        let mut entries: Vec<Vec<(usize, f64, i32)>> = vec![Vec::new(); decomposition.clusters.len()];
+        entries[source_cluster.id].push((source, 0.0, -1));
+        for (k, &u) in overlay.nodes.iter().enumerate() {
+            if overlay_dist[k].is_finite() {
+                for (v, w) in self.graph.edges(u) {
+                    if assignment[v] != assignment[u] {
+                        entries[assignment[v]].push((v, overlay_dist[k] + w, u as i32));
+                    }
+                }
+            }
+        }
+        let mut search = ClusterSearch::new(n);
+        for cluster in &decomposition.clusters {
+            search.run(&self.graph, assignment, cluster.id, &entries[cluster.id], &mut stats);
+        }
 
         Ok(SSSpResult {
-            distances: dijkstra_result.distances,
-            predecessors: dijkstra_result.predecessors,
-            nodes_visited: dijkstra_result.nodes_visited,
-            edges_relaxed: dijkstra_result.edges_relaxed,
+            distances: search.distances,
+            predecessors: search.predecessors,
+            nodes_visited: stats.nodes_visited,
+            edges_relaxed: stats.edges_relaxed,
             wall_time_ms: 0.0, // Will be set by caller
             algorithm_used: "enhanced-sssp".to_string(),
         })
     }
 
+    /// Solve with the bounded multi-source shortest path recursion
+    fn solve_bmssp(&self, source: usize) -> SSSpResult {
+        let (distances, predecessors, stats) = bmssp(&self.graph, source);
+
+        SSSpResult {
+            distances,
+            predecessors,
+            nodes_visited: stats.nodes_visited,
+            edges_relaxed: stats.edges_relaxed,
+            wall_time_ms: 0.0, // Will be set by caller
+            algorithm_used: "bmssp".to_string(),
+        }
+    }
+
+    /// Build the contraction hierarchy for solve_to_ch
+    fn build_contraction_hierarchy(&mut self) {
+        console_log!("Building contraction hierarchy...");
+        self.contraction = Some(ContractionHierarchy::build(&self.graph));
+    }
+
+    /// Reverse CSR for backward searches, built on first use
+    fn reverse_graph(&self) -> &SparseGraph {
+        self.reverse.get_or_init(|| self.graph.reversed())
+    }
+
+    fn solve_to_astar_slice(&self, source: usize, target: usize, heuristic: &[f64]) -> Result<PathResult, JsValue> {
+        if heuristic.len() != self.graph.node_count {
+            return Err(JsValue::from_str(&format!(
+                "Heuristic has {} entries, expected {}", heuristic.len(), self.graph.node_count
+            )));
+        }
+        if heuristic.iter().any(|h| h.is_nan() || *h < 0.0) {
+            return Err(JsValue::from_str("Heuristic values must be non-negative (Infinity allowed)"));
+        }
+        self.path_query(source, target, "astar", |graph| astar(graph, source, target, |v| heuristic[v]))
+    }
+
+    /// Validate the endpoints, time `search` and wrap its result
+    fn path_query(
+        &self,
+        source: usize,
+        target: usize,
+        algorithm: &str,
+        search: impl FnOnce(&SparseGraph) -> PathSearch,
+    ) -> Result<PathResult, JsValue> {
+        if source >= self.graph.node_count {
+            return Err(JsValue::from_str(&format!("Invalid source node: {}", source)));
+        }
+        if target >= self.graph.node_count {
+            return Err(JsValue::from_str(&format!("Invalid target node: {}", target)));
+        }
+
+        let start_time = now_ms();
+        let PathSearch { distance, path, stats } = search(&self.graph);
+        let wall_time_ms = now_ms() - start_time;
+
+        console_log!(
+            "Path {} -> {} solved in {:.2}ms using {} ({} nodes visited)",
+            source, target, wall_time_ms, algorithm, stats.nodes_visited
+        );
+
+        Ok(PathResult {
+            distance,
+            path,
+            nodes_visited: stats.nodes_visited,
+            edges_relaxed: stats.edges_relaxed,
+            wall_time_ms,
+            algorithm_used: algorithm.to_string(),
+        })
+    }
+
     /// Optimized Dijkstra implementation with binary heap
     fn solve_dijkstra_optimized(&self, source: usize) -> Result<SSSpResult, JsValue> {
         let n = self.graph.node_count;
@@ -342,42 +709,18 @@ impl EnhancedSSSpSolver {
     }
 
     /// Build hierarchical decomposition for enhanced algorithm
-    fn build_hierarchical_decomposition(&self) -> HierarchicalDecomposition {
-        console_log!("Building hierarchical decomposition...");
+    fn build_hierarchical_decomposition(&self, partitioner: Partitioner) -> HierarchicalDecomposition {
+        console_log!("Building hierarchical decomposition ({:?})...", partitioner);
 
         let n = self.graph.node_count;
-        let mut cluster_assignment = vec![0usize; n];
-        let mut clusters = Vec::new();
-
-        // Simple clustering based on graph structure
-        // In practice, would use more sophisticated methods like METIS
         let max_cluster_size = std::cmp::max(32, (n as f64).sqrt() as usize);
-        let mut current_cluster = 0;
-        let mut cluster_nodes = Vec::new();
-
-        for node in 0..n {
-            cluster_nodes.push(node);
-            cluster_assignment[node] = current_cluster;
-
-            if cluster_nodes.len() >= max_cluster_size {
-                clusters.push(Cluster {
-                    id: current_cluster,
-                    nodes: cluster_nodes.clone(),
-                    boundary_nodes: Vec::new(), // Will be computed later
-                });
-
-                cluster_nodes.clear();
-                current_cluster += 1;
-            }
-        }
+        let (cluster_assignment, count) = partition(&self.graph, n.div_ceil(max_cluster_size), partitioner);
 
-        // Add remaining nodes to final cluster
-        if !cluster_nodes.is_empty() {
-            clusters.push(Cluster {
-                id: current_cluster,
-                nodes: cluster_nodes,
-                boundary_nodes: Vec::new(),
-            });
+        let mut clusters: Vec<Cluster> = (0..count)
+            .map(|id| Cluster { id, nodes: Vec::new(), boundary_nodes: Vec::new() })
+            .collect();
+        for (node, &cluster) in cluster_assignment.iter().enumerate() {
+            clusters[cluster].nodes.push(node);
         }
 
         // Identify boundary nodes
@@ -385,14 +728,72 @@ impl EnhancedSSSpSolver {
 
         console_log!("Created {} clusters", clusters.len());
 
+        let overlay = self.build_overlay(&clusters, &cluster_assignment);
+
+        let largest = clusters.iter().map(|c| c.nodes.len()).max().unwrap_or(0);
+        let cut = cut_edges(&self.graph, &cluster_assignment);
+        let stats = PartitionStats {
+            partitioner,
+            clusters: clusters.len(),
+            cut_edges: cut,
+            cut_ratio: if self.graph.edge_count > 0 { cut as f64 / self.graph.edge_count as f64 } else { 0.0 },
+            balance: if n > 0 { largest as f64 * clusters.len() as f64 / n as f64 } else { 1.0 },
+            boundary_nodes: overlay.nodes.len(),
+            boundary_ratio: if n > 0 { overlay.nodes.len() as f64 / n as f64 } else { 0.0 },
+        };
+
         HierarchicalDecomposition {
             clusters,
             cluster_assignment,
+            overlay,
+            stats,
         }
     }
 
+    /// Build the boundary-node overlay: a cluster-restricted search from every
+    /// boundary node gives its intra-cluster distances to the other boundary
+    /// nodes, and cross-cluster edges are copied as they are
+    fn build_overlay(&self, clusters: &[Cluster], assignment: &[usize]) -> OverlayGraph {
+        let n = self.graph.node_count;
+        let mut index = vec![u32::MAX; n];
+        let nodes: Vec<usize> = clusters.iter().flat_map(|c| c.boundary_nodes.iter().copied()).collect();
+        for (k, &b) in nodes.iter().enumerate() {
+            index[b] = k as u32;
+        }
+
+        let mut offsets = Vec::with_capacity(nodes.len() + 1);
+        let mut targets = Vec::new();
+        let mut weights = Vec::new();
+        let mut search = ClusterSearch::new(n);
+        let mut stats = SearchStats::default();
+        for cluster in clusters {
+            for &b in &cluster.boundary_nodes {
+                offsets.push(targets.len() as u32);
+                search.run(&self.graph, assignment, cluster.id, &[(b, 0.0, -1)], &mut stats);
+                for &other in &cluster.boundary_nodes {
+                    if other != b && search.distances[other].is_finite() {
+                        targets.push(index[other]);
+                        weights.push(search.distances[other]);
+                    }
+                }
+                for (v, w) in self.graph.edges(b) {
+                    if assignment[v] != cluster.id {
+                        targets.push(index[v]);
+                        weights.push(w);
+                    }
+                }
+                search.reset(&cluster.nodes);
+            }
+        }
+        offsets.push(targets.len() as u32);
+
+        console_log!("Overlay graph: {} boundary nodes, {} edges", nodes.len(), targets.len());
+
+        OverlayGraph { index, nodes, offsets, targets, weights }
+    }
+
     /// Identify boundary nodes between clusters
-    fn identify_boundary_nodes(&self, clusters: &mut Vec<Cluster>, assignment: &[usize]) {
+    fn identify_boundary_nodes(&self, clusters: &mut [Cluster], assignment: &[usize]) {
         let mut boundary_set = std::collections::HashSet::new();
 
         for cluster in clusters.iter() {
@@ -405,7 +806,6 @@ impl EnhancedSSSpSolver {
                     if assignment[neighbor] != assignment[node] {
                         boundary_set.insert(node);
                         boundary_set.insert(neighbor);
-                        break;
                     }
                 }
             }
@@ -456,6 +856,8 @@ impl PartialOrd for HeapNode {
 struct HierarchicalDecomposition {
     clusters: Vec<Cluster>,
     cluster_assignment: Vec<usize>,
+    overlay: OverlayGraph,
+    stats: PartitionStats,
 }
 
 #[derive(Debug)]
@@ -465,6 +867,79 @@ struct Cluster {
     boundary_nodes: Vec<usize>,
 }
 
+/// CSR graph over the boundary nodes only
+#[derive(Debug)]
+struct OverlayGraph {
+    index: Vec<u32>, // Node -> overlay index, u32::MAX if not a boundary node
+    nodes: Vec<usize>, // Overlay index -> node
+    offsets: Vec<u32>,
+    targets: Vec<u32>,
+    weights: Vec<f64>,
+}
+
+/// Work counters reported in SSSpResult
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct SearchStats {
+    pub(crate) nodes_visited: u32,
+    pub(crate) edges_relaxed: u32,
+}
+
+/// Dijkstra confined to one cluster, reusing full-size label arrays across
+/// runs (clusters are disjoint, so runs on different clusters never interact)
+struct ClusterSearch {
+    distances: Vec<f64>,
+    predecessors: Vec<i32>,
+    settled: Vec<bool>,
+}
+
+impl ClusterSearch {
+    fn new(n: usize) -> Self {
+        ClusterSearch {
+            distances: vec![f64::INFINITY; n],
+            predecessors: vec![-1; n],
+            settled: vec![false; n],
+        }
+    }
+
+    /// Search from (node, distance, predecessor) seeds without leaving `cluster`
+    fn run(&mut self, graph: &SparseGraph, assignment: &[usize], cluster: usize, seeds: &[(usize, f64, i32)], stats: &mut SearchStats) {
+        let mut heap = BinaryHeap::new();
+        for &(node, distance, pred) in seeds {
+            if distance < self.distances[node] {
+                self.distances[node] = distance;
+                self.predecessors[node] = pred;
+                heap.push(HeapNode { node, distance });
+            }
+        }
+        while let Some(HeapNode { node, distance }) = heap.pop() {
+            if self.settled[node] {
+                continue;
+            }
+            self.settled[node] = true;
+            stats.nodes_visited += 1;
+            for (v, w) in graph.edges(node) {
+                if assignment[v] != cluster {
+                    continue;
+                }
+                stats.edges_relaxed += 1;
+                if distance + w < self.distances[v] {
+                    self.distances[v] = distance + w;
+                    self.predecessors[v] = node as i32;
+                    heap.push(HeapNode { node: v, distance: distance + w });
+                }
+            }
+        }
+    }
+
+    fn reset(&mut self, nodes: &[usize]) {
+        for &v in nodes {
+            self.distances[v] = f64::INFINITY;
+            self.predecessors[v] = -1;
+            self.settled[v] = false;
+        }
+    }
+}
+
 /// Trajectory graph builder for spacecraft planning
 #[wasm_bindgen]
 pub struct TrajectoryGraphBuilder;
@@ -496,8 +971,8 @@ impl TrajectoryGraphBuilder {
         let mut edge_idx = 0u32;
 
         // Generate nodes and edges for trajectory planning
-        for node in 0..node_count {
-            outgoing_edges[node] = edge_idx;
+        for (node, offset) in outgoing_edges.iter_mut().take(node_count).enumerate() {
+            *offset = edge_idx;
 
             // Generate possible maneuvers from this state
             let maneuvers = Self::generate_maneuvers_for_node(
@@ -520,19 +995,9 @@ impl TrajectoryGraphBuilder {
 
         outgoing_edges[node_count] = edge_idx;
 
-        // Convert to JavaScript arrays
-        let outgoing_js = Uint32Array::new_with_length(outgoing_edges.len() as u32);
-        outgoing_js.copy_from(&outgoing_edges);
-
-        let dest_js = Uint32Array::new_with_length(destinations.len() as u32);
-        dest_js.copy_from(&destinations);
-
-        let weights_js = Float64Array::new_with_length(weights.len() as u32);
-        weights_js.copy_from(&weights);
-
         console_log!("Generated graph with {} nodes and {} edges", node_count, destinations.len());
 
-        SparseGraph::new(node_count, &outgoing_js, &dest_js, &weights_js)
+        SparseGraph::from_csr(node_count, outgoing_edges, destinations, weights)
     }
 
     /// Generate possible spacecraft maneuvers from a given state node
@@ -555,50 +1020,47 @@ impl TrajectoryGraphBuilder {
     }
 }
 
-/// Performance benchmark runner
+/// Performance benchmark runner. With `contraction_hierarchy` set, the
+/// solver also builds a contraction hierarchy (timed on its own, after
+/// preprocess()) and the result adds point-to-point query times from `source`
+/// to `iterations` spread-out targets: Dijkstra stopped at the target,
+/// bidirectional Dijkstra and the hierarchy, with the hierarchy's build time
+/// and the number of queries after which it pays for itself. The build can
+/// take seconds on large graphs, so it is off by default.
 #[wasm_bindgen]
 pub fn benchmark_algorithms(
     graph: &SparseGraph,
     source: usize,
-    iterations: usize
+    iterations: usize,
+    contraction_hierarchy: Option<bool>,
 ) -> js_sys::Object {
     console_log!("Running performance benchmark with {} iterations", iterations);
 
     let mut solver = EnhancedSSSpSolver::new(graph.clone());
-    solver.preprocess();
+    let start_preprocess = now_ms();
+    solver.preprocess(None);
+    let preprocess_time = now_ms() - start_preprocess;
 
     // Benchmark enhanced algorithm
-    let start_enhanced = web_sys::window()
-        .and_then(|w| w.performance())
-        .map(|p| p.now())
-        .unwrap_or(0.0);
+    let start_enhanced = now_ms();
 
     for _ in 0..iterations {
         let _ = solver.solve(source);
     }
 
-    let end_enhanced = web_sys::window()
-        .and_then(|w| w.performance())
-        .map(|p| p.now())
-        .unwrap_or(0.0);
+    let end_enhanced = now_ms();
 
     let enhanced_time = (end_enhanced - start_enhanced) / iterations as f64;
 
     // Benchmark regular Dijkstra
-    let start_dijkstra = web_sys::window()
-        .and_then(|w| w.performance())
-        .map(|p| p.now())
-        .unwrap_or(0.0);
+    let start_dijkstra = now_ms();
 
     let solver_unprocessed = EnhancedSSSpSolver::new(graph.clone());
     for _ in 0..iterations {
         let _ = solver_unprocessed.solve(source);
     }
 
-    let end_dijkstra = web_sys::window()
-        .and_then(|w| w.performance())
-        .map(|p| p.now())
-        .unwrap_or(0.0);
+    let end_dijkstra = now_ms();
 
     let dijkstra_time = (end_dijkstra - start_dijkstra) / iterations as f64;
 
@@ -614,7 +1076,375 @@ pub fn benchmark_algorithms(
     js_sys::Reflect::set(&result, &"enhancedTimeMs".into(), &enhanced_time.into()).unwrap();
     js_sys::Reflect::set(&result, &"dijkstraTimeMs".into(), &dijkstra_time.into()).unwrap();
     js_sys::Reflect::set(&result, &"speedupFactor".into(), &speedup.into()).unwrap();
+    js_sys::Reflect::set(&result, &"preprocessTimeMs".into(), &preprocess_time.into()).unwrap();
     js_sys::Reflect::set(&result, &"iterations".into(), &(iterations as f64).into()).unwrap();
 
+    if contraction_hierarchy == Some(true) && source < graph.node_count {
+        solver.set_contraction_hierarchy(true);
+        let ch = benchmark_contraction_hierarchy(&mut solver, source, iterations);
+        console_log!(
+            "Point-to-point: Dijkstra={:.3}ms, Bidirectional={:.3}ms, CH={:.3}ms ({:.1}x after {:.1}ms preprocessing, {} shortcuts)",
+            ch.p2p_dijkstra_ms, ch.bidirectional_ms, ch.query_ms, ch.p2p_dijkstra_ms / ch.query_ms, ch.preprocess_ms, ch.shortcuts
+        );
+        for (key, value) in [
+            ("p2pDijkstraTimeMs", ch.p2p_dijkstra_ms),
+            ("bidirectionalTimeMs", ch.bidirectional_ms),
+            ("chQueryTimeMs", ch.query_ms),
+            ("chPreprocessTimeMs", ch.preprocess_ms),
+            ("chSpeedupFactor", ch.p2p_dijkstra_ms / ch.query_ms),
+            ("chBreakEvenQueries", ch.break_even_queries()),
+            ("chShortcuts", ch.shortcuts as f64),
+        ] {
+            js_sys::Reflect::set(&result, &key.into(), &value.into()).unwrap();
+        }
+    }
+
     result
 }
+
+/// Point-to-point timings of benchmark_algorithms' contraction hierarchy run
+#[derive(Debug, Clone, Copy)]
+struct ContractionBenchmark {
+    p2p_dijkstra_ms: f64,
+    bidirectional_ms: f64,
+    query_ms: f64,
+    preprocess_ms: f64,
+    shortcuts: usize,
+}
+
+impl ContractionBenchmark {
+    /// Queries after which the hierarchy has paid for its build
+    fn break_even_queries(&self) -> f64 {
+        if self.query_ms < self.p2p_dijkstra_ms {
+            (self.preprocess_ms / (self.p2p_dijkstra_ms - self.query_ms)).ceil()
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+/// Build the solver's contraction hierarchy and time queries from `source`
+/// (valid) to `iterations` spread-out targets
+fn benchmark_contraction_hierarchy(solver: &mut EnhancedSSSpSolver, source: usize, iterations: usize) -> ContractionBenchmark {
+    let iterations = iterations.max(1);
+    let n = solver.graph.node_count;
+    let targets: Vec<usize> = (1..=iterations).map(|k| (source + k * n / (iterations + 1)) % n).collect();
+    let zeros = vec![0.0; n];
+    // Keep the lazy reverse graph out of the bidirectional timings
+    solver.reverse_graph();
+
+    let start_ch = now_ms();
+    solver.build_contraction_hierarchy();
+    let preprocess_ms = now_ms() - start_ch;
+
+    let mean_query_ms = |query: &dyn Fn(usize) -> Result<PathResult, JsValue>| {
+        let start = now_ms();
+        for &target in &targets {
+            let _ = query(target);
+        }
+        (now_ms() - start) / iterations as f64
+    };
+    let solver = &*solver;
+    ContractionBenchmark {
+        p2p_dijkstra_ms: mean_query_ms(&|t| solver.solve_to_astar_slice(source, t, &zeros)),
+        bidirectional_ms: mean_query_ms(&|t| solver.solve_to(source, t)),
+        query_ms: mean_query_ms(&|t| solver.solve_to_ch(source, t)),
+        preprocess_ms,
+        shortcuts: solver.shortcut_count(),
+    }
+}
+
+/// Mean Dijkstra and BMSSP query times on TrajectoryGraphBuilder graphs, one
+/// row [nodes, edges, dijkstra_ms, bmssp_ms] per position resolution
+pub(crate) fn bmssp_crossover(
+    position_resolutions: &[u32],
+    velocity_resolution: usize,
+    time_steps: usize,
+    iterations: usize,
+) -> Vec<[f64; 4]> {
+    let iterations = iterations.max(1);
+    position_resolutions.iter().map(|&resolution| {
+        let graph = TrajectoryGraphBuilder::build_trajectory_graph(
+            resolution as usize, velocity_resolution, time_steps, 1.0, 300.0
+        );
+        let mut solver = EnhancedSSSpSolver::new(graph);
+        let mut time = |algorithm| {
+            solver.set_algorithm(algorithm);
+            let start = now_ms();
+            for _ in 0..iterations {
+                let _ = solver.solve(0);
+            }
+            (now_ms() - start) / iterations as f64
+        };
+        let dijkstra_ms = time(SsspAlgorithm::Dijkstra);
+        let bmssp_ms = time(SsspAlgorithm::Bmssp);
+        [solver.graph.node_count as f64, solver.graph.edge_count as f64, dijkstra_ms, bmssp_ms]
+    }).collect()
+}
+
+/// Dijkstra vs BMSSP crossover benchmark over growing trajectory graphs.
+/// Returns flat rows [nodes, edges, dijkstraTimeMs, bmsspTimeMs].
+#[wasm_bindgen]
+pub fn benchmark_bmssp_crossover(
+    position_resolutions: &Uint32Array,
+    velocity_resolution: usize,
+    time_steps: usize,
+    iterations: usize
+) -> Float64Array {
+    let rows = bmssp_crossover(&position_resolutions.to_vec(), velocity_resolution, time_steps, iterations);
+    for row in &rows {
+        console_log!(
+            "{} nodes: Dijkstra={:.2}ms, BMSSP={:.2}ms, ratio={:.2}",
+            row[0], row[2], row[3], row[3] / row[2]
+        );
+    }
+    Float64Array::from(rows.concat().as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partition::XorShift;
+
+    /// Random directed graph: mostly local edges (so clusters matter) plus a
+    /// few long-range ones, with some zero weights
+    fn random_graph(rng: &mut XorShift, n: usize, out_degree: usize) -> SparseGraph {
+        let mut offsets = vec![0u32];
+        let mut destinations = Vec::new();
+        let mut weights = Vec::new();
+        for u in 0..n {
+            for _ in 0..rng.below(2 * out_degree + 1) {
+                let v = if rng.below(5) == 0 { rng.below(n) } else { (u + rng.below(40)) % n };
+                destinations.push(v as u32);
+                weights.push(if rng.below(20) == 0 { 0.0 } else { 100.0 * rng.unit() });
+            }
+            offsets.push(destinations.len() as u32);
+        }
+        SparseGraph::from_csr(n, offsets, destinations, weights)
+    }
+
+    /// side × side 4-neighbour grid, edges both ways with weights in [1, 2)
+    fn grid_graph(side: usize, seed: u64) -> SparseGraph {
+        let mut rng = XorShift(seed);
+        let mut offsets = vec![0u32];
+        let (mut destinations, mut weights) = (Vec::new(), Vec::new());
+        for r in 0..side as i64 {
+            for c in 0..side as i64 {
+                for (rr, cc) in [(r, c + 1), (r + 1, c), (r, c - 1), (r - 1, c)] {
+                    if (0..side as i64).contains(&rr) && (0..side as i64).contains(&cc) {
+                        destinations.push((rr * side as i64 + cc) as u32);
+                        weights.push(1.0 + rng.unit());
+                    }
+                }
+                offsets.push(destinations.len() as u32);
+            }
+        }
+        SparseGraph::from_csr(side * side, offsets, destinations, weights)
+    }
+
+    /// Distances agree and every predecessor edge is tight
+    fn assert_same_tree(graph: &SparseGraph, got: &SSSpResult, want: &SSSpResult, source: usize) {
+        for v in 0..graph.node_count {
+            let (d, w) = (got.distances[v], want.distances[v]);
+            assert_eq!(d.is_finite(), w.is_finite(), "reachability of {}", v);
+            if !w.is_finite() {
+                continue;
+            }
+            assert!((d - w).abs() <= 1e-9 * w.max(1.0), "node {}: {} vs {}", v, d, w);
+            if v != source {
+                let p = got.predecessors[v];
+                assert!(p >= 0, "node {} has no predecessor", v);
+                let tight = graph.edges(p as usize).any(|(t, c)| t == v && (got.distances[p as usize] + c - d).abs() <= 1e-9 * d.max(1.0));
+                assert!(tight, "predecessor {} of {} is not on a shortest path", p, v);
+            }
+        }
+    }
+
+    #[test]
+    fn hierarchical_matches_dijkstra_on_random_graphs() {
+        let mut rng = XorShift(0x9e3779b97f4a7c15);
+        let partitioners = [Partitioner::Index, Partitioner::Bfs, Partitioner::Multilevel];
+        for ((n, degree), partitioner) in [(50, 1), (400, 3), (1500, 4)].into_iter().flat_map(|g| partitioners.map(|p| (g, p))) {
+            let graph = random_graph(&mut rng, n, degree);
+            let mut solver = EnhancedSSSpSolver::new(graph.clone());
+            assert!(solver.preprocess(Some(partitioner)));
+            for _ in 0..5 {
+                let source = rng.below(n);
+                let got = solver.solve(source).unwrap();
+                assert_eq!(got.algorithm_used, "enhanced-sssp");
+                let want = solver.solve_dijkstra_optimized(source).unwrap();
+                assert_same_tree(&graph, &got, &want, source);
+            }
+        }
+    }
+
+    #[test]
+    fn bmssp_matches_dijkstra_on_random_graphs() {
+        let mut rng = XorShift(0x2545f4914f6cdd1d);
+        for (n, degree) in [(2, 1), (60, 1), (500, 2), (3000, 3)] {
+            let graph = random_graph(&mut rng, n, degree);
+            let mut solver = EnhancedSSSpSolver::new(graph.clone());
+            solver.set_algorithm(SsspAlgorithm::Bmssp);
+            for _ in 0..5 {
+                let source = rng.below(n);
+                let got = solver.solve(source).unwrap();
+                assert_eq!(got.algorithm_used, "bmssp");
+                let want = solver.solve_dijkstra_optimized(source).unwrap();
+                assert_same_tree(&graph, &got, &want, source);
+            }
+        }
+    }
+
+    /// cargo test --release bmssp_crossover -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bmssp_crossover_benchmark() {
+        let rows = bmssp_crossover(&[10, 100, 1000, 10000, 31623], 10, 10, 3);
+        println!("{:>10} {:>10} {:>12} {:>12} {:>7}", "nodes", "edges", "dijkstra_ms", "bmssp_ms", "ratio");
+        for [nodes, edges, dijkstra, bmssp] in rows {
+            println!("{:>10} {:>10} {:>12.3} {:>12.3} {:>7.2}", nodes, edges, dijkstra, bmssp, bmssp / dijkstra);
+        }
+    }
+
+    #[test]
+    fn overlay_covers_every_cross_cluster_edge() {
+        let graph = random_graph(&mut XorShift(7), 300, 3);
+        let mut solver = EnhancedSSSpSolver::new(graph.clone());
+        solver.preprocess(None);
+        let h = solver.hierarchical_decomposition.as_ref().unwrap();
+        for u in 0..graph.node_count {
+            for (v, _) in graph.edges(u) {
+                if h.cluster_assignment[u] != h.cluster_assignment[v] {
+                    assert!(h.overlay.index[u] != u32::MAX && h.overlay.index[v] != u32::MAX, "{} -> {}", u, v);
+                }
+            }
+        }
+    }
+
+    /// Path runs source..target over existing edges and sums to its distance
+    fn assert_valid_path(graph: &SparseGraph, got: &PathResult, source: usize, target: usize, want: f64) {
+        assert_eq!(got.distance.is_finite(), want.is_finite(), "{} reachability {} -> {}", got.algorithm_used, source, target);
+        if !want.is_finite() {
+            assert!(got.path.is_empty());
+            return;
+        }
+        assert!((got.distance - want).abs() <= 1e-9 * want.max(1.0), "{} {} -> {}: {} vs {}", got.algorithm_used, source, target, got.distance, want);
+        assert_eq!((got.path[0] as usize, *got.path.last().unwrap() as usize), (source, target));
+        let length: f64 = got.path.windows(2)
+            .map(|p| graph.edges(p[0] as usize).filter(|&(v, _)| v == p[1] as usize).map(|(_, w)| w).fold(f64::INFINITY, f64::min))
+            .sum();
+        assert!((length - want).abs() <= 1e-9 * want.max(1.0), "{} path length {} vs {}", got.algorithm_used, length, want);
+    }
+
+    #[test]
+    fn point_to_point_queries_match_dijkstra() {
+        let mut rng = XorShift(0x6a09e667f3bcc909);
+        for (n, degree) in [(1, 1), (40, 1), (500, 2), (2000, 3)] {
+            let graph = random_graph(&mut rng, n, degree);
+            let backward = EnhancedSSSpSolver::new(graph.reversed());
+            let mut solver = EnhancedSSSpSolver::new(graph.clone());
+            solver.set_landmark_count(4);
+            assert!(solver.preprocess(None));
+            for _ in 0..8 {
+                let (source, target) = (rng.below(n), rng.below(n));
+                let want = solver.solve_dijkstra_optimized(source).unwrap().distances[target];
+                // Exact distance-to-target, and an admissible but inconsistent shrink of it
+                let exact = backward.solve_dijkstra_optimized(target).unwrap().distances;
+                let shrunk: Vec<f64> = exact.iter().map(|&d| if d.is_finite() { d * rng.unit() } else { d }).collect();
+
+                for got in [
+                    solver.solve_to(source, target).unwrap(),
+                    solver.solve_to_alt(source, target).unwrap(),
+                    solver.solve_to_astar_slice(source, target, &vec![0.0; n]).unwrap(),
+                    solver.solve_to_astar_slice(source, target, &exact).unwrap(),
+                    solver.solve_to_astar_slice(source, target, &shrunk).unwrap(),
+                ] {
+                    assert_valid_path(&graph, &got, source, target, want);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn goal_directed_searches_stop_early() {
+        let side = 120;
+        let (graph, n) = (grid_graph(side, 0xbb67ae8584caa73b), side * side);
+        let mut solver = EnhancedSSSpSolver::new(graph);
+        assert!(solver.preprocess(Some(Partitioner::Bfs)));
+
+        let (source, target) = (30 * side + 30, 70 * side + 80);
+        let dijkstra = solver.solve_to_astar_slice(source, target, &vec![0.0; n]).unwrap();
+        let bidirectional = solver.solve_to(source, target).unwrap();
+        let alt = solver.solve_to_alt(source, target).unwrap();
+        assert!((bidirectional.distance - dijkstra.distance).abs() < 1e-9);
+        assert!((alt.distance - dijkstra.distance).abs() < 1e-9);
+        assert!((dijkstra.nodes_visited as usize) < n, "dijkstra {} n {}", dijkstra.nodes_visited, n);
+        assert!(
+            bidirectional.nodes_visited < dijkstra.nodes_visited,
+            "bidirectional {} dijkstra {}",
+            bidirectional.nodes_visited,
+            dijkstra.nodes_visited
+        );
+        assert!(alt.nodes_visited * 2 < dijkstra.nodes_visited, "alt {} dijkstra {}", alt.nodes_visited, dijkstra.nodes_visited);
+    }
+
+    #[test]
+    fn contraction_hierarchy_settles_few_nodes() {
+        let side = 60;
+        let (graph, n) = (grid_graph(side, 0xa54ff53a5f1d36f1), side * side);
+        let mut solver = EnhancedSSSpSolver::new(graph.clone());
+        solver.set_landmark_count(0);
+        solver.set_contraction_hierarchy(true);
+        assert!(solver.preprocess(Some(Partitioner::Index)));
+        assert!(solver.shortcut_count() > 0);
+
+        for (source, target) in [(15 * side + 15, 35 * side + 40), (0, n - 1), (side - 1, (side - 1) * side)] {
+            let dijkstra = solver.solve_to_astar_slice(source, target, &vec![0.0; n]).unwrap();
+            let bidirectional = solver.solve_to(source, target).unwrap();
+            let ch = solver.solve_to_ch(source, target).unwrap();
+            assert_valid_path(&graph, &ch, source, target, dijkstra.distance);
+            assert!(
+                ch.nodes_visited * 3 < bidirectional.nodes_visited,
+                "{} -> {}: ch {} bidirectional {} dijkstra {}",
+                source, target, ch.nodes_visited, bidirectional.nodes_visited, dijkstra.nodes_visited
+            );
+        }
+    }
+
+    #[test]
+    fn contraction_hierarchy_matches_dijkstra_on_random_graphs() {
+        let mut rng = XorShift(0x3c6ef372fe94f82b);
+        for (n, degree) in [(1, 1), (2, 1), (60, 1), (400, 2), (1500, 3)] {
+            let graph = random_graph(&mut rng, n, degree);
+            let mut solver = EnhancedSSSpSolver::new(graph.clone());
+            solver.set_landmark_count(0);
+            solver.set_contraction_hierarchy(true);
+            assert!(solver.preprocess(Some(Partitioner::Index)));
+            for _ in 0..10 {
+                let (source, target) = (rng.below(n), rng.below(n));
+                let want = solver.solve_dijkstra_optimized(source).unwrap().distances[target];
+                let got = solver.solve_to_ch(source, target).unwrap();
+                assert_eq!(got.algorithm_used, "contraction-hierarchy");
+                assert_valid_path(&graph, &got, source, target, want);
+            }
+        }
+    }
+
+    #[test]
+    fn contraction_benchmark_builds_the_hierarchy_before_timing() {
+        let mut rng = XorShift(0x510e527fade682d1);
+        let graph = random_graph(&mut rng, 800, 2);
+        let mut solver = EnhancedSSSpSolver::new(graph);
+        assert!(solver.reverse.get().is_none());
+        let ch = benchmark_contraction_hierarchy(&mut solver, 5, 4);
+        assert!(solver.reverse.get().is_some() && solver.contraction.is_some());
+        assert_eq!(ch.shortcuts, solver.shortcut_count());
+        for t in [ch.p2p_dijkstra_ms, ch.bidirectional_ms, ch.query_ms, ch.preprocess_ms] {
+            assert!(t.is_finite() && t >= 0.0, "{:?}", ch);
+        }
+        let slow = ContractionBenchmark { query_ms: ch.p2p_dijkstra_ms + 1.0, ..ch };
+        assert_eq!(slow.break_even_queries(), f64::INFINITY);
+        let fast = ContractionBenchmark { p2p_dijkstra_ms: 2.0, query_ms: 0.5, preprocess_ms: 30.0, ..ch };
+        assert_eq!(fast.break_even_queries(), 20.0);
+    }
+}
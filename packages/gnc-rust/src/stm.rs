@@ -0,0 +1,224 @@
+//! ID: WASM-STM-001
+//! Requirement: Propagate the 6×6 state transition matrix Φ(t, t0) alongside
+//!   the state by integrating the variational equations.
+//! Purpose: Provide the linear sensitivities needed for differential correction,
+//!   Lambert refinement and linearised covariance propagation.
+//! Rationale: dΦ/dt = A(t)·Φ with A = ∂f/∂x integrated in the same adaptive run
+//!   as the state is far cheaper and more accurate than finite differencing six
+//!   perturbed trajectories. For conservative gravity A = [[0, I], [∂a/∂r, 0]].
+//! Inputs: state [rx,ry,rz,vx,vy,vz] [m, m/s], tf [s], mu [m³/s²], atol, rtol
+//! Outputs: final state (6) and Φ(tf, 0) (36, row-major)
+//! Layout: augmented vector y = [x (6), Φ row-major (36)] — 42 elements
+//! References: Montenbruck & Gill "Satellite Orbits" §7.1–7.2;
+//!   Vallado §10.2 (variational equations)
+
+use wasm_bindgen::prelude::*;
+use js_sys::Float64Array;
+
+use crate::dop853::dop853_integrate;
+use crate::forces::{ForceModel, ForceModelBuilder, SpacecraftParams};
+use crate::rk4::State6;
+use crate::rk45::{state6_from_js, AdaptiveOptions, IntegrationError};
+
+pub type Mat3 = [[f64; 3]; 3];
+
+/// Point-mass gravity gradient ∂a/∂r = μ/r⁵ · (3 r rᵀ − r² I).
+pub(crate) fn two_body_gradient(r: &[f64], mu: f64) -> Mat3 {
+    let r2 = r[0] * r[0] + r[1] * r[1] + r[2] * r[2];
+    let rn = r2.sqrt();
+    let k = mu / (r2 * r2 * rn);
+    let mut g = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            g[i][j] = k * (3.0 * r[i] * r[j] - if i == j { r2 } else { 0.0 });
+        }
+    }
+    g
+}
+
+/// Right-hand side of the augmented state [x, Φ] for a velocity-independent
+/// acceleration `accel(t, x)` with position gradient `gradient(t, x)`.
+pub(crate) fn variational_rhs<Acc, Grad>(
+    mut accel: Acc,
+    mut gradient: Grad,
+) -> impl FnMut(f64, &[f64], &mut [f64])
+where
+    Acc: FnMut(f64, &[f64]) -> [f64; 3],
+    Grad: FnMut(f64, &[f64]) -> Mat3,
+{
+    move |t, y, dy| {
+        let a = accel(t, &y[..6]);
+        dy[..3].copy_from_slice(&y[3..6]);
+        dy[3..6].copy_from_slice(&a);
+
+        // Φ̇ = A·Φ with A = [[0, I], [G, 0]]: the top half of Φ̇ is the bottom
+        // half of Φ, the bottom half is G times the top half.
+        let g = gradient(t, &y[..6]);
+        let phi = &y[6..42];
+        for i in 0..3 {
+            for j in 0..6 {
+                dy[6 + 6 * i + j] = phi[6 * (i + 3) + j];
+                dy[6 + 6 * (i + 3) + j] =
+                    g[i][0] * phi[j] + g[i][1] * phi[6 + j] + g[i][2] * phi[12 + j];
+            }
+        }
+    }
+}
+
+/// Augmented initial vector [x0, I₆].
+pub(crate) fn augmented_identity(x0: &[f64]) -> Vec<f64> {
+    let mut y = vec![0.0; 42];
+    y[..6].copy_from_slice(&x0[..6]);
+    for i in 0..6 {
+        y[6 + 7 * i] = 1.0;
+    }
+    y
+}
+
+/// Propagate state and STM under point-mass gravity with DOP853.
+/// Returns the 42-element augmented vector at tf.
+pub fn two_body_stm(x0: &[f64], tf: f64, mu: f64, opts: &AdaptiveOptions) -> Result<Vec<f64>, IntegrationError> {
+    let accel = move |_t: f64, s: &[f64]| {
+        let r2 = s[0] * s[0] + s[1] * s[1] + s[2] * s[2];
+        let f = -mu / (r2 * r2.sqrt());
+        [f * s[0], f * s[1], f * s[2]]
+    };
+    let gradient = move |_t: f64, s: &[f64]| two_body_gradient(&s[..3], mu);
+    let y0 = augmented_identity(x0);
+    let (y, _) = dop853_integrate(variational_rhs(accel, gradient), 0.0, &y0, tf, opts, false, |_, _, _| {})?;
+    Ok(y)
+}
+
+/// Propagate state and STM under a force model with DOP853.
+/// The variational equations use the models' position gradients; contributions
+/// without one (e.g. velocity-dependent drag) are left out of Φ but not of x.
+pub fn model_stm<M: ForceModel + ?Sized>(
+    model: &M,
+    params: &SpacecraftParams,
+    x0: &[f64],
+    tf: f64,
+    opts: &AdaptiveOptions,
+) -> Result<Vec<f64>, IntegrationError> {
+    let as_state = |s: &[f64]| -> State6 { [s[0], s[1], s[2], s[3], s[4], s[5]] };
+    let accel = |t: f64, s: &[f64]| model.acceleration(t, &as_state(s), params);
+    let gradient = |t: f64, s: &[f64]| {
+        model.position_gradient(t, &as_state(s), params).unwrap_or([[0.0; 3]; 3])
+    };
+    let y0 = augmented_identity(x0);
+    let (y, _) = dop853_integrate(variational_rhs(accel, gradient), 0.0, &y0, tf, opts, false, |_, _, _| {})?;
+    Ok(y)
+}
+
+/// Final state and state transition matrix.
+#[wasm_bindgen]
+pub struct StmPropagation {
+    augmented: Vec<f64>,
+}
+
+impl StmPropagation {
+    pub(crate) fn new(augmented: Vec<f64>) -> Self {
+        StmPropagation { augmented }
+    }
+}
+
+#[wasm_bindgen]
+impl StmPropagation {
+    /// State at tf [m, m/s]
+    #[wasm_bindgen(getter)]
+    pub fn final_state(&self) -> Float64Array {
+        Float64Array::from(&self.augmented[..6])
+    }
+
+    /// Φ(tf, 0), 36 elements row-major
+    #[wasm_bindgen(getter)]
+    pub fn stm(&self) -> Float64Array {
+        Float64Array::from(&self.augmented[6..42])
+    }
+}
+
+/// Propagate a two-body state and its 6×6 STM from t = 0 to `tf` (DOP853).
+#[wasm_bindgen]
+pub fn stm_propagate(
+    state_js: &Float64Array,
+    tf: f64,
+    mu: f64,
+    atol: f64,
+    rtol: f64,
+) -> Result<StmPropagation, JsValue> {
+    let x0 = state6_from_js(state_js)?;
+    let y = two_body_stm(&x0, tf, mu, &AdaptiveOptions::new(atol, rtol))?;
+    Ok(StmPropagation::new(y))
+}
+
+/// stm_propagate under a ForceModelBuilder's dynamics.
+#[wasm_bindgen]
+pub fn stm_propagate_model(
+    state_js: &Float64Array,
+    tf: f64,
+    model: &ForceModelBuilder,
+    atol: f64,
+    rtol: f64,
+) -> Result<StmPropagation, JsValue> {
+    let x0 = state6_from_js(state_js)?;
+    let y = model_stm(model.model(), model.params(), &x0, tf, &AdaptiveOptions::new(atol, rtol))?;
+    Ok(StmPropagation::new(y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rk45::two_body_rhs;
+
+    const MU: f64 = 3.986004418e14;
+
+    fn propagate(x0: &[f64], tf: f64) -> Vec<f64> {
+        let opts = AdaptiveOptions::new(1e-9, 1e-13);
+        dop853_integrate(two_body_rhs(MU), 0.0, x0, tf, &opts, false, |_, _, _| {}).unwrap().0
+    }
+
+    #[test]
+    fn stm_matches_central_differences() {
+        let x0 = [7.0e6, 5.0e5, 1.0e5, -300.0, 7300.0, 1500.0];
+        let tf = 2500.0;
+        let y = two_body_stm(&x0, tf, MU, &AdaptiveOptions::new(1e-9, 1e-13)).unwrap();
+
+        for j in 0..6 {
+            let step = if j < 3 { 1.0 } else { 1e-3 };
+            let mut xp = x0;
+            let mut xm = x0;
+            xp[j] += step;
+            xm[j] -= step;
+            let (fp, fm) = (propagate(&xp, tf), propagate(&xm, tf));
+            for i in 0..6 {
+                let fd = (fp[i] - fm[i]) / (2.0 * step);
+                let an = y[6 + 6 * i + j];
+                assert!((fd - an).abs() < 1e-5 * an.abs().max(1.0), "Φ[{}][{}]: {} vs {}", i, j, an, fd);
+            }
+        }
+    }
+
+    #[test]
+    fn stm_is_volume_preserving() {
+        // Hamiltonian flow: det Φ = 1
+        let x0 = [8.0e6, 0.0, 0.0, 0.0, 6500.0, 2000.0];
+        let y = two_body_stm(&x0, 6000.0, MU, &AdaptiveOptions::new(1e-9, 1e-13)).unwrap();
+        let mut m: Vec<Vec<f64>> = (0..6).map(|i| y[6 + 6 * i..12 + 6 * i].to_vec()).collect();
+        let mut det = 1.0;
+        for c in 0..6 {
+            let p = (c..6).max_by(|&a, &b| m[a][c].abs().partial_cmp(&m[b][c].abs()).unwrap()).unwrap();
+            if p != c {
+                m.swap(p, c);
+                det = -det;
+            }
+            det *= m[c][c];
+            let pivot = m[c].clone();
+            for row in m.iter_mut().skip(c + 1) {
+                let f = row[c] / pivot[c];
+                for (x, p) in row.iter_mut().zip(&pivot).skip(c) {
+                    *x -= f * p;
+                }
+            }
+        }
+        assert!((det - 1.0).abs() < 1e-6, "det Φ = {}", det);
+    }
+}
@@ -0,0 +1,544 @@
+//! ID: WASM-TIME-001
+//! Requirement: Represent absolute epochs and convert between UTC (with leap
+//!   seconds), TAI, TT, TDB and GPS time, Julian Date / MJD and ISO-8601 text.
+//! Purpose: Line propagation up with ephemerides, sensor timestamps and mission
+//!   timelines instead of bare seconds from an unspecified t = 0.
+//! Rationale: An epoch is stored as whole TAI seconds plus a fraction, so leap
+//!   seconds and sub-nanosecond offsets survive arithmetic over decades. Every
+//!   other scale is a fixed or slowly varying offset from TAI: TT = TAI + 32.184 s,
+//!   GPS = TAI − 19 s, UTC = TAI − ΔAT, TDB = TT + periodic terms (≤ 1.7 ms).
+//! Inputs: calendar fields, ISO-8601 strings, JD/MJD, GPS week + seconds
+//! Outputs: the same representations on any supported scale
+//! Assumptions: ΔAT table current through 2017-01-01 (37 s; no leap second has
+//!   been announced since); UTC before 1972 is treated as TAI − 10 s.
+//! References: IERS Bulletin C; USNO Circular 179 eq. 2.6 (Fairhead &
+//!   Bretagnon TDB − TT); IS-GPS-200 §3.3.4; ISO 8601-1:2019
+
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+
+use crate::constants::{DAYS_PER_CENTURY, JD_J2000};
+
+/// Time scales an epoch can be expressed in.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    Utc = 0,
+    Tai = 1,
+    Tt = 2,
+    Tdb = 3,
+    Gps = 4,
+}
+
+impl TimeScale {
+    fn label(self) -> &'static str {
+        match self {
+            TimeScale::Utc => "UTC",
+            TimeScale::Tai => "TAI",
+            TimeScale::Tt => "TT",
+            TimeScale::Tdb => "TDB",
+            TimeScale::Gps => "GPS",
+        }
+    }
+}
+
+const TT_MINUS_TAI: f64 = 32.184;
+const TAI_MINUS_GPS: i64 = 19;
+/// MJD of 1970-01-01
+const MJD_UNIX: i64 = 40587;
+/// MJD of 2000-01-01 (the J2000 reference is noon of this day)
+const MJD_2000: i64 = 51544;
+const SECONDS_PER_WEEK: i64 = 604800;
+
+/// ΔAT = TAI − UTC from the given UTC date onward (IERS Bulletin C).
+const LEAP_SECONDS: [(i64, i64, i64); 28] = [
+    (1972, 1, 10), (1972, 7, 11), (1973, 1, 12), (1974, 1, 13), (1975, 1, 14),
+    (1976, 1, 15), (1977, 1, 16), (1978, 1, 17), (1979, 1, 18), (1980, 1, 19),
+    (1981, 7, 20), (1982, 7, 21), (1983, 7, 22), (1985, 7, 23), (1988, 1, 24),
+    (1990, 1, 25), (1991, 1, 26), (1992, 7, 27), (1993, 7, 28), (1994, 7, 29),
+    (1996, 1, 30), (1997, 7, 31), (1999, 1, 32), (2006, 1, 33), (2009, 1, 34),
+    (2012, 7, 35), (2015, 7, 36), (2017, 1, 37),
+];
+
+/// Days from 1970-01-01 to a proleptic Gregorian date (Hinnant's algorithm).
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Inverse of days_from_civil.
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    (if m <= 2 { yoe + era * 400 + 1 } else { yoe + era * 400 }, m, d)
+}
+
+fn days_in_month(y: i64, m: i64) -> i64 {
+    days_from_civil(if m == 12 { y + 1 } else { y }, if m == 12 { 1 } else { m + 1 }, 1) - days_from_civil(y, m, 1)
+}
+
+/// ΔAT in force on the UTC day with the given MJD.
+fn delta_at_for_utc_day(mjd: i64) -> i64 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|&&(y, m, _)| days_from_civil(y, m, 1) + MJD_UNIX <= mjd)
+        .map_or(10, |e| e.2)
+}
+
+/// ΔAT at a TAI instant (whole seconds since the reference), and whether the
+/// instant falls inside an inserted leap second (23:59:60).
+fn delta_at_for_tai(tai: i64) -> (i64, bool) {
+    for &(y, m, dat) in LEAP_SECONDS.iter().rev() {
+        let start = (days_from_civil(y, m, 1) + MJD_UNIX - MJD_2000) * 86400 - 43200 + dat;
+        if tai >= start {
+            return (dat, false);
+        }
+        if tai == start - 1 {
+            return (dat - 1, true);
+        }
+    }
+    (10, false)
+}
+
+/// TDB − TT [s] (Fairhead & Bretagnon, ≈10 µs over 1600–2200).
+pub fn tdb_minus_tt(jd_tt: f64) -> f64 {
+    let t = (jd_tt - JD_J2000) / DAYS_PER_CENTURY;
+    0.001657 * (628.3076 * t + 6.2401).sin()
+        + 0.000022 * (575.3385 * t + 4.2970).sin()
+        + 0.000014 * (1256.6152 * t + 6.1969).sin()
+        + 0.000005 * (606.9777 * t + 4.0212).sin()
+        + 0.000005 * (52.9691 * t + 0.4444).sin()
+        + 0.000002 * (21.3299 * t + 5.5431).sin()
+        + 0.000010 * t * (628.3076 * t + 4.2490).sin()
+}
+
+/// An instant in time.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Epoch {
+    /// Whole TAI seconds since 2000-01-01T12:00:00 TAI
+    tai: i64,
+    /// Fraction of a second in [0, 1)
+    frac: f64,
+}
+
+impl Default for Epoch {
+    /// J2000.0 (2000-01-01T12:00:00 TT)
+    fn default() -> Self {
+        Epoch::from_seconds_since_j2000(0.0, TimeScale::Tt)
+    }
+}
+
+impl Epoch {
+    fn normalized(tai: i64, frac: f64) -> Self {
+        let whole = frac.floor();
+        Epoch { tai: tai + whole as i64, frac: frac - whole }
+    }
+
+    /// TAI seconds since the reference, as one float (loses sub-µs at decades).
+    fn tai_seconds(&self) -> f64 {
+        self.tai as f64 + self.frac
+    }
+
+    /// Offset of `scale` from TAI at this instant [s].
+    fn offset(&self, scale: TimeScale) -> f64 {
+        match scale {
+            TimeScale::Tai => 0.0,
+            TimeScale::Tt => TT_MINUS_TAI,
+            TimeScale::Gps => -(TAI_MINUS_GPS as f64),
+            TimeScale::Utc => -(delta_at_for_tai(self.tai).0 as f64),
+            TimeScale::Tdb => {
+                let jd_tt = JD_J2000 + (self.tai_seconds() + TT_MINUS_TAI) / 86400.0;
+                TT_MINUS_TAI + tdb_minus_tt(jd_tt)
+            }
+        }
+    }
+
+    /// Calendar date and time on the UTC scale; seconds reach 60.x inside a leap second.
+    pub fn from_utc(year: i64, month: i64, day: i64, hour: i64, minute: i64, second: f64) -> Result<Self, String> {
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err(format!("invalid date {:04}-{:02}-{:02}", year, month, day));
+        }
+        if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0.0..61.0).contains(&second) {
+            return Err(format!("invalid time {:02}:{:02}:{}", hour, minute, second));
+        }
+        let mjd = days_from_civil(year, month, day) + MJD_UNIX;
+        let whole = second.floor();
+        let tai = (mjd - MJD_2000) * 86400 - 43200 + hour * 3600 + minute * 60 + whole as i64 + delta_at_for_utc_day(mjd);
+        let epoch = Self::normalized(tai, second - whole);
+        if second >= 60.0 && !delta_at_for_tai(epoch.tai).1 {
+            return Err(format!("{:04}-{:02}-{:02} has no leap second", year, month, day));
+        }
+        Ok(epoch)
+    }
+
+    /// Seconds since JD 2451545.0 on `scale` (for UTC, leap seconds are not counted).
+    pub fn from_seconds_since_j2000(seconds: f64, scale: TimeScale) -> Self {
+        let whole = seconds.floor();
+        let (s, f) = (whole as i64, seconds - whole);
+        match scale {
+            TimeScale::Tai => Self::normalized(s, f),
+            TimeScale::Tt => Self::normalized(s, f - TT_MINUS_TAI),
+            TimeScale::Gps => Self::normalized(s + TAI_MINUS_GPS, f),
+            TimeScale::Utc => {
+                let mjd = (s + 43200).div_euclid(86400) + MJD_2000;
+                Self::normalized(s + delta_at_for_utc_day(mjd), f)
+            }
+            TimeScale::Tdb => {
+                // TDB − TT varies by < 1e-9 over its own size, so two passes suffice
+                let mut e = Self::normalized(s, f - TT_MINUS_TAI);
+                for _ in 0..2 {
+                    let jd_tt = JD_J2000 + (e.tai_seconds() + TT_MINUS_TAI) / 86400.0;
+                    e = Self::normalized(s, f - TT_MINUS_TAI - tdb_minus_tt(jd_tt));
+                }
+                e
+            }
+        }
+    }
+
+    /// Julian date on `scale`.
+    pub fn from_jd(jd: f64, scale: TimeScale) -> Self {
+        Self::from_seconds_since_j2000((jd - JD_J2000) * 86400.0, scale)
+    }
+
+    /// GPS week number (continuous, no rollover) and seconds of week.
+    pub fn from_gps(week: i64, seconds_of_week: f64) -> Self {
+        Self::gps_epoch().add_seconds(week as f64 * SECONDS_PER_WEEK as f64 + seconds_of_week)
+    }
+
+    fn gps_epoch() -> Self {
+        // 1980-01-06T00:00:00 UTC, when ΔAT was 19 s
+        let mjd = days_from_civil(1980, 1, 6) + MJD_UNIX;
+        Epoch { tai: (mjd - MJD_2000) * 86400 - 43200 + TAI_MINUS_GPS, frac: 0.0 }
+    }
+
+    /// Parse ISO-8601 `YYYY-MM-DD[Thh:mm[:ss[.f]]][Z|±hh:mm]`, optionally followed
+    /// by a scale name (`UTC`, `TAI`, `TT`, `TDB`, `GPS`); UTC when none is given.
+    pub fn parse_iso(text: &str) -> Result<Self, String> {
+        let bad = || format!("invalid ISO-8601 epoch '{}'", text);
+        let mut parts = text.split_whitespace();
+        let stamp = parts.next().ok_or_else(bad)?;
+        let scale = match parts.next() {
+            None | Some("UTC") => TimeScale::Utc,
+            Some("TAI") => TimeScale::Tai,
+            Some("TT") => TimeScale::Tt,
+            Some("TDB") => TimeScale::Tdb,
+            Some("GPS") => TimeScale::Gps,
+            Some(_) => return Err(bad()),
+        };
+        if parts.next().is_some() {
+            return Err(bad());
+        }
+
+        let (date, time) = stamp.split_once(['T', 't']).unwrap_or((stamp, ""));
+        let num = |s: &str| s.parse::<i64>().map_err(|_| bad());
+        let d: Vec<&str> = date.split('-').collect();
+        if d.len() != 3 || d[0].len() != 4 || d[1].len() != 2 || d[2].len() != 2 {
+            return Err(bad());
+        }
+        let (year, month, day) = (num(d[0])?, num(d[1])?, num(d[2])?);
+
+        let (clock, zone_minutes) = if let Some(c) = time.strip_suffix(['Z', 'z']) {
+            (c, 0)
+        } else if let Some(i) = time.find(['+', '-']) {
+            let (c, z) = time.split_at(i);
+            let sign = if z.starts_with('-') { -1 } else { 1 };
+            let (zh, zm) = z[1..].split_once(':').unwrap_or((&z[1..], "0"));
+            (c, sign * (num(zh)? * 60 + num(zm)?))
+        } else {
+            (time, 0)
+        };
+        if zone_minutes != 0 && scale != TimeScale::Utc {
+            return Err(bad());
+        }
+        let c: Vec<&str> = if clock.is_empty() { vec![] } else { clock.split(':').collect() };
+        if c.len() == 1 || c.len() > 3 {
+            return Err(bad());
+        }
+        let hour = c.first().map_or(Ok(0), |s| num(s))?;
+        let minute = c.get(1).map_or(Ok(0), |s| num(s))?;
+        let second = c.get(2).map_or(Ok(0.0), |s| s.parse::<f64>().map_err(|_| bad()))?;
+
+        let epoch = if scale == TimeScale::Utc {
+            Self::from_utc(year, month, day, hour, minute, second)?
+        } else {
+            // Validate the calendar fields via the UTC path, then reinterpret them
+            Self::from_utc(year, month, day, hour, minute, second.min(59.999))?;
+            let days = days_from_civil(year, month, day) + MJD_UNIX - MJD_2000;
+            let s = (days * 86400 - 43200 + hour * 3600 + minute * 60) as f64 + second;
+            Self::from_seconds_since_j2000(s, scale)
+        };
+        Ok(epoch.add_seconds(-(zone_minutes as f64) * 60.0))
+    }
+
+    /// This epoch shifted by `seconds` of SI time.
+    pub fn add_seconds(&self, seconds: f64) -> Self {
+        let whole = seconds.trunc();
+        Self::normalized(self.tai + whole as i64, self.frac + (seconds - whole))
+    }
+
+    /// Elapsed SI seconds from `other` to this epoch.
+    pub fn seconds_since(&self, other: &Epoch) -> f64 {
+        (self.tai - other.tai) as f64 + (self.frac - other.frac)
+    }
+
+    /// Seconds since JD 2451545.0 on `scale`.
+    pub fn seconds_since_j2000(&self, scale: TimeScale) -> f64 {
+        self.tai_seconds() + self.offset(scale)
+    }
+
+    /// Julian date on `scale`.
+    pub fn jd(&self, scale: TimeScale) -> f64 {
+        JD_J2000 + self.seconds_since_j2000(scale) / 86400.0
+    }
+
+    /// Modified Julian date on `scale`.
+    pub fn mjd(&self, scale: TimeScale) -> f64 {
+        (MJD_2000 as f64 + 0.5) + self.seconds_since_j2000(scale) / 86400.0
+    }
+
+    /// TAI − UTC [s] at this instant.
+    pub fn leap_seconds(&self) -> i64 {
+        delta_at_for_tai(self.tai).0
+    }
+
+    /// GPS (week, seconds of week), weeks counted continuously from 1980-01-06.
+    pub fn gps_week_seconds(&self) -> (i64, f64) {
+        let gps = self.tai - Self::gps_epoch().tai;
+        (gps.div_euclid(SECONDS_PER_WEEK), gps.rem_euclid(SECONDS_PER_WEEK) as f64 + self.frac)
+    }
+
+    /// Calendar fields on `scale`: (year, month, day, hour, minute, second).
+    pub fn calendar(&self, scale: TimeScale) -> (i64, i64, i64, i64, i64, f64) {
+        let (mut whole, frac, leap) = match scale {
+            TimeScale::Utc => {
+                let (dat, leap) = delta_at_for_tai(self.tai);
+                (self.tai - dat, self.frac, leap)
+            }
+            _ => {
+                let s = self.seconds_since_j2000(scale);
+                let w = s.floor();
+                (w as i64, s - w, false)
+            }
+        };
+        if leap {
+            // Show the inserted second as 23:59:60 of the previous day
+            whole -= 1;
+        }
+        let since_midnight = whole + 43200;
+        let days = since_midnight.div_euclid(86400);
+        let mut sod = since_midnight.rem_euclid(86400);
+        if leap {
+            sod += 1;
+        }
+        let (y, mo, d) = civil_from_days(days + MJD_2000 - MJD_UNIX);
+        let h = (sod / 3600).min(23);
+        let mi = ((sod - h * 3600) / 60).min(59);
+        let s = (sod - h * 3600 - mi * 60) as f64 + frac;
+        (y, mo, d, h, mi, s)
+    }
+
+    /// ISO-8601 text on `scale` with `decimals` fractional digits (0–9). UTC ends
+    /// in `Z`; other scales are suffixed with their name, e.g. `… TDB`.
+    pub fn to_iso_string(&self, scale: TimeScale, decimals: u32) -> String {
+        let decimals = decimals.min(9);
+        let unit = 10f64.powi(decimals as i32);
+        // Round first so carries propagate through the calendar
+        let mut rounded = *self;
+        let ticks = (self.frac * unit).round();
+        rounded.frac = 0.0;
+        let rounded = rounded.add_seconds(ticks / unit);
+        let (y, mo, d, h, mi, s) = rounded.calendar(scale);
+        let whole = s.floor();
+        let mut out = format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", y, mo, d, h, mi, whole as i64);
+        if decimals > 0 {
+            let f = ((s - whole) * unit).round() as u64;
+            out.push_str(&format!(".{:0width$}", f.min(unit as u64 - 1), width = decimals as usize));
+        }
+        match scale {
+            TimeScale::Utc => out.push('Z'),
+            other => {
+                out.push(' ');
+                out.push_str(other.label());
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for Epoch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_iso_string(TimeScale::Utc, 3))
+    }
+}
+
+#[wasm_bindgen]
+impl Epoch {
+    /// J2000.0 (2000-01-01T12:00:00 TT)
+    #[wasm_bindgen]
+    pub fn j2000() -> Epoch {
+        Epoch::default()
+    }
+
+    /// Parse ISO-8601 text, e.g. "2024-03-10T08:30:00Z" or "2000-01-01T12:00:00 TT"
+    #[wasm_bindgen]
+    pub fn from_iso(text: &str) -> Result<Epoch, JsValue> {
+        Epoch::parse_iso(text).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Julian date on the given scale
+    #[wasm_bindgen]
+    pub fn from_julian_date(jd: f64, scale: TimeScale) -> Epoch {
+        Epoch::from_jd(jd, scale)
+    }
+
+    /// Modified Julian date on the given scale
+    #[wasm_bindgen]
+    pub fn from_mjd(mjd: f64, scale: TimeScale) -> Epoch {
+        Epoch::from_jd(mjd + 2400000.5, scale)
+    }
+
+    /// GPS week (continuous) and seconds of week
+    #[wasm_bindgen]
+    pub fn from_gps_week(week: i32, seconds_of_week: f64) -> Epoch {
+        Epoch::from_gps(week as i64, seconds_of_week)
+    }
+
+    /// Seconds since J2000 on the given scale
+    #[wasm_bindgen]
+    pub fn from_j2000_seconds(seconds: f64, scale: TimeScale) -> Epoch {
+        Epoch::from_seconds_since_j2000(seconds, scale)
+    }
+
+    /// ISO-8601 text with millisecond precision
+    #[wasm_bindgen]
+    pub fn to_iso(&self, scale: TimeScale) -> String {
+        self.to_iso_string(scale, 3)
+    }
+
+    /// Julian date on the given scale
+    #[wasm_bindgen]
+    pub fn julian_date(&self, scale: TimeScale) -> f64 {
+        self.jd(scale)
+    }
+
+    /// Modified Julian date on the given scale
+    #[wasm_bindgen]
+    pub fn modified_julian_date(&self, scale: TimeScale) -> f64 {
+        self.mjd(scale)
+    }
+
+    /// Seconds since J2000 on the given scale
+    #[wasm_bindgen]
+    pub fn j2000_seconds(&self, scale: TimeScale) -> f64 {
+        self.seconds_since_j2000(scale)
+    }
+
+    /// GPS week number (continuous)
+    #[wasm_bindgen(getter)]
+    pub fn gps_week(&self) -> i32 {
+        self.gps_week_seconds().0 as i32
+    }
+
+    /// GPS seconds of week
+    #[wasm_bindgen(getter)]
+    pub fn gps_seconds_of_week(&self) -> f64 {
+        self.gps_week_seconds().1
+    }
+
+    /// TAI − UTC [s]
+    #[wasm_bindgen(getter)]
+    pub fn tai_minus_utc(&self) -> i32 {
+        self.leap_seconds() as i32
+    }
+
+    /// New epoch `seconds` later
+    #[wasm_bindgen]
+    pub fn plus_seconds(&self, seconds: f64) -> Epoch {
+        self.add_seconds(seconds)
+    }
+
+    /// Elapsed seconds since `other`
+    #[wasm_bindgen]
+    pub fn seconds_after(&self, other: &Epoch) -> f64 {
+        self.seconds_since(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iso(s: &str) -> Epoch {
+        Epoch::parse_iso(s).unwrap()
+    }
+
+    #[test]
+    fn leap_seconds_are_counted_and_formatted() {
+        let before = iso("2016-12-31T23:59:59Z");
+        let leap = iso("2016-12-31T23:59:60.5Z");
+        let after = iso("2017-01-01T00:00:00Z");
+        assert_eq!(after.seconds_since(&before), 2.0);
+        assert_eq!(leap.seconds_since(&before), 1.5);
+        assert_eq!(leap.to_string(), "2016-12-31T23:59:60.500Z");
+        assert_eq!((before.leap_seconds(), after.leap_seconds()), (36, 37));
+        assert!(Epoch::parse_iso("2016-12-30T23:59:60Z").is_err());
+        // Rounding carries into the next day
+        assert_eq!(iso("2019-12-31T23:59:59.9996Z").to_string(), "2020-01-01T00:00:00.000Z");
+    }
+
+    #[test]
+    fn scale_offsets_match_definitions() {
+        let j2000 = Epoch::j2000();
+        assert_eq!(j2000.jd(TimeScale::Tt), 2451545.0);
+        assert_eq!(j2000.to_iso_string(TimeScale::Utc, 3), "2000-01-01T11:58:55.816Z");
+        assert_eq!(j2000.to_iso_string(TimeScale::Tai, 3), "2000-01-01T11:59:27.816 TAI");
+        assert_eq!(iso("2000-01-01T12:00:00 TT"), j2000);
+        assert!((j2000.mjd(TimeScale::Tt) - 51544.5).abs() < 1e-12);
+
+        // 2017-01-01 is a Sunday: GPS week 1930, 18 s into the week
+        let (week, sow) = iso("2017-01-01T00:00:00Z").gps_week_seconds();
+        assert_eq!((week, sow), (1930, 18.0));
+        assert_eq!(Epoch::from_gps(1930, 18.0), iso("2017-01-01T00:00:00Z"));
+
+        let mut peak: f64 = 0.0;
+        for day in 0..366 {
+            let e = j2000.add_seconds(day as f64 * 86400.0);
+            let d = e.seconds_since_j2000(TimeScale::Tdb) - e.seconds_since_j2000(TimeScale::Tt);
+            assert!(d.abs() < 1.7e-3);
+            peak = peak.max(d.abs());
+            let back = Epoch::from_seconds_since_j2000(e.seconds_since_j2000(TimeScale::Tdb), TimeScale::Tdb);
+            // Round trip through one f64 of ~3e7 s: a few ulps
+            assert!(back.seconds_since(&e).abs() < 2e-8);
+        }
+        assert!(peak > 1.6e-3);
+    }
+
+    #[test]
+    fn iso_parsing_handles_zones_and_errors() {
+        assert_eq!(iso("2024-03-10T08:30:00+05:30"), iso("2024-03-10T03:00:00Z"));
+        assert_eq!(iso("2024-03-10T03:00-01:00"), iso("2024-03-10T04:00:00Z"));
+        assert_eq!(iso("2024-03-10"), iso("2024-03-10T00:00:00.000Z"));
+        let e = iso("2024-02-29T06:07:08.25 GPS");
+        assert_eq!(e.to_iso_string(TimeScale::Gps, 2), "2024-02-29T06:07:08.25 GPS");
+        assert_eq!(e.to_iso_string(TimeScale::Utc, 2), "2024-02-29T06:06:50.25Z");
+        for bad in ["2023-02-29", "2024-13-01T00:00Z", "24-01-01", "2024-01-01T25:00Z", "2024-01-01 XYZ", ""] {
+            assert!(Epoch::parse_iso(bad).is_err(), "{}", bad);
+        }
+        let jd = Epoch::from_jd(2460000.5, TimeScale::Utc);
+        assert_eq!(jd.to_string(), "2023-02-25T00:00:00.000Z");
+    }
+}